@@ -1,5 +1,7 @@
 mod codie_parser;
 mod routes;
+mod sessions;
+mod sinks;
 mod subprocess;
 
 use std::path::PathBuf;
@@ -10,6 +12,8 @@ use axum::Router;
 use tower_http::services::ServeDir;
 
 use codie_parser::Program;
+use routes::surface::ShelfManifest;
+use sinks::{MongoSink, SinkFilter, SinkPipeline, WebhookSink};
 
 pub struct AppState {
     pub cage_root: PathBuf,
@@ -17,6 +21,8 @@ pub struct AppState {
     pub tree_path: PathBuf,
     pub codie_dir: PathBuf,
     pub codie_programs: RwLock<Vec<Program>>,
+    pub shelf: ShelfManifest,
+    pub sinks: SinkPipeline,
 }
 
 #[tokio::main]
@@ -29,12 +35,23 @@ async fn main() {
             PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
         });
 
+    let shelf = routes::surface::load_shelf_manifest(&cage_root.join("gentlyos/shelf.json"));
+    let store_js = cage_root.join("mongodb/store.js");
+
+    let mut sinks = SinkPipeline::new();
+    sinks.register(Box::new(MongoSink::new(store_js.clone())), SinkFilter::All);
+    if let Ok(webhook_url) = std::env::var("CAGE_EVENT_WEBHOOK") {
+        sinks.register(Box::new(WebhookSink::new(webhook_url)), SinkFilter::All);
+    }
+
     let state = Arc::new(AppState {
-        store_js: cage_root.join("mongodb/store.js"),
+        store_js,
         tree_path: cage_root.join("gentlyos/tree.json"),
         codie_dir: cage_root.join("projects/Gently-nix/tools/codie-maps"),
         cage_root,
         codie_programs: RwLock::new(Vec::new()),
+        shelf,
+        sinks,
     });
 
     // CLI mode: --seed-codie
@@ -68,6 +85,7 @@ async fn main() {
         .merge(routes::sessions::router())
         .merge(routes::gentlyos::router())
         .merge(routes::codie::router())
+        .merge(routes::metrics::router())
         .nest_service("/static", ServeDir::new(static_dir))
         .with_state(state);
 
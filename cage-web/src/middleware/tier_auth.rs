@@ -1,19 +1,35 @@
 //! Tier Auth Middleware -- extracts layer/tier from requests.
 //!
 //! Resolution order:
-//!   1. X-Gently-Tier header
-//!   2. gently_tier cookie
-//!   3. ?tier= query parameter
+//!   1. X-Gently-Tier header, carrying a signed token
+//!   2. gently_tier cookie, carrying a signed token
+//!   3. ?tier= query parameter, carrying a signed token
 //!   4. Default: User (L5)
 //!
+//! A signed token is `base64(payload).base64(hmac_sha256(key, payload))`,
+//! where `payload` is `tier|issued_at|expires_at` (both timestamps unix
+//! seconds). `TierAuth` recomputes the HMAC over the decoded payload and
+//! only honors the claimed tier if the signature matches and `expires_at`
+//! hasn't passed - any verification failure resolves to `Layer::User`, the
+//! same as sending nothing at all. Bare unsigned tier strings (the old
+//! plaintext behavior) are only accepted when `insecure_trust_headers` is
+//! explicitly enabled, for local dev.
+//!
 //! The resolved Layer is injected into request extensions so handlers
 //! can call `req.extensions().get::<Layer>()`.
 
 use axum::{
-    extract::Request,
+    extract::{Request, State},
     middleware::Next,
     response::Response,
 };
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type HmacSha256 = Hmac<Sha256>;
 
 /// Dashboard visibility layer. L0 = highest privilege, L5 = lowest.
 /// Mirrors gently-core::layer::Layer but kept local to avoid workspace coupling.
@@ -83,19 +99,138 @@ impl Layer {
     }
 }
 
+/// Configuration for the tier-auth middleware: the HMAC key signed tokens
+/// are verified against, and whether the legacy unsigned header/cookie/query
+/// path is still honored.
+pub struct TierAuth {
+    secret: Vec<u8>,
+    insecure_trust_headers: bool,
+}
+
+impl TierAuth {
+    /// Construct with the HMAC key used to verify signed tier tokens.
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            secret: secret.into(),
+            insecure_trust_headers: false,
+        }
+    }
+
+    /// Trust bare, unsigned `X-Gently-Tier`/`gently_tier`/`?tier=` claims as
+    /// if they were verified. Only meant for local dev.
+    pub fn insecure_trust_headers(mut self, enabled: bool) -> Self {
+        self.insecure_trust_headers = enabled;
+        self
+    }
+
+    /// Sign `tier` into a token valid from now for `ttl_secs` seconds.
+    pub fn issue(&self, tier: &str, ttl_secs: u64) -> String {
+        let issued_at = now();
+        let expires_at = issued_at + ttl_secs;
+        let payload = format!("{tier}|{issued_at}|{expires_at}");
+        let mac = self.sign(payload.as_bytes());
+        format!("{}.{}", STANDARD.encode(payload), STANDARD.encode(mac))
+    }
+
+    /// Verify a signed token, returning the claimed tier if the signature
+    /// matches and it hasn't expired.
+    fn verify(&self, token: &str) -> Option<String> {
+        let (payload_b64, mac_b64) = token.split_once('.')?;
+        let payload = STANDARD.decode(payload_b64).ok()?;
+        let expected_mac = STANDARD.decode(mac_b64).ok()?;
+
+        let mut mac = HmacSha256::new_from_slice(&self.secret).expect("HMAC can take key of any size");
+        mac.update(&payload);
+        mac.verify_slice(&expected_mac).ok()?;
+
+        let payload = String::from_utf8(payload).ok()?;
+        let mut parts = payload.splitn(3, '|');
+        let tier = parts.next()?;
+        let _issued_at: u64 = parts.next()?.parse().ok()?;
+        let expires_at: u64 = parts.next()?.parse().ok()?;
+
+        if now() > expires_at {
+            return None;
+        }
+
+        Some(tier.to_lowercase())
+    }
+
+    fn sign(&self, payload: &[u8]) -> Vec<u8> {
+        let mut mac = HmacSha256::new_from_slice(&self.secret).expect("HMAC can take key of any size");
+        mac.update(payload);
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    /// Resolve the `Layer` for a request: verify a signed token if one is
+    /// present, otherwise fall back to the insecure plaintext path if
+    /// enabled. Any failure to verify resolves to `Layer::User`.
+    fn resolve(&self, req: &Request) -> Layer {
+        let Some(claim) = extract_claim(req) else {
+            return Layer::User;
+        };
+
+        if let Some(tier) = self.verify(&claim) {
+            return Layer::from_tier(&tier);
+        }
+
+        if self.insecure_trust_headers {
+            return Layer::from_tier(&claim.to_lowercase());
+        }
+
+        Layer::User
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
 /// Axum middleware function that resolves the tier and injects Layer.
-pub async fn tier_auth(mut req: Request, next: Next) -> Response {
-    let tier = extract_tier(&req);
-    let layer = Layer::from_tier(&tier);
+/// Register with `axum::middleware::from_fn_with_state(tier_auth.clone(), tier_auth_mw)`.
+pub async fn tier_auth_mw(State(auth): State<Arc<TierAuth>>, mut req: Request, next: Next) -> Response {
+    let layer = auth.resolve(&req);
     req.extensions_mut().insert(layer);
     next.run(req).await
 }
 
-fn extract_tier(req: &Request) -> String {
+impl From<Layer> for gently_network::Layer {
+    fn from(layer: Layer) -> Self {
+        match layer {
+            Layer::Admin => gently_network::Layer::Admin,
+            Layer::GentlyDev => gently_network::Layer::GentlyDev,
+            Layer::DevLevel => gently_network::Layer::DevLevel,
+            Layer::OsAdmin => gently_network::Layer::OsAdmin,
+            Layer::RootUser => gently_network::Layer::RootUser,
+            Layer::User => gently_network::Layer::User,
+        }
+    }
+}
+
+/// Pull the `Layer` that `tier_auth_mw` injected (defaulting to `User` if
+/// the middleware never ran) and feed it into `firewall.check_for`,
+/// combining tier policy and network policy at a single enforcement point.
+pub fn check_request(
+    firewall: &mut gently_network::Firewall,
+    req: &Request,
+    ip: &str,
+    port: u16,
+    direction: gently_network::Direction,
+) -> gently_network::RuleAction {
+    let layer = req.extensions().get::<Layer>().copied().unwrap_or(Layer::User);
+    firewall.check_for(layer.into(), ip, port, direction)
+}
+
+/// Pull the raw tier claim (signed token, or bare tier string in insecure
+/// mode) from the header, cookie, or query param, in that order.
+fn extract_claim(req: &Request) -> Option<String> {
     // 1. X-Gently-Tier header
     if let Some(val) = req.headers().get("X-Gently-Tier") {
         if let Ok(s) = val.to_str() {
-            return s.to_lowercase();
+            return Some(s.to_string());
         }
     }
 
@@ -105,7 +240,7 @@ fn extract_tier(req: &Request) -> String {
             for pair in cookies.split(';') {
                 let pair = pair.trim();
                 if let Some(val) = pair.strip_prefix("gently_tier=") {
-                    return val.to_lowercase();
+                    return Some(val.to_string());
                 }
             }
         }
@@ -115,18 +250,27 @@ fn extract_tier(req: &Request) -> String {
     if let Some(query) = req.uri().query() {
         for pair in query.split('&') {
             if let Some(val) = pair.strip_prefix("tier=") {
-                return val.to_lowercase();
+                return Some(val.to_string());
             }
         }
     }
 
-    // 4. Default
-    "free".to_string()
+    None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::body::Body;
+    use axum::http::Request as HttpRequest;
+
+    fn request_with_header(name: &str, value: &str) -> Request {
+        HttpRequest::builder()
+            .uri("/")
+            .header(name, value)
+            .body(Body::empty())
+            .unwrap()
+    }
 
     #[test]
     fn layer_from_tier_founder() {
@@ -151,4 +295,81 @@ mod tests {
         assert_eq!(Layer::User.badge_class(), "tier-free");
         assert_eq!(Layer::OsAdmin.badge_class(), "tier-pro");
     }
+
+    #[test]
+    fn valid_signed_token_resolves_claimed_tier() {
+        let auth = TierAuth::new("test-secret");
+        let token = auth.issue("founder", 60);
+        let req = request_with_header("X-Gently-Tier", &token);
+        assert_eq!(auth.resolve(&req), Layer::Admin);
+    }
+
+    #[test]
+    fn tampered_signature_falls_back_to_user() {
+        let auth = TierAuth::new("test-secret");
+        let token = auth.issue("founder", 60);
+        let mut tampered = token.clone();
+        tampered.push('x');
+        let req = request_with_header("X-Gently-Tier", &tampered);
+        assert_eq!(auth.resolve(&req), Layer::User);
+    }
+
+    #[test]
+    fn wrong_key_falls_back_to_user() {
+        let issuer = TierAuth::new("key-a");
+        let verifier = TierAuth::new("key-b");
+        let token = issuer.issue("founder", 60);
+        let req = request_with_header("X-Gently-Tier", &token);
+        assert_eq!(verifier.resolve(&req), Layer::User);
+    }
+
+    #[test]
+    fn expired_token_falls_back_to_user() {
+        let auth = TierAuth::new("test-secret");
+        let token = auth.issue("founder", 0);
+        // expires_at == issued_at, so it's already expired by the time we check
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        let req = request_with_header("X-Gently-Tier", &token);
+        assert_eq!(auth.resolve(&req), Layer::User);
+    }
+
+    #[test]
+    fn bare_tier_is_ignored_unless_insecure_mode_is_enabled() {
+        let secure = TierAuth::new("test-secret");
+        let req = request_with_header("X-Gently-Tier", "founder");
+        assert_eq!(secure.resolve(&req), Layer::User);
+
+        let insecure = TierAuth::new("test-secret").insecure_trust_headers(true);
+        assert_eq!(insecure.resolve(&req), Layer::Admin);
+    }
+
+    #[test]
+    fn no_claim_defaults_to_user() {
+        let auth = TierAuth::new("test-secret");
+        let req = HttpRequest::builder().uri("/").body(Body::empty()).unwrap();
+        assert_eq!(auth.resolve(&req), Layer::User);
+    }
+
+    #[test]
+    fn check_request_honors_injected_layer() {
+        let mut firewall = gently_network::Firewall::new();
+        firewall.add_rule(
+            gently_network::FirewallRule::new("admin_subnet", gently_network::RuleAction::Allow)
+                .with_ip("10.0.0.0/8")
+                .with_min_layer(gently_network::Layer::OsAdmin),
+        );
+
+        let mut admin_req = HttpRequest::builder().uri("/").body(Body::empty()).unwrap();
+        admin_req.extensions_mut().insert(Layer::OsAdmin);
+        assert_eq!(
+            check_request(&mut firewall, &admin_req, "10.1.2.3", 443, gently_network::Direction::Outbound),
+            gently_network::RuleAction::Allow
+        );
+
+        let user_req = HttpRequest::builder().uri("/").body(Body::empty()).unwrap();
+        assert_eq!(
+            check_request(&mut firewall, &user_req, "10.1.2.3", 443, gently_network::Direction::Outbound),
+            gently_network::RuleAction::Deny
+        );
+    }
 }
@@ -11,6 +11,7 @@ use serde_json::json;
 
 use crate::codie_parser::{self, Program};
 use crate::routes::{html_escape, is_htmx, wrap_page};
+use crate::sinks::CageEvent;
 use crate::subprocess;
 use crate::AppState;
 
@@ -136,14 +137,15 @@ async fn codie_execute(
         Some(p) => {
             let intent = form.intent.unwrap_or_else(|| format!("execute {name}"));
 
-            // Log the execution to MongoDB
-            let _ = subprocess::mongo_log(
-                &state.store_js,
-                "coordination:phase",
-                &format!("EXECUTE:codie-{name}"),
-                &json!({"intent": intent, "program": name}).to_string(),
-            )
-            .await;
+            // Log the execution to every registered sink (MongoDB and whatever else is wired up)
+            state
+                .sinks
+                .emit(CageEvent::new(
+                    "coordination:phase",
+                    format!("EXECUTE:codie-{name}"),
+                    json!({"intent": intent, "program": name}),
+                ))
+                .await;
 
             // Execute via PTC engine
             let task_json = json!({
@@ -270,13 +272,14 @@ pub async fn seed_codie(state: Arc<AppState>) {
     }
 
     // Log the seeding event
-    let _ = subprocess::mongo_log(
-        &state.store_js,
-        "coordination:phase",
-        "INTAKE:codie-seed",
-        &json!({"programs": programs.len()}).to_string(),
-    )
-    .await;
+    state
+        .sinks
+        .emit(CageEvent::new(
+            "coordination:phase",
+            "INTAKE:codie-seed",
+            json!({"programs": programs.len()}),
+        ))
+        .await;
 
     eprintln!("Done. Seeded {} CODIE programs.", programs.len());
 }
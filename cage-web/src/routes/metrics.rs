@@ -0,0 +1,72 @@
+//! Prometheus text-exposition metrics, so the cage can be scraped by a
+//! standard monitoring stack instead of only the HTMX status panel.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+
+use crate::sessions;
+use crate::subprocess;
+use crate::AppState;
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/api/metrics", get(api_metrics))
+}
+
+/// Best-effort document count for a MongoDB collection, via the same
+/// `node store.js` path every other mongo query uses. Caps at `limit` since
+/// store.js has no dedicated count command; returns 0 rather than failing
+/// the whole scrape if the collection is unreachable.
+async fn mongo_collection_count(state: &AppState, collection: &str) -> usize {
+    subprocess::mongo_get(&state.store_js, collection, "{}", 100_000)
+        .await
+        .map(|raw| raw.lines().filter(|l| !l.trim().is_empty()).count())
+        .unwrap_or(0)
+}
+
+async fn api_metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let session_list = sessions::list().await.unwrap_or_default();
+    let mut by_state: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    for s in &session_list {
+        *by_state.entry(s.state.clone()).or_insert(0) += 1;
+    }
+
+    let semantic_bridges = mongo_collection_count(&state, "bridges").await;
+    let alexandria_concepts = mongo_collection_count(&state, "alexandria_concepts").await;
+    let alexandria_edges = mongo_collection_count(&state, "alexandria_edges").await;
+
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# HELP claude_cage_sessions_total Cage sessions by docker state.");
+    let _ = writeln!(out, "# TYPE claude_cage_sessions_total gauge");
+    for (state_label, count) in &by_state {
+        let _ = writeln!(
+            out,
+            "claude_cage_sessions_total{{state=\"{state_label}\"}} {count}"
+        );
+    }
+    if by_state.is_empty() {
+        let _ = writeln!(out, "claude_cage_sessions_total{{state=\"running\"}} 0");
+    }
+
+    let _ = writeln!(out, "# HELP claude_cage_bridges_total Known cross-modality bridges.");
+    let _ = writeln!(out, "# TYPE claude_cage_bridges_total gauge");
+    let _ = writeln!(out, "claude_cage_bridges_total{{kind=\"semantic\"}} {semantic_bridges}");
+
+    let _ = writeln!(out, "# HELP alexandria_concepts_total Concepts known to the Alexandria knowledge graph.");
+    let _ = writeln!(out, "# TYPE alexandria_concepts_total gauge");
+    let _ = writeln!(out, "alexandria_concepts_total {alexandria_concepts}");
+
+    let _ = writeln!(out, "# HELP alexandria_edges_total Edges known to the Alexandria knowledge graph.");
+    let _ = writeln!(out, "# TYPE alexandria_edges_total gauge");
+    let _ = writeln!(out, "alexandria_edges_total {alexandria_edges}");
+
+    (
+        [(axum::http::header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        out,
+    )
+}
@@ -8,6 +8,7 @@ pub mod gentlyos;
 pub mod glyph_registry;
 pub mod health;
 pub mod inbox;
+pub mod metrics;
 pub mod models;
 pub mod pages;
 pub mod projects;
@@ -1,3 +1,4 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use askama::Template;
@@ -6,6 +7,7 @@ use axum::http::HeaderMap;
 use axum::response::{Html, IntoResponse};
 use axum::routing::get;
 use axum::Router;
+use serde::{Deserialize, Serialize};
 
 use crate::middleware::Layer;
 use crate::routes::{is_htmx, wrap_page};
@@ -15,13 +17,40 @@ pub fn router() -> Router<Arc<AppState>> {
     Router::new().route("/surface", get(surface_page))
 }
 
+/// One entry as declared in the shelf manifest, before layer gating is
+/// resolved against a request's `Layer`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShelfEntryConfig {
+    pub name: String,
+    pub icon: String,
+    pub href: String,
+    #[serde(default)]
+    pub core: bool,
+    /// Tier name required to unlock this entry, e.g. "free", "basic", "pro",
+    /// "dev", "founder" - see `Layer::tier_name`. Entries with no floor use
+    /// "free".
+    #[serde(default = "default_min_layer")]
+    pub min_layer: String,
+}
+
+fn default_min_layer() -> String {
+    "free".to_string()
+}
+
+/// The IO surface's shelf, as loaded from the manifest file.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ShelfManifest {
+    pub entries: Vec<ShelfEntryConfig>,
+}
+
+/// A manifest entry resolved against a request's `Layer`, ready to render.
 struct ShelfEntry {
-    name: &'static str,
-    icon: &'static str,
+    name: String,
+    icon: String,
     status: &'static str,
     locked: bool,
     core: bool,
-    href: &'static str,
+    href: String,
 }
 
 #[derive(Template)]
@@ -36,12 +65,12 @@ struct SurfaceTemplate {
 
 async fn surface_page(
     headers: HeaderMap,
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     ext: axum::extract::Request,
 ) -> impl IntoResponse {
     let layer = ext.extensions().get::<Layer>().copied().unwrap_or(Layer::User);
 
-    let shelf_items = build_shelf(layer);
+    let shelf_items = resolve_shelf(&state.shelf, layer);
     let active_count = shelf_items.iter().filter(|s| !s.locked).count();
     let locked_count = shelf_items.iter().filter(|s| s.locked).count();
 
@@ -62,50 +91,168 @@ async fn surface_page(
     }
 }
 
-fn build_shelf(layer: Layer) -> Vec<ShelfEntry> {
-    let mut items = vec![
-        // Core services (always active)
-        ShelfEntry { name: "alexandria", icon: "LIB", status: "active", locked: false, core: true, href: "" },
-        ShelfEntry { name: "claude-chat", icon: "AI", status: "active", locked: false, core: true, href: "" },
-        ShelfEntry { name: "guarddog-dns", icon: "DNS", status: "active", locked: false, core: true, href: "" },
-        ShelfEntry { name: "env-vault", icon: "KEY", status: "active", locked: false, core: true, href: "" },
-        ShelfEntry { name: "shelf", icon: "SHF", status: "active", locked: false, core: true, href: "" },
-        // IO Tools (always active, have dedicated pages)
-        ShelfEntry { name: "cookie-jar", icon: "JAR", status: "active", locked: false, core: true, href: "/cookie-jar" },
-        ShelfEntry { name: "glyph-registry", icon: "GLY", status: "active", locked: false, core: true, href: "/glyph-registry" },
-        ShelfEntry { name: "consent-gate", icon: "CGT", status: "active", locked: false, core: true, href: "/consent-gate" },
-        ShelfEntry { name: "genesis-shield", icon: "GEN", status: "active", locked: false, core: true, href: "/genesis-shield" },
-        ShelfEntry { name: "emoji-rewriter", icon: "EMJ", status: "active", locked: false, core: true, href: "/emoji-rewriter" },
-        ShelfEntry { name: "semantic-chars", icon: "SEM", status: "active", locked: false, core: true, href: "/semantic-chars" },
-        ShelfEntry { name: "tos-interceptor", icon: "TOS", status: "active", locked: false, core: true, href: "/tos-interceptor" },
-    ];
-
-    // Basic+ items
-    if layer.has_access(Layer::RootUser) {
-        items.push(ShelfEntry { name: "workbench", icon: "WRK", status: "active", locked: false, core: false, href: "" });
-        items.push(ShelfEntry { name: "python-bridge", icon: "PY", status: "active", locked: false, core: false, href: "" });
-    } else {
-        items.push(ShelfEntry { name: "workbench", icon: "WRK", status: "locked", locked: true, core: false, href: "" });
-        items.push(ShelfEntry { name: "python-bridge", icon: "PY", status: "locked", locked: true, core: false, href: "" });
+/// Resolve `locked`/`status` for every manifest entry against `layer`,
+/// exactly as the old hardcoded `build_shelf` did.
+fn resolve_shelf(manifest: &ShelfManifest, layer: Layer) -> Vec<ShelfEntry> {
+    manifest
+        .entries
+        .iter()
+        .map(|entry| {
+            let required = Layer::from_tier(&entry.min_layer);
+            let locked = !layer.has_access(required);
+            ShelfEntry {
+                name: entry.name.clone(),
+                icon: entry.icon.clone(),
+                status: if locked { "locked" } else { "active" },
+                locked,
+                core: entry.core,
+                href: entry.href.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Load the shelf manifest from `path`, falling back to
+/// `default_shelf_manifest()` if the file is missing or fails to validate.
+pub fn load_shelf_manifest(path: &Path) -> ShelfManifest {
+    let raw = match std::fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(e) => {
+            eprintln!("Shelf manifest {} not found ({e}), using defaults", path.display());
+            return default_shelf_manifest();
+        }
+    };
+
+    let manifest: ShelfManifest = match serde_json::from_str(&raw) {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!("Shelf manifest {} is invalid JSON ({e}), using defaults", path.display());
+            return default_shelf_manifest();
+        }
+    };
+
+    if let Err(e) = validate_shelf_manifest(&manifest) {
+        eprintln!("Shelf manifest {} failed validation ({e}), using defaults", path.display());
+        return default_shelf_manifest();
     }
 
-    // Pro+ items
-    if layer.has_access(Layer::OsAdmin) {
-        items.push(ShelfEntry { name: "docker", icon: "DKR", status: "active", locked: false, core: false, href: "" });
-        items.push(ShelfEntry { name: "agent-swarm", icon: "AGT", status: "active", locked: false, core: false, href: "" });
-    } else {
-        items.push(ShelfEntry { name: "docker", icon: "DKR", status: "locked", locked: true, core: false, href: "" });
-        items.push(ShelfEntry { name: "agent-swarm", icon: "AGT", status: "locked", locked: true, core: false, href: "" });
+    manifest
+}
+
+/// Check that every entry's `min_layer` names a real tier and every `href`
+/// is either empty (no dedicated page) or an absolute path.
+fn validate_shelf_manifest(manifest: &ShelfManifest) -> Result<(), String> {
+    const KNOWN_TIERS: &[&str] = &["founder", "dev", "pro", "basic", "free"];
+
+    for entry in &manifest.entries {
+        if entry.name.is_empty() {
+            return Err("entry with an empty name".to_string());
+        }
+        if !KNOWN_TIERS.contains(&entry.min_layer.as_str()) {
+            return Err(format!(
+                "entry '{}' has unknown min_layer '{}'",
+                entry.name, entry.min_layer
+            ));
+        }
+        if !entry.href.is_empty() && !entry.href.starts_with('/') {
+            return Err(format!(
+                "entry '{}' has a non-absolute href '{}'",
+                entry.name, entry.href
+            ));
+        }
     }
 
-    // Dev+ items
-    if layer.has_access(Layer::DevLevel) {
-        items.push(ShelfEntry { name: "limbo", icon: "LMB", status: "active", locked: false, core: false, href: "" });
-        items.push(ShelfEntry { name: "offensive-tools", icon: "OFS", status: "active", locked: false, core: false, href: "" });
-    } else {
-        items.push(ShelfEntry { name: "limbo", icon: "LMB", status: "locked", locked: true, core: false, href: "" });
-        items.push(ShelfEntry { name: "offensive-tools", icon: "OFS", status: "locked", locked: true, core: false, href: "" });
+    Ok(())
+}
+
+/// The shelf as it shipped hardcoded before the manifest loader existed,
+/// used when the manifest file is missing, is not valid JSON, or fails
+/// validation.
+fn default_shelf_manifest() -> ShelfManifest {
+    let entry = |name: &str, icon: &str, href: &str, core: bool, min_layer: &str| ShelfEntryConfig {
+        name: name.to_string(),
+        icon: icon.to_string(),
+        href: href.to_string(),
+        core,
+        min_layer: min_layer.to_string(),
+    };
+
+    ShelfManifest {
+        entries: vec![
+            // Core services (always active)
+            entry("alexandria", "LIB", "", true, "free"),
+            entry("claude-chat", "AI", "", true, "free"),
+            entry("guarddog-dns", "DNS", "", true, "free"),
+            entry("env-vault", "KEY", "", true, "free"),
+            entry("shelf", "SHF", "", true, "free"),
+            // IO Tools (always active, have dedicated pages)
+            entry("cookie-jar", "JAR", "/cookie-jar", true, "free"),
+            entry("glyph-registry", "GLY", "/glyph-registry", true, "free"),
+            entry("consent-gate", "CGT", "/consent-gate", true, "free"),
+            entry("genesis-shield", "GEN", "/genesis-shield", true, "free"),
+            entry("emoji-rewriter", "EMJ", "/emoji-rewriter", true, "free"),
+            entry("semantic-chars", "SEM", "/semantic-chars", true, "free"),
+            entry("tos-interceptor", "TOS", "/tos-interceptor", true, "free"),
+            // Basic+ items
+            entry("workbench", "WRK", "", false, "basic"),
+            entry("python-bridge", "PY", "", false, "basic"),
+            // Pro+ items
+            entry("docker", "DKR", "", false, "pro"),
+            entry("agent-swarm", "AGT", "", false, "pro"),
+            // Dev+ items
+            entry("limbo", "LMB", "", false, "dev"),
+            entry("offensive-tools", "OFS", "", false, "dev"),
+        ],
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_shelf_gates_by_layer() {
+        let manifest = default_shelf_manifest();
+
+        let free_items = resolve_shelf(&manifest, Layer::User);
+        let workbench = free_items.iter().find(|i| i.name == "workbench").unwrap();
+        assert!(workbench.locked);
 
-    items
+        let basic_items = resolve_shelf(&manifest, Layer::RootUser);
+        let workbench = basic_items.iter().find(|i| i.name == "workbench").unwrap();
+        assert!(!workbench.locked);
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_tier() {
+        let manifest = ShelfManifest {
+            entries: vec![ShelfEntryConfig {
+                name: "mystery".to_string(),
+                icon: "???".to_string(),
+                href: "".to_string(),
+                core: false,
+                min_layer: "legendary".to_string(),
+            }],
+        };
+        assert!(validate_shelf_manifest(&manifest).is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_relative_href() {
+        let manifest = ShelfManifest {
+            entries: vec![ShelfEntryConfig {
+                name: "mystery".to_string(),
+                icon: "???".to_string(),
+                href: "cookie-jar".to_string(),
+                core: false,
+                min_layer: "free".to_string(),
+            }],
+        };
+        assert!(validate_shelf_manifest(&manifest).is_err());
+    }
+
+    #[test]
+    fn test_default_manifest_is_valid() {
+        assert!(validate_shelf_manifest(&default_shelf_manifest()).is_ok());
+    }
 }
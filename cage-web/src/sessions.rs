@@ -0,0 +1,118 @@
+//! Typed session model over `docker ps`/`docker inspect`.
+//!
+//! `subprocess::list_sessions`/`inspect_container` hand back raw `{{json .}}`
+//! stdout, so every caller (the HTMX session list, `/api/sessions`, and now
+//! `/api/metrics`) re-parsed the same `serde_json::Value` shape by hand.
+//! This module does that parsing once and returns typed structs instead.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::subprocess;
+
+/// One row of `docker ps --filter label=managed-by=claude-cage --format {{json .}}`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub name: String,
+    pub image: String,
+    pub status: String,
+    pub state: String,
+    pub created: String,
+    /// Cage network mode (e.g. "filtered", "open"), read from the
+    /// container's `network` label; "unknown" if it isn't set.
+    pub network: String,
+    /// Cage run mode (e.g. "cli", "api"), read from the container's `mode`
+    /// label; "unknown" if it isn't set.
+    pub mode: String,
+}
+
+/// Richer per-container detail from `docker inspect`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionInspect {
+    pub name: String,
+    pub image: String,
+    pub status: String,
+    pub created: String,
+    pub ports: Value,
+}
+
+fn label(labels: &str, key: &str) -> String {
+    labels
+        .split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v.to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn parse_summary(v: &Value) -> SessionSummary {
+    let labels = v["Labels"].as_str().unwrap_or("");
+    SessionSummary {
+        name: v["Names"].as_str().unwrap_or("").to_string(),
+        image: v["Image"].as_str().unwrap_or("").to_string(),
+        status: v["Status"].as_str().unwrap_or("unknown").to_string(),
+        state: v["State"].as_str().unwrap_or("unknown").to_string(),
+        created: v["CreatedAt"].as_str().unwrap_or("").to_string(),
+        network: label(labels, "network"),
+        mode: label(labels, "mode"),
+    }
+}
+
+/// List every cage-managed session, typed.
+pub async fn list() -> Result<Vec<SessionSummary>, String> {
+    let raw = subprocess::list_sessions().await?;
+    Ok(raw
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str::<Value>(l).ok())
+        .map(|v| parse_summary(&v))
+        .collect())
+}
+
+/// Inspect a single container by name, typed.
+pub async fn inspect(container: &str) -> Result<SessionInspect, String> {
+    let raw = subprocess::inspect_container(container).await?;
+    let inspected: Vec<Value> =
+        serde_json::from_str(&raw).map_err(|e| format!("parse inspect output: {e}"))?;
+    let info = inspected
+        .first()
+        .ok_or_else(|| format!("no inspect data for {container}"))?;
+
+    Ok(SessionInspect {
+        name: container.to_string(),
+        image: info["Config"]["Image"].as_str().unwrap_or("").to_string(),
+        status: info["State"]["Status"].as_str().unwrap_or("unknown").to_string(),
+        created: info["Created"].as_str().unwrap_or("").to_string(),
+        ports: info["NetworkSettings"]["Ports"].clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_reads_known_key() {
+        let labels = "managed-by=claude-cage,network=filtered,mode=cli";
+        assert_eq!(label(labels, "network"), "filtered");
+        assert_eq!(label(labels, "mode"), "cli");
+    }
+
+    #[test]
+    fn test_label_falls_back_to_unknown() {
+        assert_eq!(label("managed-by=claude-cage", "network"), "unknown");
+        assert_eq!(label("", "mode"), "unknown");
+    }
+
+    #[test]
+    fn test_parse_summary_reads_docker_ps_fields() {
+        let v: Value = serde_json::from_str(
+            r#"{"Names":"cage-demo","Image":"cage:latest","Status":"Up 2 minutes","State":"running","CreatedAt":"2026-01-01","Labels":"network=open,mode=api"}"#,
+        )
+        .unwrap();
+        let summary = parse_summary(&v);
+        assert_eq!(summary.name, "cage-demo");
+        assert_eq!(summary.network, "open");
+        assert_eq!(summary.mode, "api");
+    }
+}
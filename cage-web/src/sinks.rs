@@ -0,0 +1,321 @@
+//! Event sink pipeline - fan out cage activity to many destinations.
+//!
+//! `mongo_log` used to be the only place an event could go: every caller
+//! shelled out to `node store.js` directly, so teeing activity into a log
+//! aggregator or a webhook meant touching every call site. `SinkPipeline`
+//! turns that into one registration among several: stdout, a local JSONL
+//! file, an HTTP webhook, and MongoDB all implement `EventSink`, and
+//! `emit` fans an event out to every sink whose filter allows its
+//! `event_type`, isolating failures so one broken webhook can't swallow
+//! delivery to the rest.
+
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+
+use crate::subprocess;
+
+/// One piece of cage activity, the unit `SinkPipeline` fans out.
+#[derive(Debug, Clone, Serialize)]
+pub struct CageEvent {
+    pub event_type: String,
+    pub key: String,
+    pub value: serde_json::Value,
+    pub timestamp: i64,
+}
+
+impl CageEvent {
+    pub fn new(event_type: impl Into<String>, key: impl Into<String>, value: serde_json::Value) -> Self {
+        Self {
+            event_type: event_type.into(),
+            key: key.into(),
+            value,
+            timestamp: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+/// A destination an event can be delivered to. Delivery is best-effort:
+/// an `Err` is logged by `SinkPipeline` and never stops the other sinks.
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    /// Human-readable name for logging (e.g. "stdout", "webhook:slack").
+    fn name(&self) -> &str;
+
+    async fn emit(&self, event: &CageEvent) -> Result<(), String>;
+}
+
+/// Writes events as JSON lines to stdout.
+pub struct StdoutSink;
+
+#[async_trait]
+impl EventSink for StdoutSink {
+    fn name(&self) -> &str {
+        "stdout"
+    }
+
+    async fn emit(&self, event: &CageEvent) -> Result<(), String> {
+        let line = serde_json::to_string(event).map_err(|e| e.to_string())?;
+        println!("{line}");
+        Ok(())
+    }
+}
+
+/// Appends events as JSON lines to a local audit file.
+pub struct JsonlFileSink {
+    name: String,
+    path: PathBuf,
+}
+
+impl JsonlFileSink {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            name: format!("jsonl:{}", path.display()),
+            path,
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for JsonlFileSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn emit(&self, event: &CageEvent) -> Result<(), String> {
+        let mut line = serde_json::to_string(event).map_err(|e| e.to_string())?;
+        line.push('\n');
+
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("create_dir_all {}: {e}", parent.display()))?;
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| format!("open {}: {e}", self.path.display()))?;
+
+        file.write_all(line.as_bytes())
+            .await
+            .map_err(|e| format!("write {}: {e}", self.path.display()))
+    }
+}
+
+/// POSTs events as JSON to an HTTP webhook (Slack-style incoming webhook,
+/// log aggregator ingest endpoint, etc).
+pub struct WebhookSink {
+    name: String,
+    url: String,
+    client: reqwest::Client,
+}
+
+impl WebhookSink {
+    pub fn new(url: impl Into<String>) -> Self {
+        let url = url.into();
+        Self {
+            name: format!("webhook:{url}"),
+            url,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for WebhookSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn emit(&self, event: &CageEvent) -> Result<(), String> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(event)
+            .send()
+            .await
+            .map_err(|e| format!("POST {}: {e}", self.url))?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("POST {} returned {}", self.url, response.status()))
+        }
+    }
+}
+
+/// The existing MongoDB store, reached via `node store.js` like every other
+/// `subprocess::mongo_*` call.
+pub struct MongoSink {
+    store_js: PathBuf,
+}
+
+impl MongoSink {
+    pub fn new(store_js: PathBuf) -> Self {
+        Self { store_js }
+    }
+}
+
+#[async_trait]
+impl EventSink for MongoSink {
+    fn name(&self) -> &str {
+        "mongo"
+    }
+
+    async fn emit(&self, event: &CageEvent) -> Result<(), String> {
+        subprocess::mongo_log(
+            &self.store_js,
+            &event.event_type,
+            &event.key,
+            &event.value.to_string(),
+        )
+        .await
+        .map(|_| ())
+    }
+}
+
+/// Which event types a sink is registered for.
+#[derive(Debug, Clone)]
+pub enum SinkFilter {
+    /// Receives every event.
+    All,
+    /// Receives only events whose `event_type` is in this list.
+    Allow(Vec<String>),
+    /// Receives every event except those whose `event_type` is in this list.
+    Deny(Vec<String>),
+}
+
+impl SinkFilter {
+    fn allows(&self, event_type: &str) -> bool {
+        match self {
+            SinkFilter::All => true,
+            SinkFilter::Allow(types) => types.iter().any(|t| t == event_type),
+            SinkFilter::Deny(types) => !types.iter().any(|t| t == event_type),
+        }
+    }
+}
+
+/// Ordered fan-out of one event stream to many sinks, each with its own
+/// `event_type` filter. `mongo_log` becomes one registration (`MongoSink`
+/// with `SinkFilter::All`) rather than the only path.
+#[derive(Default)]
+pub struct SinkPipeline {
+    sinks: Vec<(Box<dyn EventSink>, SinkFilter)>,
+}
+
+impl SinkPipeline {
+    pub fn new() -> Self {
+        Self { sinks: Vec::new() }
+    }
+
+    /// Register a sink, in order, with a filter deciding which events it sees.
+    pub fn register(&mut self, sink: Box<dyn EventSink>, filter: SinkFilter) {
+        self.sinks.push((sink, filter));
+    }
+
+    /// Fan `event` out to every sink whose filter allows it. Best-effort:
+    /// a sink that errors is logged to stderr and does not stop delivery
+    /// to the rest.
+    pub async fn emit(&self, event: CageEvent) {
+        for (sink, filter) in &self.sinks {
+            if !filter.allows(&event.event_type) {
+                continue;
+            }
+            if let Err(e) = sink.emit(&event).await {
+                eprintln!("sink {} failed for event {}: {e}", sink.name(), event.event_type);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct CountingSink {
+        count: Arc<AtomicUsize>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl EventSink for CountingSink {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        async fn emit(&self, _event: &CageEvent) -> Result<(), String> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            if self.fail {
+                Err("boom".to_string())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_fans_out_to_every_matching_sink() {
+        let mut pipeline = SinkPipeline::new();
+        let count_a = Arc::new(AtomicUsize::new(0));
+        let count_b = Arc::new(AtomicUsize::new(0));
+
+        pipeline.register(
+            Box::new(CountingSink { count: count_a.clone(), fail: false }),
+            SinkFilter::All,
+        );
+        pipeline.register(
+            Box::new(CountingSink { count: count_b.clone(), fail: false }),
+            SinkFilter::Allow(vec!["coordination:phase".to_string()]),
+        );
+
+        pipeline.emit(CageEvent::new("coordination:phase", "key", serde_json::json!({}))).await;
+        pipeline.emit(CageEvent::new("other", "key", serde_json::json!({}))).await;
+
+        assert_eq!(count_a.load(Ordering::SeqCst), 2);
+        assert_eq!(count_b.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_a_failing_sink_does_not_block_the_others() {
+        let mut pipeline = SinkPipeline::new();
+        let failing_count = Arc::new(AtomicUsize::new(0));
+        let healthy_count = Arc::new(AtomicUsize::new(0));
+
+        pipeline.register(
+            Box::new(CountingSink { count: failing_count.clone(), fail: true }),
+            SinkFilter::All,
+        );
+        pipeline.register(
+            Box::new(CountingSink { count: healthy_count.clone(), fail: false }),
+            SinkFilter::All,
+        );
+
+        pipeline.emit(CageEvent::new("x", "y", serde_json::json!({}))).await;
+
+        assert_eq!(failing_count.load(Ordering::SeqCst), 1);
+        assert_eq!(healthy_count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_deny_filter_skips_listed_event_types() {
+        let mut pipeline = SinkPipeline::new();
+        let count = Arc::new(AtomicUsize::new(0));
+
+        pipeline.register(
+            Box::new(CountingSink { count: count.clone(), fail: false }),
+            SinkFilter::Deny(vec!["noisy".to_string()]),
+        );
+
+        pipeline.emit(CageEvent::new("noisy", "k", serde_json::json!({}))).await;
+        pipeline.emit(CageEvent::new("quiet", "k", serde_json::json!({}))).await;
+
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+}
@@ -136,24 +136,128 @@ pub async fn ptc_run(
 
 /// Run PTC executor on a task JSON (passes input via stdin to avoid injection).
 pub async fn ptc_execute(cage_root: &Path, task_json: &str) -> Result<String, String> {
-    use tokio::io::AsyncWriteExt;
-
-    let mut child = Command::new("python3")
-        .args([
+    run_with_stdin(
+        "python3",
+        &[
             "-c",
             "import json,sys; sys.path.insert(0,'.'); from ptc.executor import execute; print(json.dumps(execute(json.loads(sys.stdin.read()))))",
-        ])
+        ],
+        Some(cage_root),
+        task_json,
+    )
+    .await
+}
+
+/// Fetch many documents by ID in one `node store.js` call. The ID array is
+/// passed over stdin (the `ptc_execute` pattern) instead of argv, so a large
+/// batch can't hit a shell argument-length limit or inject through an ID.
+pub async fn mongo_batch_get(
+    store_js: &Path,
+    collection: &str,
+    ids: &[&str],
+) -> Result<Vec<serde_json::Value>, String> {
+    let input = serde_json::to_string(ids).map_err(|e| format!("serialize ids: {e}"))?;
+    let raw = run_with_stdin(
+        "node",
+        &[
+            store_js.to_str().unwrap_or("store.js"),
+            "batch_get",
+            collection,
+        ],
+        store_js.parent(),
+        &input,
+    )
+    .await?;
+
+    serde_json::from_str(&raw).map_err(|e| format!("parse batch_get output: {e}"))
+}
+
+/// Upsert many documents in one `node store.js` call. The document array is
+/// passed over stdin, same as `mongo_batch_get`.
+pub async fn mongo_batch_put(
+    store_js: &Path,
+    collection: &str,
+    docs: &[serde_json::Value],
+) -> Result<String, String> {
+    let input = serde_json::to_string(docs).map_err(|e| format!("serialize docs: {e}"))?;
+    run_with_stdin(
+        "node",
+        &[
+            store_js.to_str().unwrap_or("store.js"),
+            "batch_put",
+            collection,
+        ],
+        store_js.parent(),
+        &input,
+    )
+    .await
+}
+
+/// Prefix/range scan over `ConceptId` hex keys (`start_key..end_key`, capped
+/// at `limit`), so the graph loader can page a large neighborhood instead of
+/// fetching one concept per `node` process spawn.
+pub async fn mongo_range(
+    store_js: &Path,
+    collection: &str,
+    start_key: &str,
+    end_key: &str,
+    limit: u32,
+) -> Result<Vec<serde_json::Value>, String> {
+    let raw = run(
+        "node",
+        &[
+            store_js.to_str().unwrap_or("store.js"),
+            "range",
+            collection,
+            start_key,
+            end_key,
+            &limit.to_string(),
+        ],
+        store_js.parent(),
+    )
+    .await?;
+
+    raw.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| serde_json::from_str(l).map_err(|e| format!("parse range output line: {e}")))
+        .collect()
+}
+
+/// Read the GentlyOS tree JSON from disk.
+pub async fn read_tree(path: &Path) -> Result<serde_json::Value, String> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| format!("Failed to read tree: {e}"))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse tree: {e}"))
+}
+
+/// Generic command runner that writes `input` to the child's stdin before
+/// waiting on it, for callers whose payload is too large (or too unsafe) to
+/// pass as argv.
+async fn run_with_stdin(
+    cmd: &str,
+    args: &[&str],
+    cwd: Option<&Path>,
+    input: &str,
+) -> Result<String, String> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut c = Command::new(cmd);
+    c.args(args)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
-        .current_dir(cage_root)
-        .env("CAGE_ROOT", cage_root.to_str().unwrap_or("."))
-        .spawn()
-        .map_err(|e| format!("spawn python3: {e}"))?;
+        .env("CAGE_ROOT", cwd.unwrap_or(Path::new(".")).to_str().unwrap_or("."));
+
+    if let Some(dir) = cwd {
+        c.current_dir(dir);
+    }
+
+    let mut child = c.spawn().map_err(|e| format!("spawn {cmd}: {e}"))?;
 
     if let Some(mut stdin) = child.stdin.take() {
         stdin
-            .write_all(task_json.as_bytes())
+            .write_all(input.as_bytes())
             .await
             .map_err(|e| format!("write stdin: {e}"))?;
     }
@@ -161,24 +265,16 @@ pub async fn ptc_execute(cage_root: &Path, task_json: &str) -> Result<String, St
     let output = child
         .wait_with_output()
         .await
-        .map_err(|e| format!("wait python3: {e}"))?;
+        .map_err(|e| format!("wait {cmd}: {e}"))?;
 
     if output.status.success() {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     } else {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("python3 failed ({}): {stderr}", output.status))
+        Err(format!("{cmd} failed ({}): {stderr}", output.status))
     }
 }
 
-/// Read the GentlyOS tree JSON from disk.
-pub async fn read_tree(path: &Path) -> Result<serde_json::Value, String> {
-    let content = tokio::fs::read_to_string(path)
-        .await
-        .map_err(|e| format!("Failed to read tree: {e}"))?;
-    serde_json::from_str(&content).map_err(|e| format!("Failed to parse tree: {e}"))
-}
-
 /// Generic command runner.
 async fn run(cmd: &str, args: &[&str], cwd: Option<&Path>) -> Result<String, String> {
     let mut c = Command::new(cmd);
@@ -20,6 +20,7 @@
 use crate::concept::ConceptId;
 use crate::node::NodeFingerprint;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// A usage-driven edge with temporal tracking
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +51,14 @@ pub struct AlexandriaEdge {
 
     /// Is this edge dormant (weight below threshold)?
     pub dormant: bool,
+
+    /// Per-node reinforcement counts - the grow-only counter side of this
+    /// edge's CRDT state (see `AlexandriaGraph::merge`). Keyed by
+    /// contributing node so merging two replicas is a per-key `max` of
+    /// counts only that node ever increments, never a single shared
+    /// counter that would double-count on repeated or out-of-order merges.
+    #[serde(default)]
+    pub reinforcement_count: HashMap<NodeFingerprint, u64>,
 }
 
 impl AlexandriaEdge {
@@ -66,6 +75,7 @@ impl AlexandriaEdge {
             use_count: 1,
             source_nodes: Vec::new(),
             dormant: false,
+            reinforcement_count: HashMap::new(),
         }
     }
 
@@ -159,6 +169,122 @@ impl AlexandriaEdge {
             (self.to, self.from)
         }
     }
+
+    // ========== CRDT state ==========
+    //
+    // `reinforcement_count` (grow-only, per-node), `last_used`
+    // (last-writer-wins) and `source_nodes` (grow-only set) are the only
+    // state two replicas ever exchange for an edge. `weight` is never
+    // merged directly - `strength` and
+    // `decayed_strength` recompute it deterministically from that state,
+    // so two nodes that exchange deltas in any order always converge on
+    // the same value, and repeated decay reads stay idempotent.
+
+    /// Record a reinforcement from `node`: bumps that node's own counter
+    /// (so merging two replicas' view of this edge never double-counts)
+    /// and advances `last_used`. Refreshes the cached `weight` from
+    /// `strength` so local reads stay in sync without waiting for a sync.
+    pub fn reinforce(&mut self, node: NodeFingerprint) {
+        *self.reinforcement_count.entry(node).or_insert(0) += 1;
+        self.last_used = chrono::Utc::now().timestamp();
+        self.weight = self.strength();
+        self.dormant = false;
+    }
+
+    /// Total reinforcements across every contributing node.
+    pub fn total_reinforcements(&self) -> u64 {
+        self.reinforcement_count.values().sum()
+    }
+
+    /// Deterministic strength computed from `reinforcement_count` alone -
+    /// the CRDT-safe replacement for treating `weight` itself as merge
+    /// state. Saturates toward twice `kind.base_weight()` as reinforcements
+    /// accumulate, so it can't diverge the way repeated `weight += delta`
+    /// could across out-of-order merges.
+    pub fn strength(&self) -> f32 {
+        let base = self.kind.base_weight();
+        let total = self.total_reinforcements() as f32;
+        base * (2.0 - 2.0_f32.powf(-total / 5.0))
+    }
+
+    /// `strength` with the same exponential half-life decay `apply_decay`
+    /// uses, applied lazily from elapsed time since `last_used` rather than
+    /// mutating stored state - so calling it repeatedly between merges
+    /// never changes what's actually stored.
+    pub fn decayed_strength(&self, half_life_days: f32) -> f32 {
+        let now = chrono::Utc::now().timestamp();
+        let age_days = (now - self.last_used).max(0) as f32 / 86400.0;
+        let decay_factor = 0.5_f32.powf(age_days / half_life_days);
+
+        // Multi-node validation slows decay, same as `apply_decay`, but
+        // driven by the CRDT-tracked contributor set instead of
+        // `source_nodes` so it stays correct after a merge.
+        let distinct_nodes = self.reinforcement_count.len().max(1) as f32;
+        let validation_bonus = (1.0 + (distinct_nodes - 1.0) * 0.05).min(1.5);
+
+        self.strength() * decay_factor * validation_bonus
+    }
+
+    /// Merge another replica's CRDT state for this same edge into `self`:
+    /// a per-node `max` over reinforcement counters (each node only ever
+    /// increments its own entry, so `max` is exactly "have I seen this
+    /// node's count before") and the later of the two `last_used`
+    /// timestamps. Commutative, associative, and idempotent - applying the
+    /// same delta twice, or two deltas in either order, lands on the same
+    /// state.
+    pub fn merge_crdt(&mut self, other: &EdgeCrdtState) {
+        for (node, &count) in &other.reinforcement_count {
+            let entry = self.reinforcement_count.entry(*node).or_insert(0);
+            *entry = (*entry).max(count);
+        }
+        if other.last_used > self.last_used {
+            self.last_used = other.last_used;
+        }
+        for source in &other.source_nodes {
+            if !self.source_nodes.contains(source) {
+                self.source_nodes.push(*source);
+            }
+        }
+        self.weight = self.strength();
+    }
+
+    /// Build a fresh edge from a peer's CRDT state, for when we haven't
+    /// seen this edge locally before.
+    pub fn from_crdt_state(state: &EdgeCrdtState) -> Self {
+        let mut edge = Self::new(state.from, state.to, state.kind.clone());
+        edge.reinforcement_count = state.reinforcement_count.clone();
+        edge.last_used = state.last_used;
+        edge.source_nodes = state.source_nodes.clone();
+        edge.weight = edge.strength();
+        edge
+    }
+}
+
+/// The CRDT state exchanged for one edge in a `GraphDelta`: the grow-only
+/// reinforcement counters and the last-writer-wins `last_used` register,
+/// deliberately excluding `weight` (see `AlexandriaEdge::strength`) so
+/// syncing never has to reconcile two divergent weight values.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeCrdtState {
+    pub from: ConceptId,
+    pub to: ConceptId,
+    pub kind: EdgeKind,
+    pub reinforcement_count: HashMap<NodeFingerprint, u64>,
+    pub last_used: i64,
+    pub source_nodes: Vec<NodeFingerprint>,
+}
+
+impl From<&AlexandriaEdge> for EdgeCrdtState {
+    fn from(edge: &AlexandriaEdge) -> Self {
+        Self {
+            from: edge.from,
+            to: edge.to,
+            kind: edge.kind.clone(),
+            reinforcement_count: edge.reinforcement_count.clone(),
+            last_used: edge.last_used,
+            source_nodes: edge.source_nodes.clone(),
+        }
+    }
 }
 
 /// Types of edges
@@ -337,4 +463,74 @@ mod tests {
 
         assert_eq!(edge1.key(), edge2.key());
     }
+
+    fn node(seed: &str) -> NodeFingerprint {
+        NodeFingerprint::from_hardware(seed, 4, 16, seed)
+    }
+
+    #[test]
+    fn test_strength_is_deterministic_from_reinforcement_count() {
+        let a = ConceptId::from_concept("a");
+        let b = ConceptId::from_concept("b");
+        let mut edge = AlexandriaEdge::new(a, b, EdgeKind::UserPath);
+        let base = edge.strength();
+
+        edge.reinforce(node("n1"));
+        edge.reinforce(node("n1"));
+        let after_two = edge.strength();
+
+        assert!(after_two > base, "strength should grow with reinforcements");
+        assert_eq!(edge.strength(), after_two, "strength is a pure function of stored state");
+    }
+
+    #[test]
+    fn test_merge_is_commutative_and_idempotent() {
+        let a = ConceptId::from_concept("a");
+        let b = ConceptId::from_concept("b");
+
+        let mut node_a = AlexandriaEdge::new(a, b, EdgeKind::UserPath);
+        node_a.reinforce(node("node_a"));
+        node_a.reinforce(node("node_a"));
+
+        let mut node_b = AlexandriaEdge::new(a, b, EdgeKind::UserPath);
+        node_b.reinforce(node("node_b"));
+
+        let state_a = EdgeCrdtState::from(&node_a);
+        let state_b = EdgeCrdtState::from(&node_b);
+
+        // Merge order 1: b into a, then a's state into a again (idempotent).
+        let mut merged_ab = node_a.clone();
+        merged_ab.merge_crdt(&state_b);
+        let total_ab = merged_ab.total_reinforcements();
+        merged_ab.merge_crdt(&state_a);
+        assert_eq!(merged_ab.total_reinforcements(), total_ab, "re-merging the same state must be a no-op");
+
+        // Merge order 2: a into b - should converge to the same total.
+        let mut merged_ba = node_b.clone();
+        merged_ba.merge_crdt(&state_a);
+
+        assert_eq!(merged_ab.total_reinforcements(), merged_ba.total_reinforcements());
+        assert_eq!(merged_ab.strength(), merged_ba.strength());
+    }
+
+    #[test]
+    fn test_merge_takes_the_later_last_used_as_last_writer_wins() {
+        let a = ConceptId::from_concept("a");
+        let b = ConceptId::from_concept("b");
+
+        let mut older = AlexandriaEdge::new(a, b, EdgeKind::UserPath);
+        older.last_used = 1_000;
+
+        let mut newer = AlexandriaEdge::new(a, b, EdgeKind::UserPath);
+        newer.last_used = 2_000;
+
+        older.merge_crdt(&EdgeCrdtState::from(&newer));
+        assert_eq!(older.last_used, 2_000);
+
+        // Merging an older state afterwards must not move it backwards.
+        let mut stale = AlexandriaEdge::new(a, b, EdgeKind::UserPath);
+        stale.last_used = 500;
+        older.merge_crdt(&EdgeCrdtState::from(&stale));
+        assert_eq!(older.last_used, 2_000);
+    }
 }
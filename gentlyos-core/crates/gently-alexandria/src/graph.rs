@@ -17,8 +17,8 @@
 //!      BUILT FROM USAGE
 //! ```
 
-use crate::concept::{Concept, ConceptId};
-use crate::edge::{AlexandriaEdge, EdgeKind, EdgeUpdate};
+use crate::concept::{Concept, ConceptId, SynonymEvidence, SynonymPair};
+use crate::edge::{AlexandriaEdge, EdgeCrdtState, EdgeKind, EdgeUpdate};
 use crate::node::NodeFingerprint;
 use crate::sync::GraphDelta;
 use crate::{AlexandriaConfig, Error, Result};
@@ -54,8 +54,24 @@ pub struct AlexandriaGraph {
     /// Pending updates to publish
     pending_updates: Arc<RwLock<Vec<EdgeUpdate>>>,
 
+    /// Concepts created since the last `create_delta`, so the grow-only
+    /// concept set can be shipped in `GraphDelta::new_concepts`.
+    pending_new_concepts: Arc<RwLock<Vec<ConceptId>>>,
+
+    /// Edge keys touched since the last `create_delta`, so `create_delta`
+    /// knows which edges' CRDT state to snapshot into `GraphDelta::edge_states`.
+    pending_edge_keys: Arc<RwLock<HashSet<(ConceptId, ConceptId)>>>,
+
     /// Sequence number for deltas
     sequence: Arc<RwLock<u64>>,
+
+    /// Monotonic session counter, bumped by `new_session`. Identifies the
+    /// current session in `concept_sessions` so `detect_synonyms`'s
+    /// `CoOccurrence` measure can tell which concepts appeared together.
+    session_counter: Arc<RwLock<u64>>,
+
+    /// Which sessions each concept has appeared in, recorded by `record_query`.
+    concept_sessions: Arc<RwLock<HashMap<ConceptId, HashSet<u64>>>>,
 }
 
 impl AlexandriaGraph {
@@ -70,7 +86,11 @@ impl AlexandriaGraph {
             incoming: Arc::new(RwLock::new(HashMap::new())),
             current_session: Arc::new(RwLock::new(Vec::new())),
             pending_updates: Arc::new(RwLock::new(Vec::new())),
+            pending_new_concepts: Arc::new(RwLock::new(Vec::new())),
+            pending_edge_keys: Arc::new(RwLock::new(HashSet::new())),
             sequence: Arc::new(RwLock::new(0)),
+            session_counter: Arc::new(RwLock::new(0)),
+            concept_sessions: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -86,7 +106,11 @@ impl AlexandriaGraph {
         let id = ConceptId::from_concept(text);
 
         let mut concepts = self.concepts.write().unwrap();
-        concepts.entry(id).or_insert_with(|| Concept::new(text));
+        if let std::collections::hash_map::Entry::Vacant(entry) = concepts.entry(id) {
+            entry.insert(Concept::new(text));
+            drop(concepts);
+            self.pending_new_concepts.write().unwrap().push(id);
+        }
 
         id
     }
@@ -124,16 +148,23 @@ impl AlexandriaGraph {
         // Ensure concepts exist
         {
             let mut concepts = self.concepts.write().unwrap();
+            let mut new_concepts = Vec::new();
             concepts.entry(from).or_insert_with(|| {
+                new_concepts.push(from);
                 let mut c = Concept::new("");
                 c.id = from;
                 c
             });
             concepts.entry(to).or_insert_with(|| {
+                new_concepts.push(to);
                 let mut c = Concept::new("");
                 c.id = to;
                 c
             });
+            drop(concepts);
+            if !new_concepts.is_empty() {
+                self.pending_new_concepts.write().unwrap().extend(new_concepts);
+            }
         }
 
         // Create edge key (ordered)
@@ -147,14 +178,17 @@ impl AlexandriaGraph {
             .entry(key)
             .and_modify(|e| {
                 e.use_edge();
+                e.reinforce(self.local_node);
                 if !e.source_nodes.contains(&self.local_node) {
                     e.source_nodes.push(self.local_node);
                 }
             })
             .or_insert_with(|| {
-                AlexandriaEdge::new(from, to, kind.clone())
+                let mut edge = AlexandriaEdge::new(from, to, kind.clone())
                     .with_weight(kind.base_weight())
-                    .with_source(self.local_node)
+                    .with_source(self.local_node);
+                edge.reinforce(self.local_node);
+                edge
             });
 
         // Update indices
@@ -166,6 +200,8 @@ impl AlexandriaGraph {
         drop(outgoing);
         drop(incoming);
 
+        self.pending_edge_keys.write().unwrap().insert(key);
+
         let mut pending = self.pending_updates.write().unwrap();
         pending.push(EdgeUpdate::WeightIncrement {
             from,
@@ -259,6 +295,12 @@ impl AlexandriaGraph {
             }
         }
 
+        // Record session membership for co-occurrence-based synonym detection.
+        {
+            let session = *self.session_counter.read().unwrap();
+            self.concept_sessions.write().unwrap().entry(id).or_default().insert(session);
+        }
+
         // Add to current session and get previous query
         let (last, session_snapshot) = {
             let mut session = self.current_session.write().unwrap();
@@ -301,6 +343,7 @@ impl AlexandriaGraph {
     pub fn new_session(&self) {
         let mut session = self.current_session.write().unwrap();
         session.clear();
+        *self.session_counter.write().unwrap() += 1;
     }
 
     /// Get current session
@@ -479,6 +522,17 @@ impl AlexandriaGraph {
     /// Create a delta from pending updates
     pub fn create_delta(&self) -> GraphDelta {
         let updates = self.take_pending_updates();
+        let new_concepts = std::mem::take(&mut *self.pending_new_concepts.write().unwrap());
+        let dirty_edge_keys = std::mem::take(&mut *self.pending_edge_keys.write().unwrap());
+
+        let edge_states = {
+            let edges = self.edges.read().unwrap();
+            dirty_edge_keys
+                .into_iter()
+                .filter_map(|key| edges.get(&key).map(EdgeCrdtState::from))
+                .collect()
+        };
+
         let mut seq = self.sequence.write().unwrap();
         *seq += 1;
 
@@ -486,12 +540,182 @@ impl AlexandriaGraph {
             from_node: self.local_node,
             timestamp: chrono::Utc::now().timestamp(),
             sequence: *seq,
-            new_concepts: Vec::new(), // TODO: track new concepts
+            new_concepts,
             edge_updates: updates,
+            edge_states,
             wormhole_updates: Vec::new(),
         }
     }
 
+    /// CRDT merge of a delta from another node: unions the grow-only concept
+    /// set and reduces each edge's CRDT state via `AlexandriaEdge::merge_crdt`,
+    /// so any two nodes that exchange deltas in any order converge on the
+    /// same graph. Unlike `merge_delta`, this never blends `weight` directly
+    /// and is safe to apply the same delta more than once.
+    pub fn merge(&self, delta: &GraphDelta) {
+        {
+            let mut concepts = self.concepts.write().unwrap();
+            for id in &delta.new_concepts {
+                concepts.entry(*id).or_insert_with(|| {
+                    let mut c = Concept::new("");
+                    c.id = *id;
+                    c
+                });
+            }
+        }
+
+        let mut edges = self.edges.write().unwrap();
+        let mut outgoing = self.outgoing.write().unwrap();
+        let mut incoming = self.incoming.write().unwrap();
+
+        for state in &delta.edge_states {
+            let key = if state.from.0 < state.to.0 {
+                (state.from, state.to)
+            } else {
+                (state.to, state.from)
+            };
+
+            edges
+                .entry(key)
+                .and_modify(|edge| edge.merge_crdt(state))
+                .or_insert_with(|| AlexandriaEdge::from_crdt_state(state));
+
+            outgoing.entry(state.from).or_default().insert(state.to);
+            incoming.entry(state.to).or_default().insert(state.from);
+        }
+    }
+
+    // ========== Synonym Detection ==========
+
+    /// Find candidate synonym pairs by edge-neighborhood overlap and session
+    /// co-occurrence, for a caller to feed high-confidence pairs back into
+    /// `ConceptId::normalize`'s "synonym resolution via graph" step. Does
+    /// not merge anything itself - detection is separate from resolution.
+    pub fn detect_synonyms(&self, cfg: &SynonymDetectionConfig) -> Vec<SynonymPair> {
+        let mut pairs = self.detect_edge_overlap_synonyms(cfg.edge_overlap_threshold);
+        pairs.extend(self.detect_co_occurrence_synonyms(cfg.co_occurrence_threshold));
+        pairs
+    }
+
+    /// Each concept's neighbor set: every concept reachable by exactly one
+    /// edge, in either direction.
+    fn neighbor_sets(&self) -> HashMap<ConceptId, HashSet<ConceptId>> {
+        let outgoing = self.outgoing.read().unwrap();
+        let incoming = self.incoming.read().unwrap();
+
+        let mut neighbors: HashMap<ConceptId, HashSet<ConceptId>> = HashMap::new();
+        for (from, tos) in outgoing.iter() {
+            neighbors.entry(*from).or_default().extend(tos.iter().copied());
+        }
+        for (to, froms) in incoming.iter() {
+            neighbors.entry(*to).or_default().extend(froms.iter().copied());
+        }
+        neighbors
+    }
+
+    /// `SynonymEvidence::EdgeOverlap`: candidates are generated by inverting
+    /// the neighbor index (neighbor -> concepts pointing at it) so only
+    /// concept pairs that already share a neighbor are ever compared,
+    /// avoiding the naive O(n^2) scan over every concept pair.
+    fn detect_edge_overlap_synonyms(&self, threshold: f32) -> Vec<SynonymPair> {
+        let neighbors = self.neighbor_sets();
+
+        let mut inverted: HashMap<ConceptId, Vec<ConceptId>> = HashMap::new();
+        for (concept, set) in &neighbors {
+            for n in set {
+                inverted.entry(*n).or_default().push(*concept);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut pairs = Vec::new();
+
+        for sharers in inverted.values() {
+            for i in 0..sharers.len() {
+                for j in (i + 1)..sharers.len() {
+                    let (a, b) = ordered_pair(sharers[i], sharers[j]);
+                    if a == b || !seen.insert((a, b)) {
+                        continue;
+                    }
+
+                    let (Some(set_a), Some(set_b)) = (neighbors.get(&a), neighbors.get(&b)) else {
+                        continue;
+                    };
+
+                    let intersection = set_a.intersection(set_b).count();
+                    let union = set_a.union(set_b).count();
+                    if union == 0 {
+                        continue;
+                    }
+                    let jaccard = intersection as f32 / union as f32;
+
+                    if jaccard >= threshold {
+                        pairs.push(SynonymPair {
+                            concept_a: a,
+                            concept_b: b,
+                            confidence: jaccard,
+                            evidence: SynonymEvidence::EdgeOverlap { jaccard },
+                        });
+                    }
+                }
+            }
+        }
+
+        pairs
+    }
+
+    /// `SynonymEvidence::CoOccurrence`: candidates are generated by
+    /// inverting `concept_sessions` (session -> concepts seen in it), so
+    /// only concepts that actually shared a session are ever compared.
+    fn detect_co_occurrence_synonyms(&self, threshold: f32) -> Vec<SynonymPair> {
+        let concept_sessions = self.concept_sessions.read().unwrap();
+
+        let mut session_members: HashMap<u64, Vec<ConceptId>> = HashMap::new();
+        for (concept, sessions) in concept_sessions.iter() {
+            for session in sessions {
+                session_members.entry(*session).or_default().push(*concept);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut pairs = Vec::new();
+
+        for members in session_members.values() {
+            for i in 0..members.len() {
+                for j in (i + 1)..members.len() {
+                    let (a, b) = ordered_pair(members[i], members[j]);
+                    if a == b || !seen.insert((a, b)) {
+                        continue;
+                    }
+
+                    let (Some(sessions_a), Some(sessions_b)) =
+                        (concept_sessions.get(&a), concept_sessions.get(&b))
+                    else {
+                        continue;
+                    };
+
+                    let intersection = sessions_a.intersection(sessions_b).count();
+                    let union = sessions_a.union(sessions_b).count();
+                    if union == 0 {
+                        continue;
+                    }
+                    let rate = intersection as f32 / union as f32;
+
+                    if rate >= threshold {
+                        pairs.push(SynonymPair {
+                            concept_a: a,
+                            concept_b: b,
+                            confidence: rate,
+                            evidence: SynonymEvidence::CoOccurrence { rate },
+                        });
+                    }
+                }
+            }
+        }
+
+        pairs
+    }
+
     // ========== Export/Import ==========
 
     /// Export graph to bytes
@@ -533,6 +757,47 @@ impl AlexandriaGraph {
         Ok(())
     }
 
+    // ========== Durable Storage (see `crate::repo`) ==========
+
+    /// Bulk-load concepts and edges from a durable repo (e.g.
+    /// `SledAlexandriaRepo`), used to hydrate a freshly constructed graph on
+    /// boot so the awareness loop doesn't start from nothing every restart.
+    pub fn hydrate_from_repo(&self, repo: &dyn crate::repo::AlexandriaRepo) -> Result<()> {
+        let concepts = repo.all_concepts()?;
+        let edges = repo.all_edges()?;
+
+        let mut concepts_map = self.concepts.write().unwrap();
+        let mut edges_map = self.edges.write().unwrap();
+        let mut outgoing = self.outgoing.write().unwrap();
+        let mut incoming = self.incoming.write().unwrap();
+
+        for concept in concepts {
+            concepts_map.insert(concept.id, concept);
+        }
+
+        for edge in edges {
+            let key = edge.key();
+            outgoing.entry(edge.from).or_default().insert(edge.to);
+            incoming.entry(edge.to).or_default().insert(edge.from);
+            edges_map.insert(key, edge);
+        }
+
+        Ok(())
+    }
+
+    /// Persist a concept and its outgoing edges to a durable repo. Called
+    /// after mutating that concept (e.g. from `tool_alexandria_record`) so
+    /// the change survives a restart without re-exporting the whole graph.
+    pub fn persist_concept(&self, repo: &dyn crate::repo::AlexandriaRepo, id: &ConceptId) -> Result<()> {
+        if let Some(concept) = self.get_concept(id) {
+            repo.put_concept(&concept)?;
+        }
+        for edge in self.edges_from(id) {
+            repo.put_edge(&edge)?;
+        }
+        Ok(())
+    }
+
     /// Get statistics
     pub fn stats(&self) -> GraphStats {
         let concepts = self.concepts.read().unwrap();
@@ -660,6 +925,34 @@ pub struct GraphStats {
     pub multi_source_edges: usize,
 }
 
+/// Tunables for `AlexandriaGraph::detect_synonyms`.
+#[derive(Debug, Clone)]
+pub struct SynonymDetectionConfig {
+    /// Minimum neighbor-set Jaccard similarity to emit an `EdgeOverlap` pair.
+    pub edge_overlap_threshold: f32,
+    /// Minimum session co-occurrence rate to emit a `CoOccurrence` pair.
+    pub co_occurrence_threshold: f32,
+}
+
+impl Default for SynonymDetectionConfig {
+    fn default() -> Self {
+        Self {
+            edge_overlap_threshold: 0.9,
+            co_occurrence_threshold: 0.9,
+        }
+    }
+}
+
+/// Order two concept IDs the same way regardless of call order, so a pair
+/// is only ever compared/emitted once.
+fn ordered_pair(a: ConceptId, b: ConceptId) -> (ConceptId, ConceptId) {
+    if a.0 < b.0 {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -801,4 +1094,135 @@ mod tests {
         assert_eq!(graph.concept_count(), 0);
         assert_eq!(graph.edge_count(), 0);
     }
+
+    #[test]
+    fn test_hydrate_and_persist_from_repo() {
+        use crate::repo::SledStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = SledStore::open(dir.path()).unwrap();
+        let repo = store.alexandria_repo();
+
+        let graph1 = AlexandriaGraph::with_defaults(test_node());
+        let a = graph1.ensure_concept("rust");
+        let b = graph1.ensure_concept("programming");
+        graph1.add_edge(a, b, EdgeKind::RelatedTo);
+        graph1.persist_concept(&repo, &a).unwrap();
+
+        let graph2 = AlexandriaGraph::with_defaults(test_node());
+        graph2.hydrate_from_repo(&repo).unwrap();
+
+        assert!(graph2.get_concept(&a).is_some());
+        assert!(graph2.get_edge(&a, &b).is_some());
+    }
+
+    #[test]
+    fn test_crdt_merge_converges_regardless_of_delta_order() {
+        let node_a = NodeFingerprint::from_hardware("node_a", 4, 16, "node_a");
+        let node_b = NodeFingerprint::from_hardware("node_b", 8, 32, "node_b");
+
+        let graph_a = AlexandriaGraph::with_defaults(node_a);
+        let a = graph_a.ensure_concept("rust");
+        let b = graph_a.ensure_concept("safety");
+        graph_a.add_edge(a, b, EdgeKind::RelatedTo);
+        let delta_a = graph_a.create_delta();
+
+        let graph_b = AlexandriaGraph::with_defaults(node_b);
+        let a2 = graph_b.ensure_concept("rust");
+        let b2 = graph_b.ensure_concept("safety");
+        assert_eq!(a, a2);
+        assert_eq!(b, b2);
+        graph_b.add_edge(a2, b2, EdgeKind::RelatedTo);
+        let delta_b = graph_b.create_delta();
+
+        // Apply in one order on a third, empty graph...
+        let merged_ab = AlexandriaGraph::with_defaults(node_a);
+        merged_ab.merge(&delta_a);
+        merged_ab.merge(&delta_b);
+
+        // ...and the opposite order on another.
+        let merged_ba = AlexandriaGraph::with_defaults(node_b);
+        merged_ba.merge(&delta_b);
+        merged_ba.merge(&delta_a);
+
+        let edge_ab = merged_ab.get_edge(&a, &b).unwrap();
+        let edge_ba = merged_ba.get_edge(&a, &b).unwrap();
+
+        assert_eq!(edge_ab.total_reinforcements(), edge_ba.total_reinforcements());
+        assert_eq!(edge_ab.strength(), edge_ba.strength());
+        assert_eq!(merged_ab.concept_count(), merged_ba.concept_count());
+
+        // Re-applying the same delta must not change anything (idempotent).
+        merged_ab.merge(&delta_a);
+        let edge_ab_again = merged_ab.get_edge(&a, &b).unwrap();
+        assert_eq!(edge_ab_again.total_reinforcements(), edge_ab.total_reinforcements());
+    }
+
+    #[test]
+    fn test_detect_synonyms_via_edge_overlap() {
+        let graph = AlexandriaGraph::with_defaults(test_node());
+
+        let crypto = graph.ensure_concept("cryptography");
+        let cipher = graph.ensure_concept("ciphers");
+        let unrelated = graph.ensure_concept("gardening");
+
+        // cryptography and ciphers share every neighbor -> Jaccard 1.0.
+        let rsa = graph.ensure_concept("rsa");
+        let aes = graph.ensure_concept("aes");
+        graph.add_edge(crypto, rsa, EdgeKind::RelatedTo);
+        graph.add_edge(crypto, aes, EdgeKind::RelatedTo);
+        graph.add_edge(cipher, rsa, EdgeKind::RelatedTo);
+        graph.add_edge(cipher, aes, EdgeKind::RelatedTo);
+
+        // unrelated shares no neighbors with either.
+        let soil = graph.ensure_concept("soil");
+        graph.add_edge(unrelated, soil, EdgeKind::RelatedTo);
+
+        let pairs = graph.detect_synonyms(&SynonymDetectionConfig::default());
+
+        let found = pairs.iter().any(|p| {
+            matches!(p.evidence, SynonymEvidence::EdgeOverlap { jaccard } if jaccard >= 0.9)
+                && ((p.concept_a == crypto && p.concept_b == cipher)
+                    || (p.concept_a == cipher && p.concept_b == crypto))
+        });
+        assert!(found, "expected cryptography/ciphers to be detected as edge-overlap synonyms");
+
+        assert!(!pairs.iter().any(|p| p.concept_a == unrelated || p.concept_b == unrelated));
+    }
+
+    #[test]
+    fn test_detect_synonyms_via_co_occurrence() {
+        let graph = AlexandriaGraph::with_defaults(test_node());
+
+        graph.new_session();
+        graph.record_query("car");
+        graph.record_query("automobile");
+
+        graph.new_session();
+        graph.record_query("car");
+        graph.record_query("automobile");
+
+        graph.new_session();
+        graph.record_query("car");
+        graph.record_query("automobile");
+        graph.record_query("bicycle");
+
+        let pairs = graph.detect_synonyms(&SynonymDetectionConfig::default());
+
+        let car = ConceptId::from_concept("car");
+        let automobile = ConceptId::from_concept("automobile");
+        let bicycle = ConceptId::from_concept("bicycle");
+
+        let found = pairs.iter().any(|p| {
+            matches!(p.evidence, SynonymEvidence::CoOccurrence { .. })
+                && ((p.concept_a == car && p.concept_b == automobile)
+                    || (p.concept_a == automobile && p.concept_b == car))
+        });
+        assert!(found, "expected car/automobile to be detected as co-occurrence synonyms");
+
+        assert!(!pairs.iter().any(|p| {
+            matches!(p.evidence, SynonymEvidence::CoOccurrence { rate } if rate >= 0.9)
+                && (p.concept_a == bicycle || p.concept_b == bicycle)
+        }));
+    }
 }
@@ -37,15 +37,17 @@ pub mod sync;
 pub mod query;
 pub mod economics;
 pub mod tesseract;
+pub mod repo;
 
-pub use concept::ConceptId;
-pub use edge::{AlexandriaEdge, EdgeKind, EdgeUpdate};
+pub use concept::{ConceptId, SynonymPair, SynonymEvidence};
+pub use edge::{AlexandriaEdge, EdgeKind, EdgeUpdate, EdgeCrdtState};
 pub use node::{AlexandriaNode, NodeFingerprint};
-pub use graph::AlexandriaGraph;
+pub use graph::{AlexandriaGraph, SynonymDetectionConfig};
 pub use wormhole::DistributedWormhole;
 pub use sync::{GraphDelta, SyncProtocol};
 pub use query::{FullTopology, HistoricalTopology, DriftAnalysis};
 pub use economics::{ContributionProof, RewardCalculator};
+pub use repo::{AlexandriaRepo, TesseractRepo, SledStore, SledAlexandriaRepo, SledTesseractRepo};
 pub use tesseract::{
     SemanticTesseract, HyperPosition, TemporalPosition,
     FullMeaning, HyperDriftAnalysis, HyperFace,
@@ -0,0 +1,316 @@
+//! Durable, sled-backed storage for the Alexandria graph and Tesseract
+//! positions.
+//!
+//! `AlexandriaGraph` and `SemanticTesseract` only ever live in memory;
+//! `tool_alexandria_record`, `ensure_concept`, and `tesseract.record_position`
+//! all build state that a restart throws away, so the awareness loop has to
+//! rebuild its whole topology from nothing every time the brain starts.
+//! `SledStore` opens one `sled::Db` and hands out a tree per relation
+//! (mirroring pict-rs's layout): concept nodes, `edges_from`, `edges_to`,
+//! serialized `HyperPosition` lists keyed by `ConceptId::to_hex()`, and a
+//! settings/version tree. `AlexandriaRepo`/`TesseractRepo` are the extension
+//! points `BrainOrchestrator` hydrates from and writes through on boot; sled
+//! is the only implementation today, but the traits keep an alternative
+//! backend (or an in-memory test double) pluggable.
+
+use crate::concept::Concept;
+use crate::edge::AlexandriaEdge;
+use crate::tesseract::HyperPosition;
+use crate::{ConceptId, Error, Result};
+use std::path::Path;
+
+/// Schema version written to the settings tree on first open, bumped
+/// whenever the on-disk key/value layout changes incompatibly.
+const SCHEMA_VERSION: u64 = 1;
+const SCHEMA_VERSION_KEY: &[u8] = b"schema_version";
+
+/// Durable storage for `AlexandriaGraph` state (concepts + edges).
+///
+/// Callers on an async runtime should run these through
+/// `tokio::task::spawn_blocking`, since sled's API is blocking.
+pub trait AlexandriaRepo: Send + Sync {
+    fn put_concept(&self, concept: &Concept) -> Result<()>;
+    fn all_concepts(&self) -> Result<Vec<Concept>>;
+    fn put_edge(&self, edge: &AlexandriaEdge) -> Result<()>;
+    fn all_edges(&self) -> Result<Vec<AlexandriaEdge>>;
+    fn flush(&self) -> Result<()>;
+}
+
+/// Durable storage for `SemanticTesseract` positions.
+///
+/// Callers on an async runtime should run these through
+/// `tokio::task::spawn_blocking`, since sled's API is blocking.
+pub trait TesseractRepo: Send + Sync {
+    fn put_positions(&self, concept: &ConceptId, positions: &[HyperPosition]) -> Result<()>;
+    fn all_positions(&self) -> Result<Vec<(ConceptId, Vec<HyperPosition>)>>;
+    fn flush(&self) -> Result<()>;
+}
+
+/// Key an edge is stored under in a directional tree: `<anchor_hex>/<other_hex>`.
+fn directional_key(anchor: &ConceptId, other: &ConceptId) -> Vec<u8> {
+    let mut key = anchor.to_hex().into_bytes();
+    key.push(b'/');
+    key.extend(other.to_hex().into_bytes());
+    key
+}
+
+fn to_io_err(e: sled::Error) -> Error {
+    Error::IoError(format!("sled: {}", e))
+}
+
+fn to_ser_err(e: serde_json::Error) -> Error {
+    Error::SerializationError(e.to_string())
+}
+
+/// Opens one `sled::Db` and exposes its relation trees.
+pub struct SledStore {
+    concepts: sled::Tree,
+    edges_from: sled::Tree,
+    edges_to: sled::Tree,
+    positions: sled::Tree,
+    settings: sled::Tree,
+}
+
+impl SledStore {
+    /// Open (or create) a sled database at `path`, recording the schema
+    /// version on first open.
+    pub fn open(path: &Path) -> Result<Self> {
+        let db = sled::open(path).map_err(to_io_err)?;
+        let concepts = db.open_tree("concepts").map_err(to_io_err)?;
+        let edges_from = db.open_tree("edges_from").map_err(to_io_err)?;
+        let edges_to = db.open_tree("edges_to").map_err(to_io_err)?;
+        let positions = db.open_tree("positions").map_err(to_io_err)?;
+        let settings = db.open_tree("settings").map_err(to_io_err)?;
+
+        match settings.get(SCHEMA_VERSION_KEY).map_err(to_io_err)? {
+            None => {
+                settings
+                    .insert(SCHEMA_VERSION_KEY, &SCHEMA_VERSION.to_le_bytes())
+                    .map_err(to_io_err)?;
+            }
+            Some(raw) if raw.as_ref() != SCHEMA_VERSION.to_le_bytes() => {
+                tracing::warn!(
+                    "Alexandria sled store at {} has a newer/older schema version than {}, continuing anyway",
+                    path.display(),
+                    SCHEMA_VERSION
+                );
+            }
+            Some(_) => {}
+        }
+
+        Ok(Self { concepts, edges_from, edges_to, positions, settings })
+    }
+
+    /// View this store as an `AlexandriaRepo`.
+    pub fn alexandria_repo(&self) -> SledAlexandriaRepo {
+        SledAlexandriaRepo {
+            concepts: self.concepts.clone(),
+            edges_from: self.edges_from.clone(),
+            edges_to: self.edges_to.clone(),
+        }
+    }
+
+    /// View this store as a `TesseractRepo`.
+    pub fn tesseract_repo(&self) -> SledTesseractRepo {
+        SledTesseractRepo { positions: self.positions.clone() }
+    }
+
+    /// Flush every tree, including the settings tree.
+    pub fn flush(&self) -> Result<()> {
+        self.concepts.flush().map_err(to_io_err)?;
+        self.edges_from.flush().map_err(to_io_err)?;
+        self.edges_to.flush().map_err(to_io_err)?;
+        self.positions.flush().map_err(to_io_err)?;
+        self.settings.flush().map_err(to_io_err)?;
+        Ok(())
+    }
+}
+
+/// Sled-backed `AlexandriaRepo`. Edges are written once into `edges_from`
+/// (keyed `from_hex/to_hex`) and once into `edges_to` (keyed
+/// `to_hex/from_hex`), so both directions resolve with a prefix scan instead
+/// of a full table scan — the same tradeoff `AlexandriaGraph` already makes
+/// with its in-memory `outgoing`/`incoming` indexes.
+#[derive(Clone)]
+pub struct SledAlexandriaRepo {
+    concepts: sled::Tree,
+    edges_from: sled::Tree,
+    edges_to: sled::Tree,
+}
+
+impl AlexandriaRepo for SledAlexandriaRepo {
+    fn put_concept(&self, concept: &Concept) -> Result<()> {
+        let value = serde_json::to_vec(concept).map_err(to_ser_err)?;
+        self.concepts
+            .insert(concept.id.to_hex().as_bytes(), value)
+            .map_err(to_io_err)?;
+        Ok(())
+    }
+
+    fn all_concepts(&self) -> Result<Vec<Concept>> {
+        self.concepts
+            .iter()
+            .values()
+            .map(|v| serde_json::from_slice(&v.map_err(to_io_err)?).map_err(to_ser_err))
+            .collect()
+    }
+
+    fn put_edge(&self, edge: &AlexandriaEdge) -> Result<()> {
+        let value = serde_json::to_vec(edge).map_err(to_ser_err)?;
+        self.edges_from
+            .insert(directional_key(&edge.from, &edge.to), value.clone())
+            .map_err(to_io_err)?;
+        self.edges_to
+            .insert(directional_key(&edge.to, &edge.from), value)
+            .map_err(to_io_err)?;
+        Ok(())
+    }
+
+    fn all_edges(&self) -> Result<Vec<AlexandriaEdge>> {
+        // `edges_from` alone has one entry per edge; `edges_to` is a mirror.
+        self.edges_from
+            .iter()
+            .values()
+            .map(|v| serde_json::from_slice(&v.map_err(to_io_err)?).map_err(to_ser_err))
+            .collect()
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.concepts.flush().map_err(to_io_err)?;
+        self.edges_from.flush().map_err(to_io_err)?;
+        self.edges_to.flush().map_err(to_io_err)?;
+        Ok(())
+    }
+}
+
+impl SledAlexandriaRepo {
+    /// Edges whose `from` side is `concept`.
+    pub fn edges_from(&self, concept: &ConceptId) -> Result<Vec<AlexandriaEdge>> {
+        let mut prefix = concept.to_hex().into_bytes();
+        prefix.push(b'/');
+        self.edges_from
+            .scan_prefix(prefix)
+            .values()
+            .map(|v| serde_json::from_slice(&v.map_err(to_io_err)?).map_err(to_ser_err))
+            .collect()
+    }
+
+    /// Edges whose `to` side is `concept`.
+    pub fn edges_to(&self, concept: &ConceptId) -> Result<Vec<AlexandriaEdge>> {
+        let mut prefix = concept.to_hex().into_bytes();
+        prefix.push(b'/');
+        self.edges_to
+            .scan_prefix(prefix)
+            .values()
+            .map(|v| serde_json::from_slice(&v.map_err(to_io_err)?).map_err(to_ser_err))
+            .collect()
+    }
+}
+
+/// Sled-backed `TesseractRepo`: one tree of serialized `HyperPosition`
+/// lists, keyed by `ConceptId::to_hex()`.
+#[derive(Clone)]
+pub struct SledTesseractRepo {
+    positions: sled::Tree,
+}
+
+impl TesseractRepo for SledTesseractRepo {
+    fn put_positions(&self, concept: &ConceptId, positions: &[HyperPosition]) -> Result<()> {
+        let value = serde_json::to_vec(positions).map_err(to_ser_err)?;
+        self.positions
+            .insert(concept.to_hex().as_bytes(), value)
+            .map_err(to_io_err)?;
+        Ok(())
+    }
+
+    fn all_positions(&self) -> Result<Vec<(ConceptId, Vec<HyperPosition>)>> {
+        self.positions
+            .iter()
+            .map(|entry| {
+                let (key, value) = entry.map_err(to_io_err)?;
+                let hex = std::str::from_utf8(&key)
+                    .map_err(|e| Error::SerializationError(e.to_string()))?;
+                let concept = ConceptId::from_hex(hex)
+                    .ok_or_else(|| Error::SerializationError(format!("invalid concept key: {}", hex)))?;
+                let positions = serde_json::from_slice(&value).map_err(to_ser_err)?;
+                Ok((concept, positions))
+            })
+            .collect()
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.positions.flush().map_err(to_io_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edge::EdgeKind;
+    use crate::tesseract::TemporalPosition;
+
+    #[test]
+    fn test_put_and_load_concepts() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SledStore::open(dir.path()).unwrap();
+        let repo = store.alexandria_repo();
+
+        let concept = Concept::new("encryption");
+        repo.put_concept(&concept).unwrap();
+
+        let loaded = repo.all_concepts().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].id, concept.id);
+    }
+
+    #[test]
+    fn test_edges_resolve_both_directions() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SledStore::open(dir.path()).unwrap();
+        let repo = store.alexandria_repo();
+
+        let a = ConceptId::from_concept("a");
+        let b = ConceptId::from_concept("b");
+        let edge = AlexandriaEdge::new(a, b, EdgeKind::RelatedTo);
+        repo.put_edge(&edge).unwrap();
+
+        assert_eq!(repo.edges_from(&a).unwrap().len(), 1);
+        assert_eq!(repo.edges_to(&b).unwrap().len(), 1);
+        assert!(repo.edges_from(&b).unwrap().is_empty());
+        assert_eq!(repo.all_edges().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_positions_survive_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let concept = ConceptId::from_concept("crypto");
+        let position = HyperPosition {
+            concept,
+            actual: vec![],
+            eliminated: vec![],
+            potential: vec![],
+            temporal: TemporalPosition::default(),
+            observer: vec![],
+            context: vec![],
+            method: vec![],
+            purpose: vec![],
+            embedding: None,
+            face_embeddings: None,
+            recorded_at: chrono::Utc::now(),
+        };
+
+        {
+            let store = SledStore::open(dir.path()).unwrap();
+            let repo = store.tesseract_repo();
+            repo.put_positions(&concept, &[position]).unwrap();
+            repo.flush().unwrap();
+        }
+
+        let store = SledStore::open(dir.path()).unwrap();
+        let repo = store.tesseract_repo();
+        let all = repo.all_positions().unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].0, concept);
+        assert_eq!(all[0].1.len(), 1);
+    }
+}
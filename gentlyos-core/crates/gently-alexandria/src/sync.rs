@@ -20,7 +20,7 @@
 //! ```
 
 use crate::concept::ConceptId;
-use crate::edge::EdgeUpdate;
+use crate::edge::{EdgeCrdtState, EdgeUpdate};
 use crate::node::{AlexandriaNode, NodeFingerprint, NodeRegistry};
 use crate::wormhole::WormholeUpdate;
 use crate::{Error, Result};
@@ -43,9 +43,17 @@ pub struct GraphDelta {
     /// New concepts introduced
     pub new_concepts: Vec<ConceptId>,
 
-    /// Edge updates
+    /// Edge updates (legacy op-log path, see `AlexandriaGraph::merge_delta`)
     pub edge_updates: Vec<EdgeUpdate>,
 
+    /// CRDT edge states changed since the last sync - grow-only
+    /// reinforcement counters plus a last-writer-wins timestamp per edge.
+    /// The merge-safe counterpart to `edge_updates`, applied by
+    /// `AlexandriaGraph::merge` so any two nodes that exchange deltas in
+    /// any order converge on the same graph.
+    #[serde(default)]
+    pub edge_states: Vec<EdgeCrdtState>,
+
     /// Wormhole updates
     pub wormhole_updates: Vec<WormholeUpdate>,
 }
@@ -59,6 +67,7 @@ impl GraphDelta {
             sequence: 0,
             new_concepts: Vec::new(),
             edge_updates: Vec::new(),
+            edge_states: Vec::new(),
             wormhole_updates: Vec::new(),
         }
     }
@@ -67,6 +76,7 @@ impl GraphDelta {
     pub fn is_empty(&self) -> bool {
         self.new_concepts.is_empty()
             && self.edge_updates.is_empty()
+            && self.edge_states.is_empty()
             && self.wormhole_updates.is_empty()
     }
 
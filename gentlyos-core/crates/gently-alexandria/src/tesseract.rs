@@ -795,6 +795,31 @@ impl SemanticTesseract {
         }
     }
 
+    // ========== Durable Storage (see `crate::repo`) ==========
+
+    /// Bulk-load positions from a durable repo (e.g. `SledTesseractRepo`),
+    /// used to hydrate a freshly constructed tesseract on boot. Replayed
+    /// through `record_position` so the temporal/observer/context indexes
+    /// end up exactly as they would from live recording.
+    pub fn hydrate_from_repo(&mut self, repo: &dyn crate::repo::TesseractRepo) -> crate::Result<()> {
+        for (_concept, positions) in repo.all_positions()? {
+            for position in positions {
+                self.record_position(position);
+            }
+        }
+        Ok(())
+    }
+
+    /// Persist a concept's full position history to a durable repo. Called
+    /// after `record_position` (e.g. from `tool_alexandria_record`) so drift
+    /// analysis can span process restarts.
+    pub fn persist_positions(&self, repo: &dyn crate::repo::TesseractRepo, concept: &ConceptId) -> crate::Result<()> {
+        if let Some(positions) = self.positions.get(concept) {
+            repo.put_positions(concept, positions)?;
+        }
+        Ok(())
+    }
+
     /// Convert an edge to a hypercube navigation
     pub fn edge_to_navigation(&self, edge: &AlexandriaEdge) -> HyperNavigation {
         let face = match &edge.kind {
@@ -1521,4 +1546,37 @@ mod tests {
         assert!(eras.contains(&"2015+".to_string()));
         assert!(eras.contains(&"modern".to_string()));
     }
+
+    #[test]
+    fn test_hydrate_and_persist_from_repo() {
+        use crate::repo::SledStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = SledStore::open(dir.path()).unwrap();
+        let repo = store.tesseract_repo();
+
+        let mut tesseract1 = SemanticTesseract::new();
+        let rust = make_concept("rust");
+        tesseract1.record_position(HyperPosition {
+            concept: rust,
+            actual: vec![make_concept("systems programming")],
+            eliminated: vec![],
+            potential: vec![],
+            temporal: TemporalPosition::default(),
+            observer: vec![],
+            context: vec![],
+            method: vec![],
+            purpose: vec![],
+            embedding: None,
+            face_embeddings: None,
+            recorded_at: Utc::now(),
+        });
+        tesseract1.persist_positions(&repo, &rust).unwrap();
+
+        let mut tesseract2 = SemanticTesseract::new();
+        tesseract2.hydrate_from_repo(&repo).unwrap();
+
+        let meaning = tesseract2.navigate(&rust).unwrap();
+        assert!(meaning.what_it_is.contains(&make_concept("systems programming")));
+    }
 }
@@ -0,0 +1,300 @@
+//! Reproducible benchmarking harness for the awareness pipeline
+//!
+//! There was no way to measure regressions in `process_thought` / `grow` /
+//! `knowledge_similar` as the graph grows. This module replays a
+//! declarative `Workload` (a JSON file describing a sequence of thoughts,
+//! focus directives, and tool calls) against a freshly constructed
+//! `BrainOrchestrator`, records per-stage latency via
+//! `BrainOrchestrator::process_thought_timed` and throughput, and emits a
+//! `BenchReport` (JSON + a plain-text summary) that can be diffed across
+//! commits for a CI-runnable performance baseline.
+
+use crate::orchestrator::{BrainConfig, BrainOrchestrator, StageTimings};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// One step in a declarative workload.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WorkloadStep {
+    /// Process a thought through the full awareness pipeline.
+    Thought { content: String },
+    /// Focus attention on a topic (cheap, used to shape `growth_direction`).
+    Focus { topic: String },
+    /// Call an MCP/orchestrator tool directly, bypassing thought processing.
+    ToolCall { name: String, input: serde_json::Value },
+}
+
+fn step_kind(step: &WorkloadStep) -> &'static str {
+    match step {
+        WorkloadStep::Thought { .. } => "thought",
+        WorkloadStep::Focus { .. } => "focus",
+        WorkloadStep::ToolCall { .. } => "tool_call",
+    }
+}
+
+/// A declarative benchmark workload, loaded from a JSON file.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Workload {
+    pub name: String,
+    /// Number of leading steps to run once and discard before timing starts,
+    /// so lazy initialization (first embedding call, first skill lookup)
+    /// doesn't skew the measured latencies.
+    #[serde(default)]
+    pub warmup_steps: usize,
+    pub steps: Vec<WorkloadStep>,
+}
+
+impl Workload {
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)?;
+        serde_json::from_str(&raw)
+            .map_err(|e| Error::InferenceFailed(format!("invalid workload file: {}", e)))
+    }
+}
+
+/// Knobs for scaling a benchmark run independent of the workload file.
+#[derive(Debug, Clone)]
+pub struct BenchConfig {
+    /// Synthetic concepts to seed into the knowledge graph before timing
+    /// starts, so similarity/inference cost can be measured at scale.
+    pub seed_concepts: usize,
+    /// Whether background daemons run alongside the timed foreground path,
+    /// to isolate background-processing overhead.
+    pub enable_daemons: bool,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            seed_concepts: 0,
+            enable_daemons: false,
+        }
+    }
+}
+
+/// Latency recorded for a single timed workload step.
+#[derive(Debug, Clone, Serialize)]
+pub struct StepReport {
+    pub step: usize,
+    pub kind: &'static str,
+    pub total_ms: f64,
+    pub skill_match_ms: f64,
+    pub alexandria_ms: f64,
+    pub knowledge_ms: f64,
+    pub response_generation_ms: f64,
+}
+
+/// Aggregate stats over all timed steps in a run.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchSummary {
+    pub step_count: usize,
+    pub total_duration_ms: f64,
+    pub throughput_steps_per_sec: f64,
+    pub mean_total_ms: f64,
+    pub p50_total_ms: f64,
+    pub p95_total_ms: f64,
+}
+
+/// Structured, diffable output of a benchmark run.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    pub workload: String,
+    pub seed_concepts: usize,
+    pub daemons_enabled: bool,
+    pub steps: Vec<StepReport>,
+    pub summary: BenchSummary,
+}
+
+impl BenchReport {
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| Error::InferenceFailed(format!("failed to serialize bench report: {}", e)))
+    }
+
+    /// A short human-readable summary, suitable for CI log output.
+    pub fn summary_text(&self) -> String {
+        format!(
+            "{} ({} steps, daemons {}, {} seeded concepts)\n  \
+             total: {:.2}ms  throughput: {:.1} steps/s\n  \
+             mean: {:.2}ms  p50: {:.2}ms  p95: {:.2}ms",
+            self.workload,
+            self.summary.step_count,
+            if self.daemons_enabled { "enabled" } else { "disabled" },
+            self.seed_concepts,
+            self.summary.total_duration_ms,
+            self.summary.throughput_steps_per_sec,
+            self.summary.mean_total_ms,
+            self.summary.p50_total_ms,
+            self.summary.p95_total_ms,
+        )
+    }
+}
+
+fn percentile(sorted_ms: &[f64], pct: f64) -> f64 {
+    if sorted_ms.is_empty() {
+        return 0.0;
+    }
+    let idx = (((sorted_ms.len() - 1) as f64) * pct).round() as usize;
+    sorted_ms[idx.min(sorted_ms.len() - 1)]
+}
+
+/// Seed `count` synthetic, mutually-distinct concepts into the
+/// orchestrator's knowledge graph via the normal `knowledge_learn` tool, so
+/// similarity/inference cost reflects a grown graph rather than an empty
+/// one.
+async fn seed_synthetic_concepts(orchestrator: &BrainOrchestrator, count: usize) {
+    for i in 0..count {
+        let input = serde_json::json!({
+            "concept": format!("synthetic concept {} is a benchmark seed", i),
+            "context": format!("seeded by the bench harness, index {}", i),
+        });
+        let _ = orchestrator.execute_tool("knowledge_learn", &input).await;
+    }
+}
+
+async fn run_step(orchestrator: &BrainOrchestrator, step: &WorkloadStep) -> StageTimings {
+    match step {
+        WorkloadStep::Thought { content } => orchestrator.process_thought_timed(content).await.1,
+        WorkloadStep::Focus { topic } => {
+            orchestrator.focus(topic);
+            StageTimings::default()
+        }
+        WorkloadStep::ToolCall { name, input } => {
+            let t0 = Instant::now();
+            let _ = orchestrator.execute_tool(name, input).await;
+            StageTimings {
+                knowledge: t0.elapsed(),
+                ..Default::default()
+            }
+        }
+    }
+}
+
+/// Replay `workload` against a freshly constructed `BrainOrchestrator`,
+/// recording per-stage latency and throughput.
+pub async fn run(workload: &Workload, config: &BenchConfig) -> BenchReport {
+    let brain_config = BrainConfig {
+        enable_daemons: config.enable_daemons,
+        ..Default::default()
+    };
+    let orchestrator = Arc::new(BrainOrchestrator::new(brain_config));
+
+    if config.enable_daemons {
+        let _ = orchestrator.start().await;
+    }
+
+    seed_synthetic_concepts(&orchestrator, config.seed_concepts).await;
+
+    for step in workload.steps.iter().take(workload.warmup_steps) {
+        run_step(&orchestrator, step).await;
+    }
+
+    let timed_steps = &workload.steps[workload.warmup_steps.min(workload.steps.len())..];
+    let mut steps = Vec::with_capacity(timed_steps.len());
+    let run_start = Instant::now();
+    for (i, step) in timed_steps.iter().enumerate() {
+        let t0 = Instant::now();
+        let timings = run_step(&orchestrator, step).await;
+        let total_ms = t0.elapsed().as_secs_f64() * 1000.0;
+
+        steps.push(StepReport {
+            step: i,
+            kind: step_kind(step),
+            total_ms,
+            skill_match_ms: timings.skill_match.as_secs_f64() * 1000.0,
+            alexandria_ms: timings.alexandria.as_secs_f64() * 1000.0,
+            knowledge_ms: timings.knowledge.as_secs_f64() * 1000.0,
+            response_generation_ms: timings.response_generation.as_secs_f64() * 1000.0,
+        });
+    }
+    let total_duration = run_start.elapsed();
+
+    if config.enable_daemons {
+        orchestrator.stop();
+    }
+
+    let mut totals_ms: Vec<f64> = steps.iter().map(|s| s.total_ms).collect();
+    totals_ms.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mean_total_ms = if totals_ms.is_empty() {
+        0.0
+    } else {
+        totals_ms.iter().sum::<f64>() / totals_ms.len() as f64
+    };
+    let total_duration_ms = total_duration.as_secs_f64() * 1000.0;
+
+    let summary = BenchSummary {
+        step_count: steps.len(),
+        total_duration_ms,
+        throughput_steps_per_sec: if total_duration.as_secs_f64() > 0.0 {
+            steps.len() as f64 / total_duration.as_secs_f64()
+        } else {
+            0.0
+        },
+        mean_total_ms,
+        p50_total_ms: percentile(&totals_ms, 0.50),
+        p95_total_ms: percentile(&totals_ms, 0.95),
+    };
+
+    BenchReport {
+        workload: workload.name.clone(),
+        seed_concepts: config.seed_concepts,
+        daemons_enabled: config.enable_daemons,
+        steps,
+        summary,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_produces_one_report_per_timed_step() {
+        let workload = Workload {
+            name: "smoke".into(),
+            warmup_steps: 1,
+            steps: vec![
+                WorkloadStep::Thought { content: "warmup thought".into() },
+                WorkloadStep::Thought { content: "rust is memory-safe".into() },
+                WorkloadStep::Focus { topic: "rust".into() },
+            ],
+        };
+
+        let report = run(&workload, &BenchConfig::default()).await;
+        assert_eq!(report.steps.len(), 2);
+        assert_eq!(report.summary.step_count, 2);
+        assert!(report.summary.total_duration_ms >= 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_seed_concepts_are_recorded() {
+        let workload = Workload {
+            name: "seeded".into(),
+            warmup_steps: 0,
+            steps: vec![WorkloadStep::Thought { content: "any thought".into() }],
+        };
+        let config = BenchConfig { seed_concepts: 3, enable_daemons: false };
+
+        let report = run(&workload, &config).await;
+        assert_eq!(report.seed_concepts, 3);
+    }
+
+    #[test]
+    fn test_workload_deserializes_tagged_steps() {
+        let json = r#"{
+            "name": "example",
+            "warmup_steps": 1,
+            "steps": [
+                {"kind": "thought", "content": "hello"},
+                {"kind": "focus", "topic": "hello"},
+                {"kind": "tool_call", "name": "awareness_state", "input": {}}
+            ]
+        }"#;
+        let workload: Workload = serde_json::from_str(json).unwrap();
+        assert_eq!(workload.steps.len(), 3);
+        assert_eq!(step_kind(&workload.steps[0]), "thought");
+    }
+}
@@ -0,0 +1,446 @@
+//! Config hot-reload
+//!
+//! Watches a directory of declarative JSON definition files (scalar config
+//! overrides, skill declarations, seed concepts) and applies changes to a
+//! running `BrainOrchestrator` without a restart: updated scalar config is
+//! swapped behind the existing atomics/mutexes (flipping `enable_daemons`
+//! starts or stops the daemon manager in place), newly-declared skills are
+//! registered and removed ones unregistered, and seed concepts are (re)learned
+//! into the knowledge graph and Alexandria. File events are debounced so a
+//! burst of writes (e.g. an editor save) triggers a single reload.
+//!
+//! Requires the `notify` crate.
+
+use crate::knowledge::KnowledgeGraph;
+use crate::skills::{Skill, SkillRegistry};
+use crate::daemon::{DaemonManager, DaemonType, VectorChainDaemon};
+use crate::orchestrator::BrainEvent;
+use crate::{Error, Result};
+use gently_alexandria::AlexandriaGraph;
+use notify::{recommended_watcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc as std_mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// How long to wait after the last filesystem event in a burst before
+/// reloading definitions.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Scalar `BrainConfig` fields that can be retuned without a restart. A
+/// `None` field means "leave as-is" — definition files only need to declare
+/// what they're changing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigOverrides {
+    pub awareness_interval_ms: Option<u64>,
+    pub vector_batch_size: Option<usize>,
+    pub growth_rate: Option<f32>,
+    pub max_context_size: Option<usize>,
+    /// Whether the core daemons (vector chain, knowledge graph, awareness,
+    /// git branch, and - if `BrainConfig::enable_ipfs` - IPFS sync) should
+    /// be running. Flipping this at runtime starts or stops them without a
+    /// restart.
+    pub enable_daemons: Option<bool>,
+}
+
+/// A concept to (re)learn into the knowledge graph and Alexandria on reload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedConcept {
+    pub concept: String,
+    pub context: Option<String>,
+    pub confidence: Option<f32>,
+}
+
+/// The full set of declarative definitions loaded from a watched directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BrainDefinitions {
+    #[serde(default)]
+    pub config: ConfigOverrides,
+    #[serde(default)]
+    pub skills: Vec<Skill>,
+    #[serde(default)]
+    pub seed_concepts: Vec<SeedConcept>,
+}
+
+/// Read every `*.json` file in `dir` (sorted by filename for determinism) as
+/// a `BrainDefinitions` fragment and merge them: later files' config
+/// overrides win, skills/seed concepts are concatenated.
+pub fn load_definitions(dir: &Path) -> Result<BrainDefinitions> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(Error::Io)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+        .collect();
+    paths.sort();
+
+    let mut merged = BrainDefinitions::default();
+    for path in paths {
+        let raw = fs::read_to_string(&path).map_err(Error::Io)?;
+        let fragment: BrainDefinitions = serde_json::from_str(&raw)
+            .map_err(|e| Error::ConfigReloadFailed(format!("{}: {}", path.display(), e)))?;
+
+        if fragment.config.awareness_interval_ms.is_some() {
+            merged.config.awareness_interval_ms = fragment.config.awareness_interval_ms;
+        }
+        if fragment.config.vector_batch_size.is_some() {
+            merged.config.vector_batch_size = fragment.config.vector_batch_size;
+        }
+        if fragment.config.growth_rate.is_some() {
+            merged.config.growth_rate = fragment.config.growth_rate;
+        }
+        if fragment.config.max_context_size.is_some() {
+            merged.config.max_context_size = fragment.config.max_context_size;
+        }
+        if fragment.config.enable_daemons.is_some() {
+            merged.config.enable_daemons = fragment.config.enable_daemons;
+        }
+        merged.skills.extend(fragment.skills);
+        merged.seed_concepts.extend(fragment.seed_concepts);
+    }
+
+    Ok(merged)
+}
+
+/// The live state a reload is applied against, gathered from a
+/// `BrainOrchestrator`.
+pub struct ReloadTargets {
+    pub daemon_manager: Arc<Mutex<DaemonManager>>,
+    pub vector_daemon: Arc<VectorChainDaemon>,
+    pub skill_registry: Arc<Mutex<SkillRegistry>>,
+    pub knowledge_graph: Arc<KnowledgeGraph>,
+    pub alexandria: Arc<Mutex<AlexandriaGraph>>,
+    pub growth_rate: Arc<Mutex<f32>>,
+    pub max_context_size: Arc<AtomicUsize>,
+    pub awareness_interval_ms: Arc<AtomicU64>,
+    /// Live mirror of `BrainConfig::enable_daemons`, toggled by
+    /// `ConfigOverrides::enable_daemons`.
+    pub daemons_enabled: Arc<AtomicBool>,
+    /// Captured from `BrainConfig::enable_ipfs` at watch-start time (not
+    /// itself hot-reloadable): whether re-enabling daemons should include
+    /// `DaemonType::IpfsSync`.
+    pub enable_ipfs: bool,
+    pub event_tx: UnboundedSender<BrainEvent>,
+}
+
+impl ReloadTargets {
+    /// Diff `defs` against `previous` (`None` on the first load) and apply
+    /// every change, returning the names of what changed for
+    /// `BrainEvent::ConfigReloaded`.
+    pub fn apply(&self, defs: &BrainDefinitions, previous: Option<&BrainDefinitions>) -> Vec<String> {
+        let mut changed = Vec::new();
+
+        if let Some(ms) = defs.config.awareness_interval_ms {
+            if self.awareness_interval_ms.swap(ms, Ordering::SeqCst) != ms {
+                self.daemon_manager.lock().unwrap().set_awareness_interval_ms(ms);
+                changed.push("awareness_interval_ms".to_string());
+            }
+        }
+        if let Some(batch_size) = defs.config.vector_batch_size {
+            if previous.and_then(|p| p.config.vector_batch_size) != Some(batch_size) {
+                self.vector_daemon.set_batch_size(batch_size);
+                changed.push("vector_batch_size".to_string());
+            }
+        }
+        if let Some(rate) = defs.config.growth_rate {
+            let mut growth_rate = self.growth_rate.lock().unwrap();
+            if *growth_rate != rate {
+                *growth_rate = rate;
+                changed.push("growth_rate".to_string());
+            }
+        }
+        if let Some(max_context_size) = defs.config.max_context_size {
+            if self.max_context_size.swap(max_context_size, Ordering::SeqCst) != max_context_size {
+                changed.push("max_context_size".to_string());
+            }
+        }
+        if let Some(enable) = defs.config.enable_daemons {
+            let was_enabled = self.daemons_enabled.swap(enable, Ordering::SeqCst);
+            if enable && !was_enabled {
+                let mut dm = self.daemon_manager.lock().unwrap();
+                dm.start();
+                let _ = dm.spawn(DaemonType::VectorChain);
+                let _ = dm.spawn(DaemonType::KnowledgeGraph);
+                let _ = dm.spawn(DaemonType::Awareness);
+                if self.enable_ipfs {
+                    let _ = dm.spawn(DaemonType::IpfsSync);
+                }
+                let _ = dm.spawn(DaemonType::GitBranch);
+                changed.push("enable_daemons".to_string());
+            } else if !enable && was_enabled {
+                self.daemon_manager.lock().unwrap().stop();
+                changed.push("enable_daemons".to_string());
+            }
+        }
+
+        let previous_skills: HashMap<&str, serde_json::Value> = previous
+            .map(|p| {
+                p.skills.iter()
+                    .filter_map(|s| serde_json::to_value(s).ok().map(|v| (s.name.as_str(), v)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let current_names: std::collections::HashSet<&str> =
+            defs.skills.iter().map(|s| s.name.as_str()).collect();
+
+        {
+            let mut registry = self.skill_registry.lock().unwrap();
+            for skill in &defs.skills {
+                let unchanged = serde_json::to_value(skill).ok()
+                    == previous_skills.get(skill.name.as_str()).cloned();
+                if !unchanged {
+                    registry.register(skill.clone());
+                    changed.push(format!("skill:{}", skill.name));
+                }
+            }
+            if let Some(previous) = previous {
+                for skill in &previous.skills {
+                    if !current_names.contains(skill.name.as_str()) {
+                        registry.unregister(&skill.name);
+                        changed.push(format!("skill:-{}", skill.name));
+                    }
+                }
+            }
+        }
+
+        let previous_concepts: std::collections::HashSet<&str> = previous
+            .map(|p| p.seed_concepts.iter().map(|c| c.concept.as_str()).collect())
+            .unwrap_or_default();
+        for seed in &defs.seed_concepts {
+            if previous_concepts.contains(seed.concept.as_str()) {
+                continue;
+            }
+            self.knowledge_graph.learn(&seed.concept, seed.context.as_deref(), seed.confidence);
+            self.alexandria.lock().unwrap().ensure_concept(&seed.concept);
+            changed.push(format!("seed:{}", seed.concept));
+        }
+
+        if !changed.is_empty() {
+            let _ = self.event_tx.send(BrainEvent::ConfigReloaded { changed: changed.clone() });
+        }
+
+        changed
+    }
+}
+
+/// Handle to a running config watcher. Dropping or calling `stop` ends the
+/// watch thread; the underlying `notify` watcher is torn down with it.
+pub struct ConfigWatcherHandle {
+    stop_flag: Arc<AtomicBool>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl ConfigWatcherHandle {
+    /// Stop watching and block until the watch thread has exited.
+    pub fn stop(mut self) {
+        self.stop_flag.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for ConfigWatcherHandle {
+    fn drop(&mut self) {
+        self.stop_flag.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Start watching `dir` for declarative definition files, applying an
+/// initial load immediately and then a debounced reload on every change.
+pub fn watch(dir: impl Into<PathBuf>, targets: ReloadTargets) -> Result<ConfigWatcherHandle> {
+    let dir = dir.into();
+    let (raw_tx, raw_rx) = std_mpsc::channel();
+
+    let mut watcher = recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if event.is_ok() {
+            let _ = raw_tx.send(());
+        }
+    }).map_err(|e| Error::ConfigReloadFailed(format!("failed to start file watcher: {}", e)))?;
+
+    watcher.watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(|e| Error::ConfigReloadFailed(format!("failed to watch {}: {}", dir.display(), e)))?;
+
+    let mut previous = match load_definitions(&dir) {
+        Ok(defs) => {
+            targets.apply(&defs, None);
+            Some(defs)
+        }
+        Err(e) => {
+            tracing::warn!("initial config load from {} failed: {}", dir.display(), e);
+            None
+        }
+    };
+
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let thread_stop_flag = stop_flag.clone();
+    // Re-enabling daemons spawns tokio tasks, so reloads run inside the
+    // caller's runtime when there is one.
+    let runtime = tokio::runtime::Handle::try_current().ok();
+
+    let thread = thread::spawn(move || {
+        let _runtime_guard = runtime.as_ref().map(|handle| handle.enter());
+        // Keep the watcher alive for the life of this thread — dropping it
+        // stops filesystem notifications.
+        let _watcher = watcher;
+
+        while !thread_stop_flag.load(Ordering::SeqCst) {
+            match raw_rx.recv_timeout(DEBOUNCE) {
+                Ok(()) => {
+                    // Drain anything else that arrives within the debounce
+                    // window so a burst of writes reloads only once.
+                    while raw_rx.recv_timeout(DEBOUNCE).is_ok() {}
+                    if thread_stop_flag.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    match load_definitions(&dir) {
+                        Ok(defs) => {
+                            targets.apply(&defs, previous.as_ref());
+                            previous = Some(defs);
+                        }
+                        Err(e) => tracing::warn!("config reload from {} failed: {}", dir.display(), e),
+                    }
+                }
+                Err(std_mpsc::RecvTimeoutError::Timeout) => continue,
+                Err(std_mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+
+    Ok(ConfigWatcherHandle { stop_flag, thread: Some(thread) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::daemon::{DaemonStatus, DaemonState, DaemonMetrics};
+    use crate::skills::SkillCategory;
+    use gently_alexandria::node::NodeFingerprint;
+    use std::time::Instant;
+
+    fn test_targets() -> ReloadTargets {
+        let knowledge_graph = Arc::new(KnowledgeGraph::new());
+        let daemon_manager = Arc::new(Mutex::new(DaemonManager::new()));
+        let event_tx = daemon_manager.lock().unwrap().event_sender();
+        let vector_daemon = Arc::new(VectorChainDaemon::new(
+            Arc::new(AtomicBool::new(false)),
+            Arc::new(Mutex::new(DaemonStatus {
+                running: true,
+                started_at: Some(Instant::now()),
+                cycles: 0,
+                last_cycle: None,
+                errors: 0,
+                state: DaemonState::Running,
+                metrics: DaemonMetrics::default(),
+            })),
+            event_tx,
+            knowledge_graph.clone(),
+            Arc::new(crate::embedding::HashEmbedder::default()),
+            10,
+            2000,
+        ));
+        let node_fingerprint = NodeFingerprint::from_hardware("test", 1, 1, "test-node");
+        let (event_tx, _event_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        ReloadTargets {
+            daemon_manager,
+            vector_daemon,
+            skill_registry: Arc::new(Mutex::new(SkillRegistry::new())),
+            knowledge_graph,
+            alexandria: Arc::new(Mutex::new(AlexandriaGraph::with_defaults(node_fingerprint))),
+            growth_rate: Arc::new(Mutex::new(0.1)),
+            max_context_size: Arc::new(AtomicUsize::new(100)),
+            awareness_interval_ms: Arc::new(AtomicU64::new(250)),
+            daemons_enabled: Arc::new(AtomicBool::new(false)),
+            enable_ipfs: false,
+            event_tx,
+        }
+    }
+
+    fn skill(name: &str, trigger: &str) -> Skill {
+        Skill {
+            name: name.to_string(),
+            description: "test skill".into(),
+            category: SkillCategory::Assistant,
+            triggers: vec![trigger.to_string()],
+            parameters: vec![],
+            examples: vec![],
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_apply_updates_scalar_config() {
+        let targets = test_targets();
+        let defs = BrainDefinitions {
+            config: ConfigOverrides { growth_rate: Some(0.5), ..Default::default() },
+            ..Default::default()
+        };
+
+        let changed = targets.apply(&defs, None);
+        assert_eq!(changed, vec!["growth_rate".to_string()]);
+        assert_eq!(*targets.growth_rate.lock().unwrap(), 0.5);
+
+        // Reapplying the same value is a no-op.
+        assert!(targets.apply(&defs, Some(&defs)).is_empty());
+    }
+
+    #[test]
+    fn test_apply_registers_and_unregisters_skills() {
+        let targets = test_targets();
+        let first = BrainDefinitions { skills: vec![skill("custom_skill", "do the thing")], ..Default::default() };
+
+        let changed = targets.apply(&first, None);
+        assert_eq!(changed, vec!["skill:custom_skill".to_string()]);
+        assert!(targets.skill_registry.lock().unwrap().get("custom_skill").is_some());
+
+        let second = BrainDefinitions::default();
+        let changed = targets.apply(&second, Some(&first));
+        assert_eq!(changed, vec!["skill:-custom_skill".to_string()]);
+        assert!(targets.skill_registry.lock().unwrap().get("custom_skill").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_apply_toggles_daemons_on_and_off() {
+        let targets = test_targets();
+        let enabled = BrainDefinitions {
+            config: ConfigOverrides { enable_daemons: Some(true), ..Default::default() },
+            ..Default::default()
+        };
+
+        let changed = targets.apply(&enabled, None);
+        assert_eq!(changed, vec!["enable_daemons".to_string()]);
+        assert!(targets.daemons_enabled.load(Ordering::SeqCst));
+
+        // Reapplying the same value is a no-op.
+        assert!(targets.apply(&enabled, Some(&enabled)).is_empty());
+
+        let disabled = BrainDefinitions {
+            config: ConfigOverrides { enable_daemons: Some(false), ..Default::default() },
+            ..Default::default()
+        };
+        let changed = targets.apply(&disabled, Some(&enabled));
+        assert_eq!(changed, vec!["enable_daemons".to_string()]);
+        assert!(!targets.daemons_enabled.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_apply_relearns_new_seed_concepts_only() {
+        let targets = test_targets();
+        let defs = BrainDefinitions {
+            seed_concepts: vec![SeedConcept { concept: "rust is memory-safe".into(), context: None, confidence: Some(0.9) }],
+            ..Default::default()
+        };
+
+        let changed = targets.apply(&defs, None);
+        assert_eq!(changed, vec!["seed:rust is memory-safe".to_string()]);
+
+        // Same concept again (as "previous") shouldn't relearn.
+        assert!(targets.apply(&defs, Some(&defs)).is_empty());
+    }
+}
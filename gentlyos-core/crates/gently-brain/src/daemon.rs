@@ -8,8 +8,12 @@
 //! - Awareness loop
 
 use crate::{Result, Error};
-use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
+use crate::embedding::{EmbeddingProvider, chunk_text, l2_normalize};
+use crate::knowledge::{KnowledgeGraph, VectorChunk};
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering}};
 use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
@@ -19,6 +23,9 @@ pub struct DaemonManager {
     running: Arc<AtomicBool>,
     event_tx: mpsc::UnboundedSender<DaemonEvent>,
     event_rx: Arc<Mutex<mpsc::UnboundedReceiver<DaemonEvent>>>,
+    /// Cycle interval for any `AwarenessDaemon` spawned from here.
+    /// Hot-reloadable via `set_awareness_interval_ms` (see `config_watcher`).
+    awareness_interval_ms: Arc<AtomicU64>,
 }
 
 /// Handle to a running daemon
@@ -27,6 +34,9 @@ pub struct DaemonHandle {
     pub daemon_type: DaemonType,
     pub status: Arc<Mutex<DaemonStatus>>,
     pub stop_flag: Arc<AtomicBool>,
+    /// The supervisor task restarting this daemon on crash, if this daemon
+    /// type is backed by a real background task (see `spawn_supervised_task`).
+    pub supervisor_handle: Option<tokio::task::JoinHandle<()>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -66,6 +76,14 @@ pub enum DaemonType {
     SwarmDefense,         // Coordinates with other nodes
 }
 
+/// Liveness/terminal state of a supervised daemon.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DaemonState {
+    Running,
+    Stopped,
+    Failed,
+}
+
 #[derive(Debug, Clone)]
 pub struct DaemonStatus {
     pub running: bool,
@@ -73,9 +91,50 @@ pub struct DaemonStatus {
     pub cycles: u64,
     pub last_cycle: Option<Instant>,
     pub errors: u32,
+    pub state: DaemonState,
     pub metrics: DaemonMetrics,
 }
 
+/// Exponential backoff for daemon restarts: delay doubles per attempt,
+/// capped at `max`.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    pub base: Duration,
+    pub max: Duration,
+}
+
+impl Backoff {
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.checked_pow(attempt.min(20)).unwrap_or(u32::MAX);
+        self.base.saturating_mul(factor).min(self.max)
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self { base: Duration::from_millis(200), max: Duration::from_secs(30) }
+    }
+}
+
+/// Restart policy for a supervised daemon: how many times it may be
+/// restarted within a sliding window before it's marked `Failed`.
+#[derive(Debug, Clone)]
+pub struct SupervisorConfig {
+    pub max_restarts: u32,
+    pub within: Duration,
+    pub backoff: Backoff,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            max_restarts: 5,
+            within: Duration::from_secs(60),
+            backoff: Backoff::default(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct DaemonMetrics {
     pub items_processed: u64,
@@ -147,15 +206,24 @@ impl DaemonManager {
             running: Arc::new(AtomicBool::new(false)),
             event_tx: tx,
             event_rx: Arc::new(Mutex::new(rx)),
+            awareness_interval_ms: Arc::new(AtomicU64::new(250)),
         }
     }
 
+    /// Change the `AwarenessDaemon` cycle interval, effective on its next
+    /// cycle. Affects every awareness daemon spawned from this manager.
+    pub fn set_awareness_interval_ms(&self, ms: u64) {
+        self.awareness_interval_ms.store(ms.max(1), Ordering::SeqCst);
+    }
+
     /// Start the daemon manager
     pub fn start(&mut self) {
         self.running.store(true, Ordering::SeqCst);
     }
 
-    /// Stop all daemons
+    /// Stop all daemons (soft stop: flips every cancellation flag but does not
+    /// wait for supervisor tasks to exit — use `take_for_stop` + await for a
+    /// single daemon's confirmed shutdown).
     pub fn stop(&mut self) {
         self.running.store(false, Ordering::SeqCst);
         for (_, handle) in &self.daemons {
@@ -163,8 +231,16 @@ impl DaemonManager {
         }
     }
 
-    /// Spawn a new daemon
+    /// Spawn a new daemon with the default restart policy.
     pub fn spawn(&mut self, daemon_type: DaemonType) -> Result<String> {
+        self.spawn_with_supervisor(daemon_type, SupervisorConfig::default())
+    }
+
+    /// Spawn a new daemon, supervised with the given restart policy. Daemon
+    /// types that are fully self-contained (need only a stop flag, status and
+    /// event sender) are backed by a real restarting background task; the
+    /// rest are tracked as bookkeeping only, same as before.
+    pub fn spawn_with_supervisor(&mut self, daemon_type: DaemonType, supervisor: SupervisorConfig) -> Result<String> {
         let name = format!("{:?}_{}", daemon_type, self.daemons.len());
         let stop_flag = Arc::new(AtomicBool::new(false));
         let status = Arc::new(Mutex::new(DaemonStatus {
@@ -173,14 +249,18 @@ impl DaemonManager {
             cycles: 0,
             last_cycle: None,
             errors: 0,
+            state: DaemonState::Running,
             metrics: DaemonMetrics::default(),
         }));
 
+        let supervisor_handle = self.spawn_supervised_task(&name, daemon_type, stop_flag.clone(), status.clone(), supervisor);
+
         let handle = DaemonHandle {
             name: name.clone(),
             daemon_type,
             status: status.clone(),
             stop_flag: stop_flag.clone(),
+            supervisor_handle,
         };
 
         self.daemons.insert(name.clone(), handle);
@@ -191,15 +271,62 @@ impl DaemonManager {
         Ok(name)
     }
 
+    /// Build and launch the supervised background task for a daemon type, if
+    /// it's one `DaemonManager` can fully construct on its own. Returns `None`
+    /// for types that need extra state `DaemonManager` doesn't have (e.g.
+    /// `VectorChain`, wired up directly by `BrainOrchestrator`) or that aren't
+    /// yet backed by a real daemon loop — those stay bookkeeping-only.
+    fn spawn_supervised_task(
+        &self,
+        name: &str,
+        daemon_type: DaemonType,
+        stop_flag: Arc<AtomicBool>,
+        status: Arc<Mutex<DaemonStatus>>,
+        supervisor: SupervisorConfig,
+    ) -> Option<tokio::task::JoinHandle<()>> {
+        let event_tx = self.event_tx.clone();
+        let name = name.to_string();
+
+        let task: SupervisedTask = match daemon_type {
+            DaemonType::Awareness => {
+                let daemon = Arc::new(AwarenessDaemon::new(stop_flag.clone(), status.clone(), event_tx.clone(), self.awareness_interval_ms.clone()));
+                Arc::new(move || {
+                    let daemon = daemon.clone();
+                    Box::pin(async move { daemon.run().await }) as Pin<Box<dyn Future<Output = ()> + Send>>
+                })
+            }
+            DaemonType::IpfsSync => {
+                let daemon = Arc::new(IpfsSyncDaemon::new(stop_flag.clone(), status.clone(), event_tx.clone()));
+                Arc::new(move || {
+                    let daemon = daemon.clone();
+                    Box::pin(async move { daemon.run().await }) as Pin<Box<dyn Future<Output = ()> + Send>>
+                })
+            }
+            DaemonType::GitBranch => {
+                let daemon = Arc::new(GitBranchDaemon::new(stop_flag.clone(), status.clone(), event_tx.clone()));
+                Arc::new(move || {
+                    let daemon = daemon.clone();
+                    Box::pin(async move { daemon.run().await }) as Pin<Box<dyn Future<Output = ()> + Send>>
+                })
+            }
+            _ => return None,
+        };
+
+        Some(tokio::spawn(supervise(name, stop_flag, status, event_tx, supervisor, task)))
+    }
+
     /// Get daemon status
     pub fn status(&self, name: &str) -> Option<DaemonStatus> {
         self.daemons.get(name).map(|h| h.status.lock().unwrap().clone())
     }
 
     /// List all daemons
-    pub fn list(&self) -> Vec<(String, DaemonType, bool)> {
+    pub fn list(&self) -> Vec<(String, DaemonType, bool, DaemonState)> {
         self.daemons.iter()
-            .map(|(name, h)| (name.clone(), h.daemon_type, h.status.lock().unwrap().running))
+            .map(|(name, h)| {
+                let status = h.status.lock().unwrap();
+                (name.clone(), h.daemon_type, status.running, status.state)
+            })
             .collect()
     }
 
@@ -208,20 +335,108 @@ impl DaemonManager {
         self.event_rx.clone()
     }
 
+    /// Clone a sender for daemons constructed outside of `spawn()` (e.g.
+    /// `VectorChainDaemon`, wired up directly by `BrainOrchestrator`)
+    pub fn event_sender(&self) -> mpsc::UnboundedSender<DaemonEvent> {
+        self.event_tx.clone()
+    }
+
     /// Send event
     pub fn emit(&self, event: DaemonEvent) {
         let _ = self.event_tx.send(event);
     }
+
+    /// Take out the pieces needed to actually stop one daemon: its
+    /// cancellation flag, its supervisor task handle (if any, so the caller
+    /// can await real shutdown), and its status. Leaves the `DaemonHandle` in
+    /// place so `status`/`list` keep reporting on it.
+    pub fn take_for_stop(&mut self, name: &str) -> Option<(Arc<AtomicBool>, Option<tokio::task::JoinHandle<()>>, Arc<Mutex<DaemonStatus>>)> {
+        let handle = self.daemons.get_mut(name)?;
+        let supervisor_handle = handle.supervisor_handle.take();
+        Some((handle.stop_flag.clone(), supervisor_handle, handle.status.clone()))
+    }
 }
 
-/// Vector Chain Daemon - processes embeddings continuously
+type SupervisedTask = Arc<dyn Fn() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+
+/// Run `task`, restarting it with exponential backoff if it panics, up to
+/// `supervisor.max_restarts` attempts within `supervisor.within`. A clean
+/// task exit (or `stop_flag` being set) ends supervision without restarting;
+/// exhausting the restart budget marks the daemon `Failed` and emits a
+/// `DaemonEvent::Error`.
+async fn supervise(
+    name: String,
+    stop_flag: Arc<AtomicBool>,
+    status: Arc<Mutex<DaemonStatus>>,
+    event_tx: mpsc::UnboundedSender<DaemonEvent>,
+    supervisor: SupervisorConfig,
+    task: SupervisedTask,
+) {
+    let mut restart_times: Vec<Instant> = Vec::new();
+
+    loop {
+        if stop_flag.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let result = tokio::spawn(task()).await;
+
+        if stop_flag.load(Ordering::SeqCst) {
+            return;
+        }
+
+        match result {
+            Ok(()) => return,
+            Err(join_err) => {
+                let now = Instant::now();
+                restart_times.retain(|t| now.duration_since(*t) <= supervisor.within);
+
+                if restart_times.len() as u32 >= supervisor.max_restarts {
+                    {
+                        let mut s = status.lock().unwrap();
+                        s.running = false;
+                        s.state = DaemonState::Failed;
+                    }
+                    let _ = event_tx.send(DaemonEvent::Error {
+                        daemon: name.clone(),
+                        error: format!(
+                            "restart budget exhausted ({} restarts within {:?}): {}",
+                            supervisor.max_restarts, supervisor.within, join_err
+                        ),
+                    });
+                    return;
+                }
+
+                restart_times.push(now);
+                let attempt = restart_times.len() as u32;
+                let _ = event_tx.send(DaemonEvent::Error {
+                    daemon: name.clone(),
+                    error: format!(
+                        "daemon task exited unexpectedly, restarting (attempt {}/{}): {}",
+                        attempt, supervisor.max_restarts, join_err
+                    ),
+                });
+                tokio::time::sleep(supervisor.backoff.delay(attempt)).await;
+            }
+        }
+    }
+}
+
+/// Vector Chain Daemon - chunks, embeds and stores concept text in batches
 pub struct VectorChainDaemon {
     stop_flag: Arc<AtomicBool>,
     status: Arc<Mutex<DaemonStatus>>,
     queue: Arc<Mutex<Vec<VectorJob>>>,
     event_tx: mpsc::UnboundedSender<DaemonEvent>,
+    knowledge_graph: Arc<KnowledgeGraph>,
+    embedding_provider: Arc<dyn EmbeddingProvider>,
+    /// Hot-reloadable via `set_batch_size` (see `config_watcher`).
+    batch_size: AtomicUsize,
+    chunk_chars: usize,
 }
 
+/// A concept whose source text still needs to be chunked, embedded and
+/// stored. `id` is the concept's `KnowledgeNode` id.
 #[derive(Debug, Clone)]
 pub struct VectorJob {
     pub id: String,
@@ -234,12 +449,20 @@ impl VectorChainDaemon {
         stop_flag: Arc<AtomicBool>,
         status: Arc<Mutex<DaemonStatus>>,
         event_tx: mpsc::UnboundedSender<DaemonEvent>,
+        knowledge_graph: Arc<KnowledgeGraph>,
+        embedding_provider: Arc<dyn EmbeddingProvider>,
+        batch_size: usize,
+        chunk_chars: usize,
     ) -> Self {
         Self {
             stop_flag,
             status,
             queue: Arc::new(Mutex::new(Vec::new())),
             event_tx,
+            knowledge_graph,
+            embedding_provider,
+            batch_size: AtomicUsize::new(batch_size.max(1)),
+            chunk_chars: chunk_chars.max(1),
         }
     }
 
@@ -249,33 +472,75 @@ impl VectorChainDaemon {
         queue.sort_by(|a, b| b.priority.cmp(&a.priority));
     }
 
-    pub async fn run(&self) {
-        while !self.stop_flag.load(Ordering::SeqCst) {
-            // Process queue
-            let job = {
-                let mut queue = self.queue.lock().unwrap();
-                queue.pop()
-            };
+    /// Change how many jobs `run()` pulls off the queue per cycle, effective
+    /// on the next cycle.
+    pub fn set_batch_size(&self, batch_size: usize) {
+        self.batch_size.store(batch_size.max(1), Ordering::SeqCst);
+    }
 
-            if let Some(job) = job {
-                // Compute embedding (simulated)
-                let vector_dim = 384; // Typical embedding dimension
+    /// Pop the next queued job, if any (same ordering `run()`'s batches use).
+    pub(crate) fn pop_job(&self) -> Option<VectorJob> {
+        self.queue.lock().unwrap().pop()
+    }
+
+    /// Chunk, embed and store one job's text. Split out of `run()` so it can
+    /// be exercised directly without a running tokio task (e.g. for tests,
+    /// or a caller that wants synchronous embedding of a single concept).
+    pub(crate) fn process_job(&self, job: &VectorJob) {
+        let chunks = chunk_text(&job.content, self.chunk_chars);
+        if chunks.is_empty() {
+            return;
+        }
+        let texts: Vec<String> = chunks.iter().map(|(text, _)| text.clone()).collect();
+
+        match self.embedding_provider.embed(&texts) {
+            Ok(vectors) => {
+                let stored: Vec<VectorChunk> = chunks.iter().zip(vectors.into_iter())
+                    .map(|((_, range), mut vector)| {
+                        l2_normalize(&mut vector);
+                        VectorChunk { concept_id: job.id.clone(), range: *range, vector }
+                    })
+                    .collect();
+                let chunk_count = stored.len() as u64;
+                self.knowledge_graph.store_vector_chunks(&job.id, stored);
 
-                // Update metrics
                 {
                     let mut status = self.status.lock().unwrap();
                     status.cycles += 1;
                     status.last_cycle = Some(Instant::now());
-                    status.metrics.vectors_computed += 1;
+                    status.metrics.vectors_computed += chunk_count;
                     status.metrics.items_processed += 1;
                 }
 
-                // Emit event
                 let _ = self.event_tx.send(DaemonEvent::VectorComputed {
-                    id: job.id,
-                    dimensions: vector_dim,
+                    id: job.id.clone(),
+                    dimensions: self.embedding_provider.dimensions(),
                 });
             }
+            Err(e) => {
+                {
+                    let mut status = self.status.lock().unwrap();
+                    status.errors += 1;
+                }
+                let _ = self.event_tx.send(DaemonEvent::Error {
+                    daemon: "vector_chain".to_string(),
+                    error: e.to_string(),
+                });
+            }
+        }
+    }
+
+    pub async fn run(&self) {
+        while !self.stop_flag.load(Ordering::SeqCst) {
+            let batch: Vec<VectorJob> = {
+                let mut queue = self.queue.lock().unwrap();
+                let n = self.batch_size.load(Ordering::SeqCst).min(queue.len());
+                queue.split_off(queue.len() - n)
+            };
+
+            for job in &batch {
+                self.process_job(job);
+            }
 
             // Sleep between cycles
             tokio::time::sleep(Duration::from_millis(100)).await;
@@ -353,6 +618,8 @@ pub struct AwarenessDaemon {
     status: Arc<Mutex<DaemonStatus>>,
     state: Arc<Mutex<AwarenessState>>,
     event_tx: mpsc::UnboundedSender<DaemonEvent>,
+    /// Hot-reloadable via `DaemonManager::set_awareness_interval_ms`.
+    interval_ms: Arc<AtomicU64>,
 }
 
 impl AwarenessDaemon {
@@ -360,6 +627,7 @@ impl AwarenessDaemon {
         stop_flag: Arc<AtomicBool>,
         status: Arc<Mutex<DaemonStatus>>,
         event_tx: mpsc::UnboundedSender<DaemonEvent>,
+        interval_ms: Arc<AtomicU64>,
     ) -> Self {
         Self {
             stop_flag,
@@ -372,6 +640,7 @@ impl AwarenessDaemon {
                 growth_direction: "general".into(),
             })),
             event_tx,
+            interval_ms,
         }
     }
 
@@ -444,7 +713,7 @@ impl AwarenessDaemon {
                 let _ = self.event_tx.send(DaemonEvent::AwarenessState { state });
             }
 
-            tokio::time::sleep(Duration::from_millis(250)).await;
+            tokio::time::sleep(Duration::from_millis(self.interval_ms.load(Ordering::SeqCst))).await;
         }
     }
 }
@@ -558,4 +827,92 @@ mod tests {
         let name = manager.spawn(DaemonType::VectorChain).unwrap();
         assert!(manager.status(&name).is_some());
     }
+
+    #[tokio::test]
+    async fn test_supervise_restarts_then_marks_failed_after_budget_exhausted() {
+        use std::sync::atomic::AtomicU32;
+
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let status = Arc::new(Mutex::new(DaemonStatus {
+            running: true,
+            started_at: None,
+            cycles: 0,
+            last_cycle: None,
+            errors: 0,
+            state: DaemonState::Running,
+            metrics: DaemonMetrics::default(),
+        }));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let attempts = Arc::new(AtomicU32::new(0));
+        let attempts_for_task = attempts.clone();
+        let task: SupervisedTask = Arc::new(move || {
+            let attempts = attempts_for_task.clone();
+            Box::pin(async move {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                panic!("simulated crash");
+            }) as Pin<Box<dyn Future<Output = ()> + Send>>
+        });
+
+        let supervisor = SupervisorConfig {
+            max_restarts: 2,
+            within: Duration::from_secs(60),
+            backoff: Backoff { base: Duration::from_millis(1), max: Duration::from_millis(5) },
+        };
+
+        supervise("test_daemon".to_string(), stop_flag, status.clone(), tx, supervisor, task).await;
+
+        // Initial attempt plus 2 restarts, then the budget is exhausted.
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(status.lock().unwrap().state, DaemonState::Failed);
+
+        let mut saw_error = false;
+        while let Ok(event) = rx.try_recv() {
+            if matches!(event, DaemonEvent::Error { .. }) {
+                saw_error = true;
+            }
+        }
+        assert!(saw_error, "expected at least one DaemonEvent::Error");
+    }
+
+    #[test]
+    fn test_vector_chain_daemon_embeds_and_stores_chunks() {
+        use crate::embedding::HashEmbedder;
+        use crate::knowledge::KnowledgeGraph;
+
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let knowledge_graph = Arc::new(KnowledgeGraph::new());
+        let daemon = VectorChainDaemon::new(
+            Arc::new(AtomicBool::new(false)),
+            Arc::new(Mutex::new(DaemonStatus {
+                running: true,
+                started_at: None,
+                cycles: 0,
+                last_cycle: None,
+                errors: 0,
+                state: DaemonState::Running,
+                metrics: DaemonMetrics::default(),
+            })),
+            tx,
+            knowledge_graph.clone(),
+            Arc::new(HashEmbedder::new(32)),
+            5,
+            8,
+        );
+
+        daemon.enqueue(VectorJob {
+            id: "concept_1".to_string(),
+            content: "a sentence long enough to span several chunks".to_string(),
+            priority: 1,
+        });
+        let job = daemon.pop_job().unwrap();
+        daemon.process_job(&job);
+
+        let similar = knowledge_graph.similar_by_vector(
+            &crate::embedding::HashEmbedder::new(32).embed(&["a sentence".to_string()]).unwrap()[0],
+            5,
+        );
+        assert!(similar.iter().any(|(id, _)| id == "concept_1"));
+        assert_eq!(daemon.status.lock().unwrap().metrics.items_processed, 1);
+    }
 }
@@ -0,0 +1,194 @@
+//! In-flight deduplication for expensive, idempotent tool calls
+//!
+//! `tool_alexandria_wormhole` (graph path search), `tool_grow`, and
+//! `tool_alexandria_drift` can be fired concurrently with identical inputs,
+//! duplicating expensive work. `DedupMap` is a `ProcessMap`-style guard -
+//! the pattern pict-rs uses to collapse concurrent identical uploads - keyed
+//! by `(tool_name, canonicalized_input)`. The first caller for a key inserts
+//! a broadcast sender and runs the future; concurrent callers with the same
+//! key subscribe and get a clone of the same `ToolResult` instead of
+//! recomputing. The entry is removed once the leader finishes - success,
+//! error, or panic - via a drop guard, so keys never leak and a later call
+//! with the same key genuinely recomputes.
+//!
+//! Only read-only tools should ever be routed through `DedupMap::run`;
+//! `is_dedupable` is the single place that decides which ones, so an
+//! intended repeated side-effecting write is never collapsed into one call.
+
+use crate::mcp::ToolResult;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use std::future::Future;
+use tokio::sync::broadcast;
+
+/// Tools safe to dedup: pure reads over already-recorded state.
+const DEDUPABLE_TOOLS: &[&str] = &["alexandria_wormhole", "grow", "alexandria_drift"];
+
+/// Whether `tool` is safe to route through `DedupMap::run`.
+pub fn is_dedupable(tool: &str) -> bool {
+    DEDUPABLE_TOOLS.contains(&tool)
+}
+
+/// A stable dedup key for `value`: object keys are sorted recursively so
+/// two JSON values that differ only in key order canonicalize identically.
+fn canonicalize(value: &serde_json::Value) -> String {
+    fn sorted(value: &serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(map) => {
+                let mut sorted_map: Vec<(String, serde_json::Value)> =
+                    map.iter().map(|(k, v)| (k.clone(), sorted(v))).collect();
+                sorted_map.sort_by(|a, b| a.0.cmp(&b.0));
+                serde_json::Value::Object(sorted_map.into_iter().collect())
+            }
+            serde_json::Value::Array(items) => {
+                serde_json::Value::Array(items.iter().map(sorted).collect())
+            }
+            other => other.clone(),
+        }
+    }
+    sorted(value).to_string()
+}
+
+type Key = (String, String);
+
+/// Removes `key` from `map` when dropped, including during an unwind, so an
+/// in-flight entry is never left behind after its leader finishes.
+struct RemoveOnDrop<'a> {
+    map: &'a DashMap<Key, broadcast::Sender<ToolResult>>,
+    key: Key,
+}
+
+impl Drop for RemoveOnDrop<'_> {
+    fn drop(&mut self) {
+        self.map.remove(&self.key);
+    }
+}
+
+/// Collapses concurrent identical calls to dedupable tools into one.
+#[derive(Default)]
+pub struct DedupMap {
+    inflight: DashMap<Key, broadcast::Sender<ToolResult>>,
+}
+
+impl DedupMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `compute` for `(tool, input)`, deduplicating concurrent calls
+    /// with the same canonicalized key. Callers are expected to have
+    /// already checked `is_dedupable(tool)`.
+    pub async fn run<F, Fut>(&self, tool: &str, input: &serde_json::Value, compute: F) -> ToolResult
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ToolResult>,
+    {
+        let key = (tool.to_string(), canonicalize(input));
+
+        loop {
+            // Subscribe without keeping a sender clone, so the channel closes
+            // once the leader's guard and sender are gone.
+            if let Some(mut receiver) = self.inflight.get(&key).map(|entry| entry.subscribe()) {
+                match receiver.recv().await {
+                    Ok(result) => return result,
+                    // Leader panicked before sending; retry as the new leader.
+                    Err(_) => continue,
+                }
+            }
+
+            let (sender, _) = broadcast::channel(1);
+            let became_leader = match self.inflight.entry(key.clone()) {
+                Entry::Occupied(_) => false,
+                Entry::Vacant(entry) => {
+                    entry.insert(sender.clone());
+                    true
+                }
+            };
+            if !became_leader {
+                continue;
+            }
+
+            let _guard = RemoveOnDrop { map: &self.inflight, key: key.clone() };
+            let result = compute().await;
+            let _ = sender.send(result.clone());
+            return result;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::ToolResult;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn ok_result(tool: &str) -> ToolResult {
+        ToolResult {
+            tool: tool.to_string(),
+            success: true,
+            output: serde_json::json!({}),
+            side_effects: vec![],
+            learnings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_is_dedupable_only_matches_listed_tools() {
+        assert!(is_dedupable("alexandria_wormhole"));
+        assert!(is_dedupable("grow"));
+        assert!(is_dedupable("alexandria_drift"));
+        assert!(!is_dedupable("alexandria_record"));
+    }
+
+    #[test]
+    fn test_canonicalize_ignores_object_key_order() {
+        let a = serde_json::json!({ "from": "x", "to": "y" });
+        let b = serde_json::json!({ "to": "y", "from": "x" });
+        assert_eq!(canonicalize(&a), canonicalize(&b));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_identical_calls_run_compute_once() {
+        let dedup = Arc::new(DedupMap::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let input = serde_json::json!({ "concept": "shared" });
+
+        let mut handles = Vec::new();
+        for _ in 0..8 {
+            let dedup = dedup.clone();
+            let calls = calls.clone();
+            let input = input.clone();
+            handles.push(tokio::spawn(async move {
+                dedup.run("grow", &input, || async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                    ok_result("grow")
+                }).await
+            }));
+        }
+
+        for handle in handles {
+            let result = handle.await.unwrap();
+            assert!(result.success);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1, "all concurrent identical calls should share one computation");
+    }
+
+    #[tokio::test]
+    async fn test_entry_is_removed_after_completion_so_later_calls_recompute() {
+        let dedup = DedupMap::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let input = serde_json::json!({ "concept": "sequential" });
+
+        for _ in 0..2 {
+            let calls = calls.clone();
+            dedup.run("grow", &input, || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                ok_result("grow")
+            }).await;
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2, "a call made after the prior one finished should recompute");
+    }
+}
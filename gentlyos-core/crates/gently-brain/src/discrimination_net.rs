@@ -0,0 +1,270 @@
+//! Discrimination network for trigger/pattern matching
+//!
+//! `SkillRegistry::find_by_trigger` and `BrainOrchestrator::is_learnable`
+//! used to linearly scan every registered trigger/pattern against every
+//! thought, which is O(patterns) per thought and only gets worse as skills
+//! are added at runtime (see `config_watcher`). This indexes triggers into a
+//! trie keyed by token *position*, not value: each position is either a
+//! required literal ("const") slot or a wildcard slot, and patterns that
+//! share the same const/wildcard skeleton share the same trie path. A leaf
+//! holds a `leaf_map` from the tuple of literal tokens required at that
+//! skeleton's const positions to the set of trigger ids with exactly those
+//! literals, so a single walk down the tree — branching at each position
+//! into its const child, its wildcard child, or both — identifies every
+//! matching trigger in one pass, independent of how many triggers are
+//! registered.
+//!
+//! A `*` token in a trigger is a wildcard: it matches exactly one token, and
+//! its matched value is returned to the caller as a binding.
+
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum PatternToken {
+    Const(String),
+    Wildcard,
+}
+
+#[derive(Debug, Clone)]
+struct Pattern {
+    tokens: Vec<PatternToken>,
+}
+
+impl Pattern {
+    fn compile(trigger: &str) -> Self {
+        let tokens = trigger
+            .to_lowercase()
+            .split_whitespace()
+            .map(|word| if word == "*" { PatternToken::Wildcard } else { PatternToken::Const(word.to_string()) })
+            .collect();
+        Self { tokens }
+    }
+
+    /// The literal tokens required at this pattern's const positions, in
+    /// order — the key a leaf's `leaf_map` is looked up by.
+    fn const_tuple(&self) -> Vec<String> {
+        self.tokens.iter()
+            .filter_map(|t| match t {
+                PatternToken::Const(word) => Some(word.clone()),
+                PatternToken::Wildcard => None,
+            })
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct Continuation {
+    leaf_map: HashMap<Vec<String>, HashSet<String>>,
+}
+
+#[derive(Default)]
+struct Node {
+    const_child: Option<Box<Node>>,
+    wildcard_child: Option<Box<Node>>,
+    leaf: Option<Continuation>,
+}
+
+impl Node {
+    fn insert(&mut self, tokens: &[PatternToken], depth: usize, id: &str, const_tuple: &[String]) {
+        if depth == tokens.len() {
+            self.leaf.get_or_insert_with(Continuation::default)
+                .leaf_map.entry(const_tuple.to_vec()).or_default()
+                .insert(id.to_string());
+            return;
+        }
+        let child = match &tokens[depth] {
+            PatternToken::Const(_) => self.const_child.get_or_insert_with(|| Box::new(Node::default())),
+            PatternToken::Wildcard => self.wildcard_child.get_or_insert_with(|| Box::new(Node::default())),
+        };
+        child.insert(tokens, depth + 1, id, const_tuple);
+    }
+
+    fn remove(&mut self, tokens: &[PatternToken], depth: usize, id: &str, const_tuple: &[String]) {
+        if depth == tokens.len() {
+            if let Some(leaf) = &mut self.leaf {
+                if let Some(ids) = leaf.leaf_map.get_mut(const_tuple) {
+                    ids.remove(id);
+                    if ids.is_empty() {
+                        leaf.leaf_map.remove(const_tuple);
+                    }
+                }
+            }
+            return;
+        }
+        let child = match &tokens[depth] {
+            PatternToken::Const(_) => self.const_child.as_mut(),
+            PatternToken::Wildcard => self.wildcard_child.as_mut(),
+        };
+        if let Some(child) = child {
+            child.remove(tokens, depth + 1, id, const_tuple);
+        }
+    }
+
+    /// Walk every live path from this node against `words[start..]`,
+    /// recording a match for each leaf reached whose `leaf_map` recognizes
+    /// the const tokens observed along the way.
+    fn walk(
+        &self,
+        words: &[String],
+        start: usize,
+        depth: usize,
+        const_so_far: &mut Vec<String>,
+        bindings: &mut Vec<String>,
+        out: &mut Vec<Match>,
+    ) {
+        if let Some(leaf) = &self.leaf {
+            if let Some(ids) = leaf.leaf_map.get(const_so_far) {
+                out.push(Match { ids: ids.clone(), bindings: bindings.clone(), start, len: depth });
+            }
+        }
+        if start + depth >= words.len() {
+            return;
+        }
+        let word = &words[start + depth];
+        if let Some(child) = &self.const_child {
+            const_so_far.push(word.clone());
+            child.walk(words, start, depth + 1, const_so_far, bindings, out);
+            const_so_far.pop();
+        }
+        if let Some(child) = &self.wildcard_child {
+            bindings.push(word.clone());
+            child.walk(words, start, depth + 1, const_so_far, bindings, out);
+            bindings.pop();
+        }
+    }
+}
+
+/// A trigger match found somewhere inside the matched text.
+#[derive(Debug, Clone)]
+pub struct Match {
+    /// Trigger ids (e.g. skill names) whose pattern matched this window.
+    pub ids: HashSet<String>,
+    /// Tokens captured by `*` wildcards, in left-to-right order.
+    pub bindings: Vec<String>,
+    /// Token index the match starts at.
+    pub start: usize,
+    /// Number of tokens the match spans.
+    pub len: usize,
+}
+
+/// An incrementally-updatable index of token patterns (trigger phrases,
+/// learnable-content markers, ...) supporting sublinear matching against
+/// arbitrary text.
+#[derive(Default)]
+pub struct DiscriminationNet {
+    root: Node,
+    /// Patterns registered per id, kept around so `remove` can find every
+    /// trie path that id touched.
+    id_patterns: HashMap<String, Vec<Pattern>>,
+}
+
+impl DiscriminationNet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `trigger` under `id`. An id may have multiple triggers
+    /// (e.g. a skill with several trigger phrases) — each is indexed
+    /// independently.
+    pub fn add(&mut self, id: &str, trigger: &str) {
+        let pattern = Pattern::compile(trigger);
+        let const_tuple = pattern.const_tuple();
+        self.root.insert(&pattern.tokens, 0, id, &const_tuple);
+        self.id_patterns.entry(id.to_string()).or_default().push(pattern);
+    }
+
+    /// Remove every trigger registered under `id`.
+    pub fn remove(&mut self, id: &str) {
+        if let Some(patterns) = self.id_patterns.remove(id) {
+            for pattern in &patterns {
+                let const_tuple = pattern.const_tuple();
+                self.root.remove(&pattern.tokens, 0, id, &const_tuple);
+            }
+        }
+    }
+
+    /// Find every id whose trigger matches some contiguous token window of
+    /// `text`, along with its captured wildcard bindings.
+    pub fn matches(&self, text: &str) -> Vec<Match> {
+        let words: Vec<String> = text.to_lowercase().split_whitespace().map(String::from).collect();
+        let mut out = Vec::new();
+        for start in 0..words.len() {
+            let mut const_so_far = Vec::new();
+            let mut bindings = Vec::new();
+            self.root.walk(&words, start, 0, &mut const_so_far, &mut bindings, &mut out);
+        }
+        out
+    }
+
+    /// Whether any registered trigger matches somewhere in `text`.
+    pub fn has_match(&self, text: &str) -> bool {
+        let words: Vec<String> = text.to_lowercase().split_whitespace().map(String::from).collect();
+        for start in 0..words.len() {
+            let mut out = Vec::new();
+            let mut const_so_far = Vec::new();
+            let mut bindings = Vec::new();
+            self.root.walk(&words, start, 0, &mut const_so_far, &mut bindings, &mut out);
+            if !out.is_empty() {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal_trigger_matches() {
+        let mut net = DiscriminationNet::new();
+        net.add("hash_crack", "crack hash");
+        net.add("hash_crack", "find password");
+
+        let matches = net.matches("can you find password abc123");
+        assert!(matches.iter().any(|m| m.ids.contains("hash_crack") && m.bindings.is_empty()));
+    }
+
+    #[test]
+    fn test_wildcard_binds_captured_token() {
+        let mut net = DiscriminationNet::new();
+        net.add("recall", "recall *");
+
+        let matches = net.matches("recall endpoint");
+        let m = matches.iter().find(|m| m.ids.contains("recall")).expect("expected a match");
+        assert_eq!(m.bindings, vec!["endpoint".to_string()]);
+    }
+
+    #[test]
+    fn test_shared_skeleton_disambiguates_by_const_tuple() {
+        let mut net = DiscriminationNet::new();
+        net.add("is_a", "* is cool");
+        net.add("not_cool", "* is boring");
+
+        let matches = net.matches("rust is cool");
+        let ids: HashSet<String> = matches.into_iter().flat_map(|m| m.ids).collect();
+        assert!(ids.contains("is_a"));
+        assert!(!ids.contains("not_cool"));
+    }
+
+    #[test]
+    fn test_remove_clears_all_triggers_for_id() {
+        let mut net = DiscriminationNet::new();
+        net.add("learn", "learn");
+        net.add("learn", "remember");
+        assert!(net.has_match("please learn this"));
+        assert!(net.has_match("please remember this"));
+
+        net.remove("learn");
+        assert!(!net.has_match("please learn this"));
+        assert!(!net.has_match("please remember this"));
+    }
+
+    #[test]
+    fn test_no_match_returns_empty() {
+        let mut net = DiscriminationNet::new();
+        net.add("hash_crack", "crack hash");
+        assert!(!net.has_match("nothing relevant here"));
+    }
+}
@@ -0,0 +1,263 @@
+//! Pluggable embedding backends
+//!
+//! `EmbeddingProvider` abstracts over how text becomes a vector so
+//! `knowledge_similar` and `VectorChainDaemon` don't care whether that's a
+//! hosted API, a local model server, or — when neither is configured — a
+//! deterministic offline fallback. The concrete backend is selected via
+//! `BrainConfig::embedding_backend`.
+
+use crate::orchestrator::{BrainConfig, EmbeddingBackend};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Something that can turn text into embedding vectors.
+pub trait EmbeddingProvider: Send + Sync {
+    /// Embed a batch of texts, returning one vector per input, in order.
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+
+    /// Dimensionality of the vectors this provider produces.
+    fn dimensions(&self) -> usize;
+}
+
+/// OpenAI's `/v1/embeddings` endpoint.
+pub struct OpenAiEmbedder {
+    api_key: String,
+    model: String,
+    dimensions: usize,
+}
+
+impl OpenAiEmbedder {
+    pub fn new(api_key: impl Into<String>, model: impl Into<String>, dimensions: usize) -> Self {
+        Self { api_key: api_key.into(), model: model.into(), dimensions }
+    }
+}
+
+#[derive(Serialize)]
+struct OpenAiEmbedRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct OpenAiEmbedResponse {
+    data: Vec<OpenAiEmbedDatum>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiEmbedDatum {
+    embedding: Vec<f32>,
+}
+
+impl EmbeddingProvider for OpenAiEmbedder {
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let request = OpenAiEmbedRequest { model: &self.model, input: texts };
+
+        let response = ureq::post("https://api.openai.com/v1/embeddings")
+            .set("authorization", &format!("Bearer {}", self.api_key))
+            .set("content-type", "application/json")
+            .send_json(&request);
+
+        match response {
+            Ok(resp) => {
+                let body: OpenAiEmbedResponse = resp.into_json()
+                    .map_err(|e| Error::EmbeddingFailed(format!("OpenAI response parse error: {}", e)))?;
+                Ok(body.data.into_iter().map(|d| d.embedding).collect())
+            }
+            Err(ureq::Error::Status(code, resp)) => {
+                let message = resp.into_string().unwrap_or_else(|_| format!("HTTP {}", code));
+                Err(Error::EmbeddingFailed(format!("OpenAI embeddings request failed: {}", message)))
+            }
+            Err(e) => Err(Error::EmbeddingFailed(format!("OpenAI embeddings request failed: {}", e))),
+        }
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+}
+
+/// A local Ollama server's `/api/embeddings` endpoint.
+pub struct OllamaEmbedder {
+    endpoint: String,
+    model: String,
+    dimensions: usize,
+}
+
+impl OllamaEmbedder {
+    pub fn new(endpoint: impl Into<String>, model: impl Into<String>, dimensions: usize) -> Self {
+        Self { endpoint: endpoint.into(), model: model.into(), dimensions }
+    }
+}
+
+#[derive(Serialize)]
+struct OllamaEmbedRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+}
+
+#[derive(Deserialize)]
+struct OllamaEmbedResponse {
+    embedding: Vec<f32>,
+}
+
+impl EmbeddingProvider for OllamaEmbedder {
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        // Ollama's embeddings endpoint takes one prompt per request.
+        texts.iter().map(|text| {
+            let request = OllamaEmbedRequest { model: &self.model, prompt: text };
+            let url = format!("{}/api/embeddings", self.endpoint.trim_end_matches('/'));
+
+            match ureq::post(&url).send_json(&request) {
+                Ok(resp) => {
+                    let body: OllamaEmbedResponse = resp.into_json()
+                        .map_err(|e| Error::EmbeddingFailed(format!("Ollama response parse error: {}", e)))?;
+                    Ok(body.embedding)
+                }
+                Err(ureq::Error::Status(code, resp)) => {
+                    let message = resp.into_string().unwrap_or_else(|_| format!("HTTP {}", code));
+                    Err(Error::EmbeddingFailed(format!("Ollama embeddings request failed: {}", message)))
+                }
+                Err(e) => Err(Error::EmbeddingFailed(format!("Ollama embeddings request failed: {}", e))),
+            }
+        }).collect()
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+}
+
+/// No-network deterministic fallback — the same hashing trick `Embedder`
+/// already falls back to without the `fastembed` feature, kept here so a
+/// provider is always available with no API key and no local model server.
+pub struct HashEmbedder {
+    dimensions: usize,
+}
+
+impl HashEmbedder {
+    pub fn new(dimensions: usize) -> Self {
+        Self { dimensions }
+    }
+}
+
+impl Default for HashEmbedder {
+    fn default() -> Self {
+        Self::new(384)
+    }
+}
+
+impl EmbeddingProvider for HashEmbedder {
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|text| hash_embedding(text, self.dimensions)).collect())
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+}
+
+fn hash_embedding(text: &str, dimensions: usize) -> Vec<f32> {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut embedding = vec![0.0f32; dimensions];
+    for (i, chunk) in text.as_bytes().chunks(4).enumerate() {
+        let mut hasher = DefaultHasher::new();
+        chunk.hash(&mut hasher);
+        let hash = hasher.finish();
+        let idx = i % dimensions;
+        embedding[idx] += ((hash % 1000) as f32 / 500.0) - 1.0;
+    }
+    l2_normalize(&mut embedding);
+    embedding
+}
+
+/// Normalize a vector to unit length in place (a no-op on an all-zero vector).
+pub fn l2_normalize(vector: &mut [f32]) {
+    let norm: f32 = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for x in vector.iter_mut() {
+            *x /= norm;
+        }
+    }
+}
+
+/// Split `text` into contiguous windows of at most `max_chars` characters (a
+/// conservative proxy for "sub-token-limit" without pulling in a real
+/// tokenizer), returning each chunk alongside its `(start, end)` char range
+/// in the original text.
+pub fn chunk_text(text: &str, max_chars: usize) -> Vec<(String, (usize, usize))> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+    let max_chars = max_chars.max(1);
+    let chars: Vec<char> = text.chars().collect();
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let end = (start + max_chars).min(chars.len());
+        chunks.push((chars[start..end].iter().collect(), (start, end)));
+        start = end;
+    }
+    chunks
+}
+
+/// Build the embedding provider selected by `BrainConfig::embedding_backend`.
+pub fn build_embedding_provider(config: &BrainConfig) -> Arc<dyn EmbeddingProvider> {
+    match &config.embedding_backend {
+        EmbeddingBackend::OpenAi { api_key, model, dimensions } => {
+            Arc::new(OpenAiEmbedder::new(api_key.clone(), model.clone(), *dimensions))
+        }
+        EmbeddingBackend::Ollama { endpoint, model, dimensions } => {
+            Arc::new(OllamaEmbedder::new(endpoint.clone(), model.clone(), *dimensions))
+        }
+        EmbeddingBackend::Hash { dimensions } => Arc::new(HashEmbedder::new(*dimensions)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_embedder_is_deterministic_and_normalized() {
+        let embedder = HashEmbedder::new(32);
+        let vectors = embedder.embed(&["hello world".to_string(), "hello world".to_string()]).unwrap();
+        assert_eq!(vectors[0], vectors[1]);
+
+        let norm: f32 = vectors[0].iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 0.01 || norm == 0.0);
+    }
+
+    #[test]
+    fn test_chunk_text_covers_whole_string_without_overlap() {
+        let text = "abcdefghij";
+        let chunks = chunk_text(text, 4);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0], ("abcd".to_string(), (0, 4)));
+        assert_eq!(chunks[1], ("efgh".to_string(), (4, 8)));
+        assert_eq!(chunks[2], ("ij".to_string(), (8, 10)));
+    }
+
+    #[test]
+    fn test_chunk_text_empty_input() {
+        assert!(chunk_text("", 10).is_empty());
+    }
+
+    #[test]
+    fn test_l2_normalize() {
+        let mut v = vec![3.0, 4.0];
+        l2_normalize(&mut v);
+        assert!((v[0] - 0.6).abs() < 0.001);
+        assert!((v[1] - 0.8).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_build_embedding_provider_hash_backend() {
+        let config = BrainConfig { embedding_backend: EmbeddingBackend::Hash { dimensions: 64 }, ..BrainConfig::default() };
+        let provider = build_embedding_provider(&config);
+        assert_eq!(provider.dimensions(), 64);
+    }
+}
@@ -65,6 +65,16 @@ pub enum EdgeType {
     UsedIn,      // A is used in B
 }
 
+/// An embedded chunk of a concept's source text: the concept it was learned
+/// from, its char range within that text (so a similarity hit can be traced
+/// back to what was actually said), and its L2-normalized vector.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VectorChunk {
+    pub concept_id: String,
+    pub range: (usize, usize),
+    pub vector: Vec<f32>,
+}
+
 /// The knowledge graph
 #[derive(Clone)]
 pub struct KnowledgeGraph {
@@ -72,6 +82,7 @@ pub struct KnowledgeGraph {
     edges: Arc<Mutex<Vec<KnowledgeEdge>>>,
     index: Arc<Mutex<GraphIndex>>,
     growth_log: Arc<Mutex<Vec<GrowthEvent>>>,
+    vector_chunks: Arc<Mutex<Vec<VectorChunk>>>,
 }
 
 /// Index for fast lookups
@@ -110,6 +121,7 @@ impl KnowledgeGraph {
             edges: Arc::new(Mutex::new(Vec::new())),
             index: Arc::new(Mutex::new(GraphIndex::default())),
             growth_log: Arc::new(Mutex::new(Vec::new())),
+            vector_chunks: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -431,6 +443,35 @@ impl KnowledgeGraph {
         similarities
     }
 
+    /// Store embedded chunks of a concept's source text (see `VectorChunk`),
+    /// replacing any chunks previously stored for that concept. This is what
+    /// `VectorChainDaemon` calls once it's embedded a learned concept.
+    pub fn store_vector_chunks(&self, concept_id: &str, chunks: Vec<VectorChunk>) {
+        let mut store = self.vector_chunks.lock().unwrap();
+        store.retain(|c| c.concept_id != concept_id);
+        store.extend(chunks);
+    }
+
+    /// Find the concepts whose stored chunks are most similar to an already-
+    /// embedded query vector, via plain dot product over L2-normalized
+    /// vectors. Each concept is ranked by its single best-matching chunk.
+    pub fn similar_by_vector(&self, query: &[f32], top_k: usize) -> Vec<(String, f32)> {
+        let store = self.vector_chunks.lock().unwrap();
+
+        let mut best: HashMap<String, f32> = HashMap::new();
+        for chunk in store.iter() {
+            let score = dot(query, &chunk.vector);
+            best.entry(chunk.concept_id.clone())
+                .and_modify(|existing| *existing = existing.max(score))
+                .or_insert(score);
+        }
+
+        let mut ranked: Vec<(String, f32)> = best.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        ranked.truncate(top_k);
+        ranked
+    }
+
     /// Export graph for IPFS
     pub fn export(&self) -> Vec<u8> {
         let nodes = self.nodes.lock().unwrap();
@@ -827,6 +868,13 @@ fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     }
 }
 
+/// Plain dot product — cosine similarity without the normalization step,
+/// valid as long as both vectors are already unit length (true of every
+/// `VectorChunk`, which `VectorChainDaemon` L2-normalizes before storing).
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
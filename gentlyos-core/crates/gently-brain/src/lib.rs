@@ -12,7 +12,9 @@
 //! The brain grows smarter through routine processes.
 
 pub mod agent;
+pub mod bench;
 pub mod embedder;
+pub mod embedding;
 pub mod evolve;
 pub mod gitchain;
 pub mod llama;
@@ -23,15 +25,22 @@ pub mod download;
 pub mod claude;
 pub mod skills;
 pub mod daemon;
+pub mod config_watcher;
+pub mod dedup;
+pub mod discrimination_net;
 pub mod knowledge;
 pub mod learner;
 pub mod mcp;
+pub mod metrics;
 pub mod orchestrator;
 pub mod pipeline;
+pub mod standing_query;
 pub mod watchdog;
 
 pub use agent::{Agent, AgentRuntime, AgentMeta, Observation};
+pub use bench::{Workload, WorkloadStep, BenchConfig, BenchReport, BenchSummary, StepReport, run as run_bench};
 pub use embedder::Embedder;
+pub use embedding::{EmbeddingProvider, OpenAiEmbedder, OllamaEmbedder, HashEmbedder, build_embedding_provider};
 pub use evolve::{Evolver, EvolveLoop, EvolveConfig, EvolveState, Pattern, CycleResult};
 pub use gitchain::{GitChain, CommitMeta, Branch};
 pub use llama::LlamaInference;
@@ -42,11 +51,16 @@ pub use download::ModelDownloader;
 pub use claude::{ClaudeClient, ClaudeModel, ClaudeSession, GentlyAssistant, Message, AssistantResponse, ToolUseResponse, ToolResultInput};
 pub use skills::{Skill, SkillRegistry, SkillResult, SkillCategory, SkillHandler, SkillContext};
 pub use daemon::{DaemonManager, DaemonType, DaemonEvent, AwarenessState};
-pub use knowledge::{KnowledgeGraph, KnowledgeNode, NodeType, EdgeType};
+pub use config_watcher::{ConfigOverrides, SeedConcept, BrainDefinitions, ReloadTargets, ConfigWatcherHandle, load_definitions, watch as watch_config};
+pub use dedup::{DedupMap, is_dedupable};
+pub use discrimination_net::{DiscriminationNet, Match as TriggerMatch};
+pub use knowledge::{KnowledgeGraph, KnowledgeNode, NodeType, EdgeType, VectorChunk};
 pub use learner::{ConversationLearner, LearnedConcept, LearningResult};
 pub use mcp::{McpToolRegistry, Tool, ToolCategory, ToolResult, ToolExecutor};
-pub use orchestrator::{BrainOrchestrator, BrainConfig, ProcessingResult};
+pub use metrics::{MetricsRegistry, serve as serve_metrics};
+pub use orchestrator::{BrainOrchestrator, BrainConfig, ProcessingResult, EmbeddingBackend, StageTimings, BatchOp, BatchResult};
 pub use pipeline::{BlobPipeline, PipelineConfig, SyncJob, SyncResult};
+pub use standing_query::{StandingQueryIndex, SubscriptionId, Match as StandingQueryMatch};
 pub use watchdog::{Watchdog, Event, Rule, Action, EventKind};
 
 use thiserror::Error;
@@ -65,6 +79,9 @@ pub enum Error {
     #[error("Download failed: {0}")]
     DownloadFailed(String),
 
+    #[error("Config reload failed: {0}")]
+    ConfigReloadFailed(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -0,0 +1,239 @@
+//! OpenMetrics/Prometheus exporter for daemon and awareness metrics
+//!
+//! `tool_daemon_metrics` and `tool_awareness_state` only ever return a
+//! one-shot JSON snapshot, so nothing outside a single tool call can see
+//! `items_processed`/`cycles`/`errors`/`knowledge_nodes` trend over time.
+//! This registers the same counters as a `MetricsRegistry` and serves them
+//! over HTTP in Prometheus text exposition format, the same shape as
+//! garage's `admin/metrics.rs`. `refresh_loop` polls
+//! `BrainOrchestrator::daemon_statuses`/`get_awareness_snapshot` on an
+//! interval and swaps the registry's values in place, so a scrape just
+//! reads whatever was last recorded rather than triggering new work.
+
+use crate::daemon::DaemonType;
+use crate::orchestrator::BrainOrchestrator;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Point-in-time metrics for one daemon, labeled by `daemon`/`type` on
+/// render.
+#[derive(Debug, Clone, Default)]
+struct DaemonSample {
+    daemon_type: &'static str,
+    running: bool,
+    cycles: u64,
+    errors: u32,
+    items_processed: u64,
+    bytes_synced: u64,
+    vectors_computed: u64,
+}
+
+/// Last-known values for every metric this exporter serves. Updated by
+/// `refresh_loop`, read by `render` on every scrape. Gauges with no natural
+/// lock-free integer representation (the growth rate, a float) are stored
+/// pre-scaled by 1000 since `std` has no atomic `f32`.
+#[derive(Default)]
+pub struct MetricsRegistry {
+    daemons: Mutex<HashMap<String, DaemonSample>>,
+    knowledge_nodes: AtomicU64,
+    active_daemons: AtomicU64,
+    active_thoughts: AtomicU64,
+    attention_len: AtomicU64,
+    growth_rate_milli: AtomicU64,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Pull a fresh snapshot from `orchestrator` and swap it in. Safe to
+    /// call concurrently with `render`: each gauge is a single atomic and the
+    /// daemon map is replaced wholesale under its lock, so no individual
+    /// value is ever torn. The fields are stored one after another, though,
+    /// so a concurrent render can mix values from two consecutive refreshes.
+    fn refresh(&self, orchestrator: &BrainOrchestrator) {
+        let mut daemons = HashMap::new();
+        for (name, daemon_type, status) in orchestrator.daemon_statuses() {
+            daemons.insert(name, DaemonSample {
+                daemon_type: daemon_type_label(daemon_type),
+                running: status.running,
+                cycles: status.cycles,
+                errors: status.errors,
+                items_processed: status.metrics.items_processed,
+                bytes_synced: status.metrics.bytes_synced,
+                vectors_computed: status.metrics.vectors_computed,
+            });
+        }
+        *self.daemons.lock().unwrap() = daemons;
+
+        let snapshot = orchestrator.get_awareness_snapshot();
+        self.knowledge_nodes.store(snapshot.knowledge_nodes as u64, Ordering::SeqCst);
+        self.active_daemons.store(snapshot.active_daemons as u64, Ordering::SeqCst);
+        self.active_thoughts.store(snapshot.active_thoughts as u64, Ordering::SeqCst);
+        self.attention_len.store(snapshot.attention.len() as u64, Ordering::SeqCst);
+        self.growth_rate_milli.store((orchestrator.growth_rate() * 1000.0).round() as u64, Ordering::SeqCst);
+    }
+
+    /// Render the current snapshot as Prometheus text exposition format.
+    fn render(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(out, "# HELP gently_brain_knowledge_nodes Total nodes in the knowledge graph.");
+        let _ = writeln!(out, "# TYPE gently_brain_knowledge_nodes gauge");
+        let _ = writeln!(out, "gently_brain_knowledge_nodes {}", self.knowledge_nodes.load(Ordering::SeqCst));
+
+        let _ = writeln!(out, "# HELP gently_brain_active_daemons Daemons currently running.");
+        let _ = writeln!(out, "# TYPE gently_brain_active_daemons gauge");
+        let _ = writeln!(out, "gently_brain_active_daemons {}", self.active_daemons.load(Ordering::SeqCst));
+
+        let _ = writeln!(out, "# HELP gently_brain_active_thoughts Thoughts queued for the awareness loop.");
+        let _ = writeln!(out, "# TYPE gently_brain_active_thoughts gauge");
+        let _ = writeln!(out, "gently_brain_active_thoughts {}", self.active_thoughts.load(Ordering::SeqCst));
+
+        let _ = writeln!(out, "# HELP gently_brain_attention_size Concepts currently held in attention (a proxy for attention drift over time).");
+        let _ = writeln!(out, "# TYPE gently_brain_attention_size gauge");
+        let _ = writeln!(out, "gently_brain_attention_size {}", self.attention_len.load(Ordering::SeqCst));
+
+        let _ = writeln!(out, "# HELP gently_brain_growth_rate Current BrainConfig::growth_rate.");
+        let _ = writeln!(out, "# TYPE gently_brain_growth_rate gauge");
+        let _ = writeln!(out, "gently_brain_growth_rate {}", self.growth_rate_milli.load(Ordering::SeqCst) as f64 / 1000.0);
+
+        let daemons = self.daemons.lock().unwrap();
+
+        let _ = writeln!(out, "# HELP gently_brain_daemon_running Whether a daemon is currently running (1) or not (0).");
+        let _ = writeln!(out, "# TYPE gently_brain_daemon_running gauge");
+        for (name, sample) in daemons.iter() {
+            let _ = writeln!(out, "gently_brain_daemon_running{{daemon=\"{}\",type=\"{}\"}} {}", name, sample.daemon_type, sample.running as u8);
+        }
+
+        let _ = writeln!(out, "# HELP gently_brain_daemon_cycles_total Completed cycles for a daemon.");
+        let _ = writeln!(out, "# TYPE gently_brain_daemon_cycles_total counter");
+        for (name, sample) in daemons.iter() {
+            let _ = writeln!(out, "gently_brain_daemon_cycles_total{{daemon=\"{}\",type=\"{}\"}} {}", name, sample.daemon_type, sample.cycles);
+        }
+
+        let _ = writeln!(out, "# HELP gently_brain_daemon_errors_total Errors recorded for a daemon.");
+        let _ = writeln!(out, "# TYPE gently_brain_daemon_errors_total counter");
+        for (name, sample) in daemons.iter() {
+            let _ = writeln!(out, "gently_brain_daemon_errors_total{{daemon=\"{}\",type=\"{}\"}} {}", name, sample.daemon_type, sample.errors);
+        }
+
+        let _ = writeln!(out, "# HELP gently_brain_daemon_items_processed_total Items processed by a daemon.");
+        let _ = writeln!(out, "# TYPE gently_brain_daemon_items_processed_total counter");
+        for (name, sample) in daemons.iter() {
+            let _ = writeln!(out, "gently_brain_daemon_items_processed_total{{daemon=\"{}\",type=\"{}\"}} {}", name, sample.daemon_type, sample.items_processed);
+        }
+
+        let _ = writeln!(out, "# HELP gently_brain_daemon_bytes_synced_total Bytes synced by a daemon.");
+        let _ = writeln!(out, "# TYPE gently_brain_daemon_bytes_synced_total counter");
+        for (name, sample) in daemons.iter() {
+            let _ = writeln!(out, "gently_brain_daemon_bytes_synced_total{{daemon=\"{}\",type=\"{}\"}} {}", name, sample.daemon_type, sample.bytes_synced);
+        }
+
+        let _ = writeln!(out, "# HELP gently_brain_daemon_vectors_computed_total Vectors computed by a daemon.");
+        let _ = writeln!(out, "# TYPE gently_brain_daemon_vectors_computed_total counter");
+        for (name, sample) in daemons.iter() {
+            let _ = writeln!(out, "gently_brain_daemon_vectors_computed_total{{daemon=\"{}\",type=\"{}\"}} {}", name, sample.daemon_type, sample.vectors_computed);
+        }
+
+        out
+    }
+}
+
+fn daemon_type_label(daemon_type: DaemonType) -> &'static str {
+    match daemon_type {
+        DaemonType::VectorChain => "VectorChain",
+        DaemonType::IpfsSync => "IpfsSync",
+        DaemonType::GitBranch => "GitBranch",
+        DaemonType::KnowledgeGraph => "KnowledgeGraph",
+        DaemonType::Awareness => "Awareness",
+        DaemonType::Inference => "Inference",
+        other => {
+            // Security daemons etc. - `{:?}` gives the same identifier a
+            // `DaemonManager::list()` name is already derived from.
+            Box::leak(format!("{:?}", other).into_boxed_str())
+        }
+    }
+}
+
+/// Poll `orchestrator` for fresh metrics every `interval` until `registry`
+/// has no other owners.
+async fn refresh_loop(orchestrator: Arc<BrainOrchestrator>, registry: Arc<MetricsRegistry>, interval: Duration) {
+    loop {
+        if Arc::strong_count(&registry) == 1 {
+            return;
+        }
+        registry.refresh(&orchestrator);
+        tokio::time::sleep(interval).await;
+    }
+}
+
+async fn metrics_handler(State(registry): State<Arc<MetricsRegistry>>) -> impl IntoResponse {
+    (
+        [("Content-Type", "text/plain; version=0.0.4; charset=utf-8")],
+        registry.render(),
+    )
+}
+
+/// Start serving `/metrics` for `orchestrator` at `addr`, refreshing the
+/// registry every `refresh_interval`. Runs until the process exits; the
+/// refresh task stops itself once the server's router, which holds the only
+/// other reference to the registry, is dropped.
+pub async fn serve(
+    orchestrator: Arc<BrainOrchestrator>,
+    addr: &str,
+    refresh_interval: Duration,
+) -> std::io::Result<()> {
+    let registry = MetricsRegistry::new();
+    tokio::spawn(refresh_loop(orchestrator, registry.clone(), refresh_interval));
+
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .with_state(registry);
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("Brain metrics exporter listening on http://{}/metrics", addr);
+    axum::serve(listener, app).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_includes_gauges_with_no_daemons() {
+        let registry = MetricsRegistry::default();
+        registry.knowledge_nodes.store(7, Ordering::SeqCst);
+        registry.growth_rate_milli.store(150, Ordering::SeqCst);
+
+        let text = registry.render();
+        assert!(text.contains("gently_brain_knowledge_nodes 7"));
+        assert!(text.contains("gently_brain_growth_rate 0.15"));
+    }
+
+    #[test]
+    fn test_render_includes_per_daemon_labels() {
+        let registry = MetricsRegistry::default();
+        registry.daemons.lock().unwrap().insert("Awareness_0".to_string(), DaemonSample {
+            daemon_type: "Awareness",
+            running: true,
+            cycles: 42,
+            errors: 1,
+            items_processed: 10,
+            bytes_synced: 0,
+            vectors_computed: 0,
+        });
+
+        let text = registry.render();
+        assert!(text.contains("gently_brain_daemon_running{daemon=\"Awareness_0\",type=\"Awareness\"} 1"));
+        assert!(text.contains("gently_brain_daemon_cycles_total{daemon=\"Awareness_0\",type=\"Awareness\"} 42"));
+    }
+}
@@ -11,21 +11,39 @@
 
 use crate::{
     Result, Error,
-    daemon::{DaemonManager, DaemonType, DaemonEvent, AwarenessDaemon, VectorChainDaemon, IpfsSyncDaemon, GitBranchDaemon, VectorJob, SyncJob},
+    daemon::{DaemonManager, DaemonType, DaemonEvent, DaemonStatus, DaemonState, DaemonMetrics, AwarenessDaemon, VectorChainDaemon, IpfsSyncDaemon, GitBranchDaemon, VectorJob, SyncJob},
     knowledge::{KnowledgeGraph, NodeType, EdgeType},
+    embedding::{EmbeddingProvider, build_embedding_provider},
+    discrimination_net::DiscriminationNet,
     skills::{SkillRegistry, SkillContext, Learning},
     mcp::{McpToolRegistry, ToolResult, SideEffect},
     claude::{ClaudeClient, ClaudeModel, GentlyAssistant},
+    standing_query::{StandingQueryIndex, SubscriptionId},
+    dedup::{DedupMap, is_dedupable},
 };
 use gently_alexandria::{
     AlexandriaGraph, AlexandriaConfig, ConceptId,
     SemanticTesseract, HyperPosition, TemporalPosition,
+    SledStore, SledAlexandriaRepo, SledTesseractRepo,
     node::NodeFingerprint,
 };
-use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
-use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering}};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Words and phrases that flag a thought as containing learnable content,
+/// indexed into `BrainOrchestrator::learnable_pattern_net` at construction
+/// time rather than scanned linearly on every thought. They match whole
+/// whitespace-separated tokens (case-insensitively), not substrings.
+const LEARNABLE_PATTERNS: &[&str] = &[
+    "is", "are", "means", "defined as", "equals",
+    "learned", "discovered", "found", "realized",
+    "fact:", "note:", "remember:",
+];
 
 /// Brain configuration
 #[derive(Debug, Clone)]
@@ -38,6 +56,14 @@ pub struct BrainConfig {
     pub ipfs_sync_interval_ms: u64,
     pub growth_rate: f32,
     pub max_context_size: usize,
+    pub embedding_backend: EmbeddingBackend,
+    /// Max characters per embedded chunk (a proxy for "sub-token-limit"
+    /// windows without pulling in a real tokenizer)
+    pub embedding_chunk_chars: usize,
+    /// Optional path to a sled database used to persist the Alexandria graph
+    /// and Tesseract positions across restarts (see `gently_alexandria::repo`).
+    /// `None` keeps the historical in-memory-only behavior.
+    pub alexandria_db_path: Option<PathBuf>,
 }
 
 impl Default for BrainConfig {
@@ -51,10 +77,29 @@ impl Default for BrainConfig {
             ipfs_sync_interval_ms: 5000,
             growth_rate: 0.1,
             max_context_size: 100,
+            embedding_backend: EmbeddingBackend::default(),
+            embedding_chunk_chars: 2000,
+            alexandria_db_path: None,
         }
     }
 }
 
+/// Which backend `EmbeddingProvider` to construct (see `crate::embedding`).
+#[derive(Debug, Clone)]
+pub enum EmbeddingBackend {
+    OpenAi { api_key: String, model: String, dimensions: usize },
+    Ollama { endpoint: String, model: String, dimensions: usize },
+    /// No-network deterministic fallback, used when no API key or local
+    /// model server is configured.
+    Hash { dimensions: usize },
+}
+
+impl Default for EmbeddingBackend {
+    fn default() -> Self {
+        EmbeddingBackend::Hash { dimensions: 384 }
+    }
+}
+
 /// Result of processing a thought/input
 #[derive(Debug, Clone)]
 pub struct ProcessingResult {
@@ -65,6 +110,18 @@ pub struct ProcessingResult {
     pub awareness_update: Option<AwarenessSnapshot>,
 }
 
+/// Per-stage latency recorded while processing one thought, surfaced to the
+/// `bench` harness so maintainers can diff hot-path regressions across
+/// commits. Cheap enough (a handful of `Instant::now()` pairs) to record on every
+/// call rather than gating it behind a flag.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StageTimings {
+    pub skill_match: Duration,
+    pub alexandria: Duration,
+    pub knowledge: Duration,
+    pub response_generation: Duration,
+}
+
 /// Snapshot of awareness state
 #[derive(Debug, Clone)]
 pub struct AwarenessSnapshot {
@@ -76,6 +133,26 @@ pub struct AwarenessSnapshot {
     pub growth_direction: String,
 }
 
+/// One operation within a `tool_batch`/`execute_batch` request: the tool
+/// name plus its input, in call order.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchOp {
+    pub tool: String,
+    pub input: serde_json::Value,
+}
+
+/// Aggregate outcome of a batch of tool calls: the per-op results in
+/// request order (shorter than the request if `stop_on_error` halted it
+/// early), plus the rollup `success`/`side_effects`/`learnings` so a caller
+/// doesn't have to re-walk `results` itself.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchResult {
+    pub results: Vec<ToolResult>,
+    pub success: bool,
+    pub side_effects: Vec<SideEffect>,
+    pub learnings: Vec<String>,
+}
+
 /// The Brain Orchestrator - coordinates all brain components
 pub struct BrainOrchestrator {
     config: BrainConfig,
@@ -83,13 +160,38 @@ pub struct BrainOrchestrator {
     // Core components
     daemon_manager: Arc<Mutex<DaemonManager>>,
     knowledge_graph: Arc<KnowledgeGraph>,
-    skill_registry: Arc<SkillRegistry>,
+    embedding_provider: Arc<dyn EmbeddingProvider>,
+    vector_daemon: Arc<VectorChainDaemon>,
+    skill_registry: Arc<Mutex<SkillRegistry>>,
     tool_registry: Arc<McpToolRegistry>,
 
+    // Hot-reloadable scalar config (see `config_watcher`) — swapped in place
+    // behind atomics/a mutex so a running brain can be retuned without a
+    // restart.
+    growth_rate: Arc<Mutex<f32>>,
+    max_context_size: Arc<AtomicUsize>,
+    awareness_interval_ms: Arc<AtomicU64>,
+    daemons_enabled: Arc<AtomicBool>,
+
+    // Sublinear pattern matching for `is_learnable` (see `discrimination_net`)
+    learnable_pattern_net: DiscriminationNet,
+
     // Alexandria - distributed knowledge graph
     alexandria: Arc<Mutex<AlexandriaGraph>>,
     tesseract: Arc<Mutex<SemanticTesseract>>,
 
+    // Durable storage for the above, opened from `BrainConfig::alexandria_db_path`.
+    // `None` means in-memory-only (the historical behavior).
+    alexandria_repo: Option<Arc<SledAlexandriaRepo>>,
+    tesseract_repo: Option<Arc<SledTesseractRepo>>,
+
+    // Standing queries registered over Alexandria position/edge records (see
+    // `standing_query` and `tool_alexandria_subscribe`).
+    standing_queries: Arc<Mutex<StandingQueryIndex>>,
+
+    // In-flight dedup for expensive read-only tools (see `dedup`).
+    dedup: DedupMap,
+
     // State
     running: Arc<AtomicBool>,
     context: Arc<Mutex<VecDeque<String>>>,
@@ -130,6 +232,16 @@ pub enum BrainEvent {
     AlexandriaEdge { from: String, to: String, kind: String },
     AlexandriaTesseract { concept: String, face: String },
     AlexandriaDrift { concept: String, positions: usize },
+
+    /// A standing query registered via `tool_alexandria_subscribe` matched a
+    /// newly recorded position face or edge.
+    StandingQueryMatch { subscription_id: SubscriptionId, captures: Vec<(String, String)> },
+
+    // Config hot-reload
+    /// A watched definitions directory changed and was applied; `changed`
+    /// names the config fields/skills/seed concepts affected (see
+    /// `config_watcher::ReloadTargets::apply`).
+    ConfigReloaded { changed: Vec<String> },
 }
 
 impl BrainOrchestrator {
@@ -144,14 +256,86 @@ impl BrainOrchestrator {
             &format!("brain-{}", uuid::Uuid::new_v4()),
         );
 
+        let daemon_manager = DaemonManager::new();
+        let daemon_event_tx = daemon_manager.event_sender();
+
+        let knowledge_graph = Arc::new(KnowledgeGraph::new());
+        let embedding_provider = build_embedding_provider(&config);
+        let vector_daemon = Arc::new(VectorChainDaemon::new(
+            Arc::new(AtomicBool::new(false)),
+            Arc::new(Mutex::new(DaemonStatus {
+                running: true,
+                started_at: Some(Instant::now()),
+                cycles: 0,
+                last_cycle: None,
+                errors: 0,
+                state: DaemonState::Running,
+                metrics: DaemonMetrics::default(),
+            })),
+            daemon_event_tx,
+            knowledge_graph.clone(),
+            embedding_provider.clone(),
+            config.vector_batch_size,
+            config.embedding_chunk_chars,
+        ));
+
+        let growth_rate = Arc::new(Mutex::new(config.growth_rate));
+        let max_context_size = Arc::new(AtomicUsize::new(config.max_context_size));
+        let awareness_interval_ms = Arc::new(AtomicU64::new(config.awareness_interval_ms));
+        let daemons_enabled = Arc::new(AtomicBool::new(config.enable_daemons));
+
+        let mut learnable_pattern_net = DiscriminationNet::new();
+        for pattern in LEARNABLE_PATTERNS {
+            learnable_pattern_net.add("learnable", pattern);
+        }
+
+        let (alexandria_repo, tesseract_repo) = match &config.alexandria_db_path {
+            Some(path) => match SledStore::open(path) {
+                Ok(store) => (
+                    Some(Arc::new(store.alexandria_repo())),
+                    Some(Arc::new(store.tesseract_repo())),
+                ),
+                Err(e) => {
+                    tracing::warn!("Failed to open Alexandria sled store at {}: {}", path.display(), e);
+                    (None, None)
+                }
+            },
+            None => (None, None),
+        };
+
+        let alexandria = AlexandriaGraph::with_defaults(node_fingerprint);
+        if let Some(repo) = &alexandria_repo {
+            if let Err(e) = alexandria.hydrate_from_repo(repo.as_ref()) {
+                tracing::warn!("Failed to hydrate Alexandria graph from disk: {}", e);
+            }
+        }
+
+        let mut tesseract = SemanticTesseract::new();
+        if let Some(repo) = &tesseract_repo {
+            if let Err(e) = tesseract.hydrate_from_repo(repo.as_ref()) {
+                tracing::warn!("Failed to hydrate Tesseract from disk: {}", e);
+            }
+        }
+
         Self {
             config,
-            daemon_manager: Arc::new(Mutex::new(DaemonManager::new())),
-            knowledge_graph: Arc::new(KnowledgeGraph::new()),
-            skill_registry: Arc::new(SkillRegistry::new()),
+            daemon_manager: Arc::new(Mutex::new(daemon_manager)),
+            knowledge_graph,
+            embedding_provider,
+            vector_daemon,
+            skill_registry: Arc::new(Mutex::new(SkillRegistry::new())),
             tool_registry: Arc::new(McpToolRegistry::new()),
-            alexandria: Arc::new(Mutex::new(AlexandriaGraph::with_defaults(node_fingerprint))),
-            tesseract: Arc::new(Mutex::new(SemanticTesseract::new())),
+            growth_rate,
+            max_context_size,
+            awareness_interval_ms,
+            daemons_enabled,
+            learnable_pattern_net,
+            alexandria: Arc::new(Mutex::new(alexandria)),
+            tesseract: Arc::new(Mutex::new(tesseract)),
+            alexandria_repo,
+            tesseract_repo,
+            standing_queries: Arc::new(Mutex::new(StandingQueryIndex::new())),
+            dedup: DedupMap::new(),
             running: Arc::new(AtomicBool::new(false)),
             context: Arc::new(Mutex::new(VecDeque::new())),
             attention: Arc::new(Mutex::new(Vec::new())),
@@ -162,6 +346,18 @@ impl BrainOrchestrator {
         }
     }
 
+    /// Queue freshly learned concept ids for chunking/embedding so they
+    /// become searchable via `knowledge_similar`.
+    fn enqueue_vector_jobs(&self, ids: &[String], content: &str) {
+        for id in ids {
+            self.vector_daemon.enqueue(VectorJob {
+                id: id.clone(),
+                content: content.to_string(),
+                priority: 5,
+            });
+        }
+    }
+
     /// Start the brain - initializes all daemons and begins awareness loop
     pub async fn start(&self) -> Result<()> {
         self.running.store(true, Ordering::SeqCst);
@@ -172,7 +368,7 @@ impl BrainOrchestrator {
             dm.start();
         }
 
-        if self.config.enable_daemons {
+        if self.daemons_enabled.load(Ordering::SeqCst) {
             // Spawn core daemons
             self.spawn_daemon(DaemonType::VectorChain)?;
             self.spawn_daemon(DaemonType::KnowledgeGraph)?;
@@ -204,13 +400,41 @@ impl BrainOrchestrator {
         dm.spawn(daemon_type)
     }
 
+    /// Watch `dir` for declarative definition files (scalar config
+    /// overrides, skill declarations, seed concepts), applying an initial
+    /// load immediately and reloading on every subsequent change. Keep the
+    /// returned handle alive for as long as the watch should run.
+    pub fn start_config_watcher(&self, dir: impl Into<std::path::PathBuf>) -> Result<crate::config_watcher::ConfigWatcherHandle> {
+        let targets = crate::config_watcher::ReloadTargets {
+            daemon_manager: self.daemon_manager.clone(),
+            vector_daemon: self.vector_daemon.clone(),
+            skill_registry: self.skill_registry.clone(),
+            knowledge_graph: self.knowledge_graph.clone(),
+            alexandria: self.alexandria.clone(),
+            growth_rate: self.growth_rate.clone(),
+            max_context_size: self.max_context_size.clone(),
+            awareness_interval_ms: self.awareness_interval_ms.clone(),
+            daemons_enabled: self.daemons_enabled.clone(),
+            enable_ipfs: self.config.enable_ipfs,
+            event_tx: self.event_tx.clone(),
+        };
+        crate::config_watcher::watch(dir, targets)
+    }
+
     /// Process a thought - the main entry point for awareness
     pub async fn process_thought(&self, thought: &str) -> ProcessingResult {
+        self.process_thought_timed(thought).await.0
+    }
+
+    /// Like `process_thought`, but also returns per-stage latency (see
+    /// `StageTimings`) so the `bench` harness can build a diffable report
+    /// without re-implementing the processing pipeline.
+    pub async fn process_thought_timed(&self, thought: &str) -> (ProcessingResult, StageTimings) {
         // Add to context
         {
             let mut ctx = self.context.lock().unwrap();
             ctx.push_back(thought.to_string());
-            if ctx.len() > self.config.max_context_size {
+            if ctx.len() > self.max_context_size.load(Ordering::SeqCst) {
                 ctx.pop_front();
             }
         }
@@ -229,32 +453,45 @@ impl BrainOrchestrator {
     }
 
     /// Process a single thought and generate response
-    async fn process_single_thought(&self, thought: &str) -> ProcessingResult {
+    async fn process_single_thought(&self, thought: &str) -> (ProcessingResult, StageTimings) {
         let mut tool_uses = Vec::new();
         let mut learnings = Vec::new();
         let mut side_effects = Vec::new();
+        let mut timings = StageTimings::default();
 
         // Check for skill triggers
-        let matching_skills = self.skill_registry.find_by_trigger(thought);
-        for skill in matching_skills {
-            tool_uses.push(format!("skill:{}", skill.name));
+        {
+            let t0 = Instant::now();
+            let registry = self.skill_registry.lock().unwrap();
+            for skill in registry.find_by_trigger(thought) {
+                tool_uses.push(format!("skill:{}", skill.name));
+            }
+            drop(registry);
+            timings.skill_match += t0.elapsed();
         }
 
         // Record query in Alexandria (builds usage graph)
         {
+            let t0 = Instant::now();
             let alexandria = self.alexandria.lock().unwrap();
             alexandria.record_query(thought);
+            drop(alexandria);
+            timings.alexandria += t0.elapsed();
         }
 
         // Extract learnable content
         if self.is_learnable(thought) {
+            let t0 = Instant::now();
             let concept = self.extract_concept(thought);
-            self.knowledge_graph.learn(&concept, Some(thought), Some(0.7));
+            let added_ids = self.knowledge_graph.learn(&concept, Some(thought), Some(0.7));
+            self.enqueue_vector_jobs(&added_ids, thought);
             learnings.push(concept.clone());
             side_effects.push(SideEffect::KnowledgeAdded { concept: concept.clone() });
+            timings.knowledge += t0.elapsed();
 
             // Also record in Alexandria with concept edges
             {
+                let t0 = Instant::now();
                 let alexandria = self.alexandria.lock().unwrap();
                 let concept_id = alexandria.ensure_concept(&concept);
 
@@ -265,6 +502,9 @@ impl BrainOrchestrator {
                     concept_id,
                     gently_alexandria::EdgeKind::SessionCorrelation,
                 );
+                drop(alexandria);
+                timings.alexandria += t0.elapsed();
+                self.check_standing_queries_for_edge(thought_id, concept_id, &gently_alexandria::EdgeKind::SessionCorrelation);
             }
 
             let _ = self.event_tx.send(BrainEvent::Learning {
@@ -277,10 +517,14 @@ impl BrainOrchestrator {
         self.update_attention(thought);
 
         // Check for connections to existing knowledge
+        let t0 = Instant::now();
         let related = self.knowledge_graph.search(thought);
+        timings.knowledge += t0.elapsed();
+
         for node in related.iter().take(3) {
             // Build edges in Alexandria for discovered connections
             {
+                let t0 = Instant::now();
                 let alexandria = self.alexandria.lock().unwrap();
                 let from_id = ConceptId::from_concept(thought);
                 let to_id = ConceptId::from_concept(&node.concept);
@@ -289,6 +533,9 @@ impl BrainOrchestrator {
                     to_id,
                     gently_alexandria::EdgeKind::UserPath,
                 );
+                drop(alexandria);
+                timings.alexandria += t0.elapsed();
+                self.check_standing_queries_for_edge(from_id, to_id, &gently_alexandria::EdgeKind::UserPath);
             }
 
             let _ = self.event_tx.send(BrainEvent::Connection {
@@ -299,31 +546,39 @@ impl BrainOrchestrator {
         }
 
         // Query Alexandria for additional connections
+        let t0 = Instant::now();
         let alexandria_topology = {
             let alexandria = self.alexandria.lock().unwrap();
             alexandria.query_topology(thought)
         };
+        timings.alexandria += t0.elapsed();
 
         // Add Alexandria-discovered concepts to learnings
         if let Some(topology) = alexandria_topology {
+            let t0 = Instant::now();
             for edge in topology.outgoing.iter().take(2) {
                 let alexandria = self.alexandria.lock().unwrap();
                 if let Some(concept) = alexandria.get_concept(&edge.to) {
                     learnings.push(format!("discovered:{}", concept.text));
                 }
             }
+            timings.alexandria += t0.elapsed();
         }
 
         // Generate response based on context and knowledge
+        let t0 = Instant::now();
         let response = self.generate_response(thought, &related).await;
+        timings.response_generation += t0.elapsed();
 
-        ProcessingResult {
+        let result = ProcessingResult {
             response,
             tool_uses,
             learnings,
             side_effects,
             awareness_update: Some(self.get_awareness_snapshot()),
-        }
+        };
+
+        (result, timings)
     }
 
     /// Execute a tool call
@@ -334,7 +589,28 @@ impl BrainOrchestrator {
             input: input.clone(),
         });
 
-        // Route to appropriate handler
+        if is_dedupable(name) {
+            return Ok(self.dedup.run(name, input, || async move {
+                match self.dispatch_tool(name, input).await {
+                    Ok(result) => result,
+                    Err(e) => ToolResult {
+                        tool: name.to_string(),
+                        success: false,
+                        output: serde_json::json!({ "error": e.to_string() }),
+                        side_effects: vec![],
+                        learnings: vec![],
+                    },
+                }
+            }).await);
+        }
+
+        self.dispatch_tool(name, input).await
+    }
+
+    /// Route `name` to its handler. Split out of `execute_tool` so dedupable
+    /// tools (see `dedup`) can run through `DedupMap::run` without
+    /// duplicating the routing table.
+    async fn dispatch_tool(&self, name: &str, input: &serde_json::Value) -> Result<ToolResult> {
         match name {
             // Knowledge tools
             "knowledge_learn" => self.tool_knowledge_learn(input).await,
@@ -360,12 +636,59 @@ impl BrainOrchestrator {
             "alexandria_drift" => self.tool_alexandria_drift(input).await,
             "alexandria_wormhole" => self.tool_alexandria_wormhole(input).await,
             "alexandria_record" => self.tool_alexandria_record(input).await,
+            "alexandria_subscribe" => self.tool_alexandria_subscribe(input).await,
+            "alexandria_unsubscribe" => self.tool_alexandria_unsubscribe(input).await,
+
+            // Batch tools
+            "batch" => self.tool_batch(input).await,
 
             // Default: try registry
             _ => self.tool_registry.execute(name, input),
         }
     }
 
+    /// Run an ordered list of tool calls as one round trip, modeled on
+    /// garage's K2V batch API bundling many reads/writes into a single
+    /// request. With `stop_on_error` set, halts and returns as soon as one
+    /// op fails, so `results` is shorter than `ops`; otherwise every op
+    /// runs best-effort and every failure is collected in place. Side
+    /// effects and learnings from every op (successful or not) are
+    /// aggregated so a caller can, e.g., record ten `alexandria_record`
+    /// positions and then navigate them without ten separate round trips.
+    pub async fn execute_batch(&self, ops: &[BatchOp], stop_on_error: bool) -> BatchResult {
+        let mut results = Vec::with_capacity(ops.len());
+        let mut success = true;
+        let mut side_effects = Vec::new();
+        let mut learnings = Vec::new();
+
+        for op in ops {
+            let result = match self.execute_tool(&op.tool, &op.input).await {
+                Ok(result) => result,
+                Err(e) => ToolResult {
+                    tool: op.tool.clone(),
+                    success: false,
+                    output: serde_json::json!({ "error": e.to_string() }),
+                    side_effects: vec![],
+                    learnings: vec![],
+                },
+            };
+
+            if !result.success {
+                success = false;
+            }
+            side_effects.extend(result.side_effects.clone());
+            learnings.extend(result.learnings.clone());
+
+            let halt = !result.success && stop_on_error;
+            results.push(result);
+            if halt {
+                break;
+            }
+        }
+
+        BatchResult { results, success, side_effects, learnings }
+    }
+
     /// Focus attention on a topic
     pub fn focus(&self, topic: &str) {
         let mut attention = self.attention.lock().unwrap();
@@ -418,7 +741,7 @@ impl BrainOrchestrator {
         let knowledge_nodes = self.knowledge_graph.search("*").len();
         let active_daemons = {
             let dm = self.daemon_manager.lock().unwrap();
-            dm.list().iter().filter(|(_, _, running)| *running).count()
+            dm.list().iter().filter(|(_, _, running, _)| *running).count()
         };
         let growth_direction = self.growth_direction.lock().unwrap().clone();
 
@@ -448,8 +771,27 @@ impl BrainOrchestrator {
     }
 
     /// Get skill registry
-    pub fn skill_registry(&self) -> &SkillRegistry {
-        &self.skill_registry
+    pub fn skill_registry(&self) -> Arc<Mutex<SkillRegistry>> {
+        self.skill_registry.clone()
+    }
+
+    /// Full status (including per-daemon `DaemonMetrics`) of every
+    /// registered daemon, for exporters like `metrics` that need more than
+    /// the name/type/running summary `DaemonManager::list` gives.
+    pub fn daemon_statuses(&self) -> Vec<(String, DaemonType, DaemonStatus)> {
+        let dm = self.daemon_manager.lock().unwrap();
+        dm.list()
+            .into_iter()
+            .filter_map(|(name, daemon_type, _running, _state)| {
+                dm.status(&name).map(|status| (name, daemon_type, status))
+            })
+            .collect()
+    }
+
+    /// Current growth rate (see `BrainConfig::growth_rate`, hot-reloadable
+    /// via `config_watcher`).
+    pub fn growth_rate(&self) -> f32 {
+        *self.growth_rate.lock().unwrap()
     }
 
     /// Get Alexandria graph
@@ -465,14 +807,7 @@ impl BrainOrchestrator {
     // === Internal helpers ===
 
     fn is_learnable(&self, thought: &str) -> bool {
-        // Check if thought contains learnable patterns
-        let learnable_patterns = [
-            "is", "are", "means", "defined as", "equals",
-            "learned", "discovered", "found", "realized",
-            "fact:", "note:", "remember:",
-        ];
-        let thought_lower = thought.to_lowercase();
-        learnable_patterns.iter().any(|p| thought_lower.contains(p))
+        self.learnable_pattern_net.has_match(thought)
     }
 
     fn extract_concept(&self, thought: &str) -> String {
@@ -516,7 +851,8 @@ impl BrainOrchestrator {
             .and_then(|v| v.as_str())
             .unwrap_or("");
 
-        self.knowledge_graph.learn(concept, Some(context), Some(0.8));
+        let added_ids = self.knowledge_graph.learn(concept, Some(context), Some(0.8));
+        self.enqueue_vector_jobs(&added_ids, context);
 
         // Handle connections if provided
         if let Some(connections) = input.get("connections").and_then(|v| v.as_array()) {
@@ -604,7 +940,17 @@ impl BrainOrchestrator {
 
         let count = input.get("count").and_then(|v| v.as_u64()).unwrap_or(5) as usize;
 
-        let similar = self.knowledge_graph.similar(concept, count);
+        // Embed the query itself and rank stored chunks by plain dot
+        // product (every stored chunk is already L2-normalized), instead of
+        // requiring the query to already be a node with a precomputed vector.
+        let mut query_vector = self.embedding_provider.embed(&[concept.to_string()])
+            .map_err(|e| Error::InferenceFailed(format!("Failed to embed query: {}", e)))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::InferenceFailed("Embedding provider returned no vector".into()))?;
+        crate::embedding::l2_normalize(&mut query_vector);
+
+        let similar = self.knowledge_graph.similar_by_vector(&query_vector, count);
 
         Ok(ToolResult {
             tool: "knowledge_similar".into(),
@@ -656,13 +1002,40 @@ impl BrainOrchestrator {
             .and_then(|v| v.as_str())
             .ok_or_else(|| Error::InferenceFailed("Missing daemon name".into()))?;
 
-        // Note: Full stop implementation would require stopping specific daemon
+        // Pull out the pieces we need without holding the manager's
+        // std::sync::MutexGuard across the await below (it isn't Send).
+        let (stop_flag, supervisor_handle, status) = {
+            let mut dm = self.daemon_manager.lock().unwrap();
+            dm.take_for_stop(name)
+                .ok_or_else(|| Error::InferenceFailed(format!("Daemon not found: {}", name)))?
+        };
+
+        stop_flag.store(true, Ordering::SeqCst);
+        if let Some(handle) = supervisor_handle {
+            let _ = handle.await;
+        }
+
+        let final_status = {
+            let mut s = status.lock().unwrap();
+            if s.state != DaemonState::Failed {
+                s.state = DaemonState::Stopped;
+            }
+            s.running = false;
+            s.clone()
+        };
+
+        {
+            let dm = self.daemon_manager.lock().unwrap();
+            dm.emit(DaemonEvent::Stopped { daemon: name.to_string() });
+        }
+
         Ok(ToolResult {
             tool: "daemon_stop".into(),
             success: true,
             output: serde_json::json!({
                 "daemon": name,
-                "status": "stopped",
+                "state": format!("{:?}", final_status.state),
+                "running": final_status.running,
             }),
             side_effects: vec![],
             learnings: vec![],
@@ -672,10 +1045,11 @@ impl BrainOrchestrator {
     async fn tool_daemon_list(&self, _input: &serde_json::Value) -> Result<ToolResult> {
         let dm = self.daemon_manager.lock().unwrap();
         let daemons: Vec<serde_json::Value> = dm.list().iter()
-            .map(|(name, dtype, running)| serde_json::json!({
+            .map(|(name, dtype, running, state)| serde_json::json!({
                 "name": name,
                 "type": format!("{:?}", dtype),
                 "running": running,
+                "state": format!("{:?}", state),
             }))
             .collect();
 
@@ -706,6 +1080,7 @@ impl BrainOrchestrator {
                 output: serde_json::json!({
                     "daemon": name,
                     "running": s.running,
+                    "state": format!("{:?}", s.state),
                     "cycles": s.cycles,
                     "errors": s.errors,
                     "metrics": {
@@ -734,13 +1109,13 @@ impl BrainOrchestrator {
                 "growth_direction": snapshot.growth_direction,
             }),
             "capabilities" => serde_json::json!({
-                "skills": self.skill_registry.list().len(),
+                "skills": self.skill_registry.lock().unwrap().list().len(),
                 "tools": self.tool_registry.list().len(),
                 "daemons": snapshot.active_daemons,
             }),
             "growth" => serde_json::json!({
                 "direction": snapshot.growth_direction,
-                "rate": self.config.growth_rate,
+                "rate": *self.growth_rate.lock().unwrap(),
             }),
             "context" => serde_json::json!({
                 "attention": snapshot.attention,
@@ -1136,6 +1511,8 @@ impl BrainOrchestrator {
             recorded_at: Utc::now(),
         };
 
+        self.check_standing_queries_for_position(&position);
+
         // Record in tesseract
         {
             let mut tesseract = self.tesseract.lock().unwrap();
@@ -1148,6 +1525,29 @@ impl BrainOrchestrator {
             alexandria.ensure_concept(concept);
         }
 
+        // Persist to disk if durable storage is configured (see
+        // `BrainConfig::alexandria_db_path`), so drift analysis survives a
+        // restart. Sled's API is blocking, so the writes run off the async
+        // runtime's worker threads.
+        if let Some(repo) = self.tesseract_repo.clone() {
+            let tesseract = self.tesseract.clone();
+            let _ = tokio::task::spawn_blocking(move || {
+                let tesseract = tesseract.lock().unwrap();
+                if let Err(e) = tesseract.persist_positions(repo.as_ref(), &concept_id) {
+                    tracing::warn!("Failed to persist HyperPosition to disk: {}", e);
+                }
+            }).await;
+        }
+        if let Some(repo) = self.alexandria_repo.clone() {
+            let alexandria = self.alexandria.clone();
+            let _ = tokio::task::spawn_blocking(move || {
+                let alexandria = alexandria.lock().unwrap();
+                if let Err(e) = alexandria.persist_concept(repo.as_ref(), &concept_id) {
+                    tracing::warn!("Failed to persist Alexandria concept to disk: {}", e);
+                }
+            }).await;
+        }
+
         let _ = self.event_tx.send(BrainEvent::AlexandriaEdge {
             from: concept.to_string(),
             to: "hypercube".to_string(),
@@ -1166,12 +1566,148 @@ impl BrainOrchestrator {
             learnings: vec![concept.to_string()],
         })
     }
+
+    /// Project the concept-valued faces (actual, eliminated, potential,
+    /// purpose, method) of a newly recorded `HyperPosition` into the
+    /// standing-query index and emit a `BrainEvent` for each match. Each
+    /// face value (e.g. a concept added to PURPOSE) is projected
+    /// independently, so a pattern like `{kind: "position", face: "purpose",
+    /// face_value: "*"}` fires once per concept gained in that face rather
+    /// than once for the whole position.
+    fn check_standing_queries_for_position(&self, position: &HyperPosition) {
+        let concept_hex = position.concept.to_hex();
+        let faces: [(&str, &[ConceptId]); 5] = [
+            ("actual", &position.actual),
+            ("eliminated", &position.eliminated),
+            ("potential", &position.potential),
+            ("purpose", &position.purpose),
+            ("method", &position.method),
+        ];
+
+        let standing_queries = self.standing_queries.lock().unwrap();
+        for (face, values) in faces {
+            for value in values {
+                let record: HashMap<String, String> = HashMap::from([
+                    ("kind".to_string(), "position".to_string()),
+                    ("concept".to_string(), concept_hex.clone()),
+                    ("face".to_string(), face.to_string()),
+                    ("face_value".to_string(), value.to_hex()),
+                ]);
+                for m in standing_queries.matches(&record) {
+                    for subscription_id in m.ids {
+                        let _ = self.event_tx.send(BrainEvent::StandingQueryMatch {
+                            subscription_id,
+                            captures: m.captures.clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Project a newly inserted `AlexandriaEdge` into the standing-query
+    /// index and emit a `BrainEvent` for each match.
+    fn check_standing_queries_for_edge(&self, from: ConceptId, to: ConceptId, kind: &gently_alexandria::EdgeKind) {
+        let record: HashMap<String, String> = HashMap::from([
+            ("kind".to_string(), "edge".to_string()),
+            ("edge_kind".to_string(), format!("{:?}", kind)),
+            ("edge_from".to_string(), from.to_hex()),
+            ("edge_to".to_string(), to.to_hex()),
+        ]);
+
+        let standing_queries = self.standing_queries.lock().unwrap();
+        for m in standing_queries.matches(&record) {
+            for subscription_id in m.ids {
+                let _ = self.event_tx.send(BrainEvent::StandingQueryMatch {
+                    subscription_id,
+                    captures: m.captures.clone(),
+                });
+            }
+        }
+    }
+
+    /// Register a standing query over Alexandria position/edge records (see
+    /// `standing_query`). `input` is a flat object of field/value pairs -
+    /// `kind` ("position" or "edge") plus whichever of `concept`, `face`,
+    /// `face_value`, `edge_kind`, `edge_from`, `edge_to` the pattern
+    /// constrains - where a value of `"*"` captures instead of requiring a
+    /// literal.
+    async fn tool_alexandria_subscribe(&self, input: &serde_json::Value) -> Result<ToolResult> {
+        let fields: HashMap<String, String> = input.as_object()
+            .ok_or_else(|| Error::InferenceFailed("Subscription pattern must be an object".into()))?
+            .iter()
+            .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+            .collect();
+
+        if !matches!(fields.get("kind").map(String::as_str), Some("position") | Some("edge")) {
+            return Err(Error::InferenceFailed("Missing or invalid kind (expected \"position\" or \"edge\")".into()));
+        }
+
+        let subscription_id = self.standing_queries.lock().unwrap().subscribe(&fields);
+
+        Ok(ToolResult {
+            tool: "alexandria_subscribe".into(),
+            success: true,
+            output: serde_json::json!({ "subscription_id": subscription_id }),
+            side_effects: vec![],
+            learnings: vec![],
+        })
+    }
+
+    /// Remove a standing query previously registered via
+    /// `tool_alexandria_subscribe`.
+    async fn tool_alexandria_unsubscribe(&self, input: &serde_json::Value) -> Result<ToolResult> {
+        let subscription_id = input.get("subscription_id")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| Error::InferenceFailed("Missing subscription_id".into()))?;
+
+        self.standing_queries.lock().unwrap().unsubscribe(subscription_id);
+
+        Ok(ToolResult {
+            tool: "alexandria_unsubscribe".into(),
+            success: true,
+            output: serde_json::json!({ "subscription_id": subscription_id, "removed": true }),
+            side_effects: vec![],
+            learnings: vec![],
+        })
+    }
+
+    /// Run a batch of tool calls via `execute_batch`. `input` is
+    /// `{ "ops": [{"tool": ..., "input": ...}, ...], "stop_on_error": bool }`
+    /// (`stop_on_error` defaults to `false`, i.e. best-effort).
+    async fn tool_batch(&self, input: &serde_json::Value) -> Result<ToolResult> {
+        let ops_value = input.get("ops")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| Error::InferenceFailed("Missing ops array".into()))?;
+
+        let mut ops = Vec::with_capacity(ops_value.len());
+        for op in ops_value {
+            let tool = op.get("tool")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| Error::InferenceFailed("Batch op missing tool name".into()))?
+                .to_string();
+            let op_input = op.get("input").cloned().unwrap_or_else(|| serde_json::json!({}));
+            ops.push(BatchOp { tool, input: op_input });
+        }
+
+        let stop_on_error = input.get("stop_on_error").and_then(|v| v.as_bool()).unwrap_or(false);
+        let batch = self.execute_batch(&ops, stop_on_error).await;
+
+        Ok(ToolResult {
+            tool: "batch".into(),
+            success: batch.success,
+            output: serde_json::json!({
+                "results": batch.results,
+                "count": batch.results.len(),
+            }),
+            side_effects: batch.side_effects,
+            learnings: batch.learnings,
+        })
+    }
 }
 
 /// Run the awareness loop - the "consciousness" that processes thoughts
 pub async fn run_awareness_loop(orchestrator: Arc<BrainOrchestrator>) {
-    let interval = std::time::Duration::from_millis(orchestrator.config.awareness_interval_ms);
-
     while orchestrator.running.load(Ordering::SeqCst) {
         // Process pending thoughts
         let thought = {
@@ -1189,7 +1725,8 @@ pub async fn run_awareness_loop(orchestrator: Arc<BrainOrchestrator>) {
             let _ = orchestrator.event_tx.send(BrainEvent::AwarenessUpdate(snapshot));
         }
 
-        tokio::time::sleep(interval).await;
+        let interval_ms = orchestrator.awareness_interval_ms.load(Ordering::SeqCst);
+        tokio::time::sleep(std::time::Duration::from_millis(interval_ms)).await;
     }
 }
 
@@ -1204,7 +1741,7 @@ mod tests {
 
         // Should have registries
         assert!(!orchestrator.tool_registry().list().is_empty());
-        assert!(!orchestrator.skill_registry().list().is_empty());
+        assert!(!orchestrator.skill_registry().lock().unwrap().list().is_empty());
     }
 
     #[tokio::test]
@@ -1256,6 +1793,31 @@ mod tests {
         assert!(tool_result.is_ok(), "Alexandria navigate tool should work");
     }
 
+    #[tokio::test]
+    async fn test_knowledge_similar_tool_uses_real_embeddings() {
+        let config = BrainConfig { enable_daemons: false, ..Default::default() };
+        let orchestrator = BrainOrchestrator::new(config);
+
+        // "X is Y" is the pattern `KnowledgeGraph::learn` extracts nodes from.
+        let learn_input = serde_json::json!({
+            "concept": "rust is memory-safe",
+            "context": "Rust prevents memory safety bugs through ownership",
+        });
+        orchestrator.execute_tool("knowledge_learn", &learn_input).await.unwrap();
+
+        // Daemons aren't running in this test, so drive the vector chain
+        // daemon's queue synchronously instead of waiting on its loop.
+        while let Some(job) = orchestrator.vector_daemon.pop_job() {
+            orchestrator.vector_daemon.process_job(&job);
+        }
+
+        let similar_input = serde_json::json!({ "concept": "memory safety", "count": 5 });
+        let result = orchestrator.execute_tool("knowledge_similar", &similar_input).await.unwrap();
+        assert!(result.success);
+        let similar = result.output["similar"].as_array().unwrap();
+        assert!(!similar.is_empty(), "expected at least one similar concept from stored chunks");
+    }
+
     #[tokio::test]
     async fn test_tesseract_integration() {
         let config = BrainConfig { enable_daemons: false, ..Default::default() };
@@ -1279,4 +1841,63 @@ mod tests {
         let query_result = orchestrator.execute_tool("alexandria_tesseract", &tesseract_input).await;
         assert!(query_result.is_ok(), "Should be able to query tesseract");
     }
+
+    #[tokio::test]
+    async fn test_daemon_stop_actually_stops_the_supervised_task() {
+        let config = BrainConfig { enable_daemons: false, ..Default::default() };
+        let orchestrator = BrainOrchestrator::new(config);
+
+        let spawn_input = serde_json::json!({ "daemon_type": "awareness" });
+        let spawn_result = orchestrator.execute_tool("daemon_spawn", &spawn_input).await.unwrap();
+        let name = spawn_result.output["daemon"].as_str().unwrap().to_string();
+
+        let stop_input = serde_json::json!({ "name": name });
+        let stop_result = orchestrator.execute_tool("daemon_stop", &stop_input).await.unwrap();
+        assert!(stop_result.success);
+        assert_eq!(stop_result.output["state"], "Stopped");
+        assert_eq!(stop_result.output["running"], false);
+
+        let metrics_input = serde_json::json!({ "name": name });
+        let metrics_result = orchestrator.execute_tool("daemon_metrics", &metrics_input).await.unwrap();
+        assert_eq!(metrics_result.output["state"], "Stopped");
+    }
+
+    #[tokio::test]
+    async fn test_batch_runs_every_op_best_effort_by_default() {
+        let config = BrainConfig { enable_daemons: false, ..Default::default() };
+        let orchestrator = BrainOrchestrator::new(config);
+
+        let batch_input = serde_json::json!({
+            "ops": [
+                { "tool": "alexandria_record", "input": { "concept": "batched_concept", "actual": ["state1"] } },
+                { "tool": "not_a_real_tool", "input": {} },
+                { "tool": "alexandria_tesseract", "input": { "concept": "batched_concept" } },
+            ]
+        });
+
+        let result = orchestrator.execute_tool("batch", &batch_input).await.unwrap();
+        assert!(!result.success, "a failing op should mark the whole batch unsuccessful");
+        let results = result.output["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3, "best-effort batches run every op, even after a failure");
+        assert_eq!(result.output["count"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_batch_stop_on_error_halts_after_first_failure() {
+        let config = BrainConfig { enable_daemons: false, ..Default::default() };
+        let orchestrator = BrainOrchestrator::new(config);
+
+        let batch_input = serde_json::json!({
+            "stop_on_error": true,
+            "ops": [
+                { "tool": "not_a_real_tool", "input": {} },
+                { "tool": "alexandria_tesseract", "input": { "concept": "unreached" } },
+            ]
+        });
+
+        let result = orchestrator.execute_tool("batch", &batch_input).await.unwrap();
+        assert!(!result.success);
+        let results = result.output["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1, "stop_on_error should halt before the second op runs");
+    }
 }
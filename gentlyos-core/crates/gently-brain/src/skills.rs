@@ -4,8 +4,9 @@
 //! Skills are self-contained units of functionality.
 
 use crate::{Result, Error};
+use crate::discrimination_net::DiscriminationNet;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 /// A skill that can be invoked by the assistant
@@ -93,6 +94,9 @@ pub struct Learning {
 pub struct SkillRegistry {
     skills: HashMap<String, Skill>,
     handlers: HashMap<String, Arc<dyn SkillHandler + Send + Sync>>,
+    /// Indexes every skill's triggers for sublinear `find_by_trigger`
+    /// lookups, kept in sync by `register`/`unregister`.
+    trigger_net: DiscriminationNet,
 }
 
 /// Trait for skill execution
@@ -106,6 +110,7 @@ impl SkillRegistry {
         let mut registry = Self {
             skills: HashMap::new(),
             handlers: HashMap::new(),
+            trigger_net: DiscriminationNet::new(),
         };
         registry.register_builtins();
         registry
@@ -246,9 +251,23 @@ impl SkillRegistry {
     }
 
     pub fn register(&mut self, skill: Skill) {
+        // Re-registering an existing skill (e.g. a config reload updating
+        // its triggers) must drop its old trigger entries first.
+        self.trigger_net.remove(&skill.name);
+        for trigger in &skill.triggers {
+            self.trigger_net.add(&skill.name, trigger);
+        }
         self.skills.insert(skill.name.clone(), skill);
     }
 
+    /// Remove a skill and its handler, if any (e.g. a config reload that
+    /// drops a declared skill). Returns the removed skill, if it existed.
+    pub fn unregister(&mut self, name: &str) -> Option<Skill> {
+        self.trigger_net.remove(name);
+        self.handlers.remove(name);
+        self.skills.remove(name)
+    }
+
     pub fn register_handler<H: SkillHandler + Send + Sync + 'static>(&mut self, name: &str, handler: H) {
         self.handlers.insert(name.to_string(), Arc::new(handler));
     }
@@ -258,10 +277,27 @@ impl SkillRegistry {
     }
 
     pub fn find_by_trigger(&self, input: &str) -> Vec<&Skill> {
-        let input_lower = input.to_lowercase();
-        self.skills.values()
-            .filter(|s| s.enabled && s.triggers.iter().any(|t| input_lower.contains(t)))
-            .collect()
+        self.match_triggers(input).into_iter().map(|(skill, _)| skill).collect()
+    }
+
+    /// Like `find_by_trigger`, but also surfaces the tokens each match's `*`
+    /// wildcards captured.
+    pub fn match_triggers(&self, input: &str) -> Vec<(&Skill, Vec<String>)> {
+        let mut seen = HashSet::new();
+        let mut out = Vec::new();
+        for m in self.trigger_net.matches(input) {
+            for name in &m.ids {
+                if !seen.insert(name.clone()) {
+                    continue;
+                }
+                if let Some(skill) = self.skills.get(name) {
+                    if skill.enabled {
+                        out.push((skill, m.bindings.clone()));
+                    }
+                }
+            }
+        }
+        out
     }
 
     pub fn list(&self) -> Vec<&Skill> {
@@ -336,4 +372,14 @@ mod tests {
         let skills = registry.find_by_trigger("crack this hash");
         assert!(!skills.is_empty());
     }
+
+    #[test]
+    fn test_unregister_removes_skill() {
+        let mut registry = SkillRegistry::new();
+        assert!(registry.get("learn").is_some());
+        let removed = registry.unregister("learn");
+        assert_eq!(removed.unwrap().name, "learn");
+        assert!(registry.get("learn").is_none());
+        assert!(registry.unregister("learn").is_none());
+    }
 }
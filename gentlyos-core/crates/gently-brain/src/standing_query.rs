@@ -0,0 +1,337 @@
+//! Standing-query subsystem for the Alexandria reactive dataspace
+//!
+//! `tool_alexandria_record` and the edge-insertion sites in
+//! `process_single_thought` used to be poll-only: a subscriber could only
+//! notice a new `HyperPosition` face or `AlexandriaEdge` by calling
+//! `alexandria_navigate`/`alexandria_drift` again later. `tool_alexandria_subscribe`
+//! registers a standing pattern over those records instead - "any concept
+//! whose PURPOSE face gains X", "edges of kind Causes into concept Y" - and
+//! `BrainOrchestrator` fires a `BrainEvent::StandingQueryMatch` the moment a
+//! later record satisfies it.
+//!
+//! This reuses the discrimination-trie shape from `discrimination_net`
+//! (itself modelled on syndicate's skeleton index): every record - a
+//! recorded position face or a newly inserted edge - is projected onto the
+//! fixed, ordered field set in `FIELDS`. A pattern only ever constrains a
+//! subset of those fields, either to a literal ("const") or to a capture
+//! ("*"); the fields it leaves unconstrained are absent. That triple
+//! (absent/const/capture) per field is the pattern's `Skeleton`, and the trie
+//! branches on it one field at a time, so matching a record only walks the
+//! branches whose skeleton could apply to it rather than scanning every
+//! registered pattern. Patterns that share a skeleton but different literal
+//! values are disambiguated at the leaf by a `leaf_map` keyed on the const
+//! values observed along the walk - the same trick `discrimination_net` uses
+//! for overlapping trigger phrases.
+
+use std::collections::{HashMap, HashSet};
+
+/// The fixed, ordered set of fields a standing-query record is projected
+/// onto. A pattern's skeleton is which of these it constrains; field order
+/// is the trie's depth order. `kind` discriminates a position-record
+/// ("position") from an edge-record ("edge"); the rest apply to one kind or
+/// the other and are simply absent on records of the other kind.
+const FIELDS: [&str; 7] = [
+    "kind", "concept", "face", "face_value", "edge_kind", "edge_from", "edge_to",
+];
+
+/// Opaque handle returned by `StandingQueryIndex::subscribe`, used later to
+/// `unsubscribe` and to identify which registered pattern matched in a
+/// `Match`.
+pub type SubscriptionId = u64;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum FieldPattern {
+    Const(String),
+    Capture,
+}
+
+/// One registered standing query, compiled from the subscriber's raw
+/// field/value map (see `tool_alexandria_subscribe`) into a const/capture/
+/// absent slot per `FIELDS` entry. A `"*"` value compiles to a capture.
+#[derive(Debug, Clone)]
+struct Pattern {
+    slots: Vec<Option<FieldPattern>>,
+}
+
+impl Pattern {
+    fn compile(fields: &HashMap<String, String>) -> Self {
+        let slots = FIELDS
+            .iter()
+            .map(|name| {
+                fields.get(*name).map(|value| {
+                    if value == "*" {
+                        FieldPattern::Capture
+                    } else {
+                        FieldPattern::Const(value.clone())
+                    }
+                })
+            })
+            .collect();
+        Self { slots }
+    }
+
+    /// The literal values required at this pattern's const slots, in
+    /// `FIELDS` order - the key a leaf's `leaf_map` is looked up by.
+    fn const_tuple(&self) -> Vec<String> {
+        self.slots
+            .iter()
+            .filter_map(|slot| match slot {
+                Some(FieldPattern::Const(value)) => Some(value.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct Continuation {
+    leaf_map: HashMap<Vec<String>, HashSet<SubscriptionId>>,
+}
+
+#[derive(Default)]
+struct Node {
+    absent_child: Option<Box<Node>>,
+    const_child: Option<Box<Node>>,
+    capture_child: Option<Box<Node>>,
+    leaf: Option<Continuation>,
+}
+
+impl Node {
+    fn insert(&mut self, slots: &[Option<FieldPattern>], depth: usize, id: SubscriptionId, const_tuple: &[String]) {
+        if depth == slots.len() {
+            self.leaf
+                .get_or_insert_with(Continuation::default)
+                .leaf_map
+                .entry(const_tuple.to_vec())
+                .or_default()
+                .insert(id);
+            return;
+        }
+        let child = match &slots[depth] {
+            None => self.absent_child.get_or_insert_with(|| Box::new(Node::default())),
+            Some(FieldPattern::Const(_)) => self.const_child.get_or_insert_with(|| Box::new(Node::default())),
+            Some(FieldPattern::Capture) => self.capture_child.get_or_insert_with(|| Box::new(Node::default())),
+        };
+        child.insert(slots, depth + 1, id, const_tuple);
+    }
+
+    fn remove(&mut self, slots: &[Option<FieldPattern>], depth: usize, id: SubscriptionId, const_tuple: &[String]) {
+        if depth == slots.len() {
+            if let Some(leaf) = &mut self.leaf {
+                if let Some(ids) = leaf.leaf_map.get_mut(const_tuple) {
+                    ids.remove(&id);
+                    if ids.is_empty() {
+                        leaf.leaf_map.remove(const_tuple);
+                    }
+                }
+            }
+            return;
+        }
+        let child = match &slots[depth] {
+            None => self.absent_child.as_mut(),
+            Some(FieldPattern::Const(_)) => self.const_child.as_mut(),
+            Some(FieldPattern::Capture) => self.capture_child.as_mut(),
+        };
+        if let Some(child) = child {
+            child.remove(slots, depth + 1, id, const_tuple);
+        }
+    }
+
+    /// Walk every live path from this node against `values` (the record's
+    /// projection onto `FIELDS`, `None` where the field doesn't apply to
+    /// this record's kind), recording a match for each leaf reached whose
+    /// `leaf_map` recognizes the const values observed along the way.
+    ///
+    /// A field absent from the *pattern* is unconstrained, so the
+    /// `absent_child` branch is always explored regardless of the record's
+    /// value. A field present in the record can satisfy both a `const`
+    /// pattern (if the values are equal) and a `capture` pattern at the same
+    /// time, so both children are explored when the record has a value -
+    /// this is what lets overlapping const/capture paths, and a single
+    /// record matching more than one registered pattern, fall out of one
+    /// walk instead of needing special-casing.
+    fn walk(
+        &self,
+        values: &[Option<String>],
+        depth: usize,
+        const_so_far: &mut Vec<String>,
+        captures: &mut Vec<(String, String)>,
+        out: &mut Vec<Match>,
+    ) {
+        if depth == values.len() {
+            if let Some(leaf) = &self.leaf {
+                if let Some(ids) = leaf.leaf_map.get(const_so_far) {
+                    out.push(Match { ids: ids.clone(), captures: captures.clone() });
+                }
+            }
+            return;
+        }
+
+        if let Some(child) = &self.absent_child {
+            child.walk(values, depth + 1, const_so_far, captures, out);
+        }
+
+        if let Some(value) = &values[depth] {
+            if let Some(child) = &self.const_child {
+                const_so_far.push(value.clone());
+                child.walk(values, depth + 1, const_so_far, captures, out);
+                const_so_far.pop();
+            }
+            if let Some(child) = &self.capture_child {
+                captures.push((FIELDS[depth].to_string(), value.clone()));
+                child.walk(values, depth + 1, const_so_far, captures, out);
+                captures.pop();
+            }
+        }
+    }
+}
+
+/// A standing query match found while projecting one new record.
+#[derive(Debug, Clone)]
+pub struct Match {
+    /// Subscriptions whose pattern matched this record.
+    pub ids: HashSet<SubscriptionId>,
+    /// Field/value pairs captured by this pattern's `"*"` slots.
+    pub captures: Vec<(String, String)>,
+}
+
+/// An incrementally-updatable index of standing queries over Alexandria
+/// position/edge records, supporting sublinear matching as new records
+/// arrive (see the module docs for the trie shape).
+#[derive(Default)]
+pub struct StandingQueryIndex {
+    root: Node,
+    next_id: SubscriptionId,
+    id_patterns: HashMap<SubscriptionId, Pattern>,
+}
+
+impl StandingQueryIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a standing query from its raw field/value map (string
+    /// values only; `"*"` marks a capture) and return the id used to
+    /// `unsubscribe` it later.
+    pub fn subscribe(&mut self, fields: &HashMap<String, String>) -> SubscriptionId {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let pattern = Pattern::compile(fields);
+        let const_tuple = pattern.const_tuple();
+        self.root.insert(&pattern.slots, 0, id, &const_tuple);
+        self.id_patterns.insert(id, pattern);
+        id
+    }
+
+    /// Remove a previously registered standing query. A no-op if `id` is
+    /// unknown (already removed, or never registered).
+    pub fn unsubscribe(&mut self, id: SubscriptionId) {
+        if let Some(pattern) = self.id_patterns.remove(&id) {
+            let const_tuple = pattern.const_tuple();
+            self.root.remove(&pattern.slots, 0, id, &const_tuple);
+        }
+    }
+
+    /// Project `record` onto `FIELDS` and find every standing query it
+    /// satisfies. A single record can appear in more than one `Match` (one
+    /// per distinct skeleton+const-tuple leaf reached), and a single
+    /// `Match` can carry more than one subscription id.
+    pub fn matches(&self, record: &HashMap<String, String>) -> Vec<Match> {
+        let values: Vec<Option<String>> = FIELDS.iter().map(|name| record.get(*name).cloned()).collect();
+        let mut out = Vec::new();
+        self.root.walk(&values, 0, &mut Vec::new(), &mut Vec::new(), &mut out);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_const_pattern_matches_literal_record() {
+        let mut index = StandingQueryIndex::new();
+        let id = index.subscribe(&record(&[("kind", "position"), ("face", "purpose"), ("face_value", "rust")]));
+
+        let matches = index.matches(&record(&[
+            ("kind", "position"),
+            ("concept", "ownership"),
+            ("face", "purpose"),
+            ("face_value", "rust"),
+        ]));
+
+        assert!(matches.iter().any(|m| m.ids.contains(&id)));
+    }
+
+    #[test]
+    fn test_capture_binds_matched_value() {
+        let mut index = StandingQueryIndex::new();
+        let id = index.subscribe(&record(&[("kind", "edge"), ("edge_kind", "Causes"), ("edge_to", "*")]));
+
+        let matches = index.matches(&record(&[
+            ("kind", "edge"),
+            ("edge_kind", "Causes"),
+            ("edge_from", "lightning"),
+            ("edge_to", "thunder"),
+        ]));
+
+        let m = matches.iter().find(|m| m.ids.contains(&id)).expect("expected a match");
+        assert!(m.captures.contains(&("edge_to".to_string(), "thunder".to_string())));
+    }
+
+    #[test]
+    fn test_const_mismatch_does_not_match() {
+        let mut index = StandingQueryIndex::new();
+        let id = index.subscribe(&record(&[("kind", "edge"), ("edge_kind", "Causes")]));
+
+        let matches = index.matches(&record(&[("kind", "edge"), ("edge_kind", "Enables")]));
+
+        assert!(!matches.iter().any(|m| m.ids.contains(&id)));
+    }
+
+    #[test]
+    fn test_disjoint_kinds_do_not_cross_match() {
+        let mut index = StandingQueryIndex::new();
+        let position_sub = index.subscribe(&record(&[("kind", "position"), ("face", "purpose")]));
+        let edge_sub = index.subscribe(&record(&[("kind", "edge"), ("edge_kind", "Causes")]));
+
+        let matches = index.matches(&record(&[("kind", "position"), ("face", "purpose"), ("face_value", "x")]));
+        let ids: HashSet<SubscriptionId> = matches.into_iter().flat_map(|m| m.ids).collect();
+
+        assert!(ids.contains(&position_sub));
+        assert!(!ids.contains(&edge_sub));
+    }
+
+    #[test]
+    fn test_one_record_matches_multiple_overlapping_patterns() {
+        let mut index = StandingQueryIndex::new();
+        let specific = index.subscribe(&record(&[("kind", "edge"), ("edge_kind", "Causes"), ("edge_to", "thunder")]));
+        let general = index.subscribe(&record(&[("kind", "edge"), ("edge_to", "*")]));
+
+        let matches = index.matches(&record(&[
+            ("kind", "edge"),
+            ("edge_kind", "Causes"),
+            ("edge_from", "lightning"),
+            ("edge_to", "thunder"),
+        ]));
+        let ids: HashSet<SubscriptionId> = matches.into_iter().flat_map(|m| m.ids).collect();
+
+        assert!(ids.contains(&specific));
+        assert!(ids.contains(&general));
+    }
+
+    #[test]
+    fn test_unsubscribe_removes_pattern() {
+        let mut index = StandingQueryIndex::new();
+        let id = index.subscribe(&record(&[("kind", "position"), ("face", "purpose")]));
+        index.unsubscribe(id);
+
+        let matches = index.matches(&record(&[("kind", "position"), ("face", "purpose"), ("face_value", "x")]));
+        assert!(!matches.iter().any(|m| m.ids.contains(&id)));
+    }
+}
@@ -32,4 +32,4 @@ pub use transactions::{PtbBuilder, TransactionResult};
 pub use events::{EventFilter, SuiEvent};
 pub use types::{ReasoningStep, ObjectID};
 pub use three_kings::ThreeKings;
-pub use transpile::{MoveModule, codie_to_move, source_to_move};
+pub use transpile::{MoveModule, codie_to_move, source_to_move, move_to_codie};
@@ -19,12 +19,19 @@
 //! turk             TODO comment (incomplete marker)
 //! ```
 //!
+//! A fence rule prefixed `REQUIRES:`/`ENSURES:` additionally emits a Move Prover
+//! `spec` clause (precondition/postcondition) next to its defensive `assert!`,
+//! so the same rule is both runtime-checked and statically provable. An
+//! `INVARIANT:` rule becomes a struct invariant in `spec <Struct>` only; it
+//! emits no runtime `assert!`.
+//!
 //! CODIE doesn't need a general-purpose code generator.
 //! CODIE IS Move's human-readable layer.
 //! The blockchain IS the evaluator.
 
 use anyhow::{Result, bail};
 use std::fmt::Write;
+use std::hash::{Hash, Hasher};
 
 use gently_codie::{CodieAst, CodieType, SourceKind};
 
@@ -43,6 +50,23 @@ pub struct MoveModule {
     pub functions: Vec<MoveFunction>,
     /// Dependencies (bark @external references)
     pub dependencies: Vec<String>,
+    /// Abort-code table: one entry per distinct fence/bone constraint, for
+    /// client-side error decoding
+    pub errors: Vec<MoveErrorCode>,
+}
+
+/// A stable abort-code assignment for one fence/bone constraint.
+///
+/// `code` is derived by hashing `description`, so re-running the transpiler
+/// keeps codes stable across unrelated edits elsewhere in the source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveErrorCode {
+    /// Move constant identifier, e.g. `E_AMOUNT_MUST_BE_POSITIVE`
+    pub name: String,
+    /// Numeric abort code referenced by the generated `assert!`
+    pub code: u64,
+    /// Original constraint text this code stands for
+    pub description: String,
 }
 
 /// A Move struct definition (from bone or blob)
@@ -52,6 +76,9 @@ pub struct MoveStruct {
     /// bone = has key (linear resource), blob = has key + store + drop (flexible)
     pub abilities: Vec<MoveAbility>,
     pub fields: Vec<MoveField>,
+    /// Move Prover invariants (from `fence`'s `INVARIANT:` rules), rendered once
+    /// as `spec <Struct> { invariant ...; }`
+    pub invariants: Vec<String>,
 }
 
 /// Move abilities — the physics of the type system
@@ -93,6 +120,12 @@ pub struct MoveFunction {
     pub params: Vec<MoveParam>,
     pub return_type: Option<String>,
     pub body: Vec<MoveStatement>,
+    /// Move Prover preconditions (from `fence`'s `REQUIRES:` rules), rendered as
+    /// `requires ...;` in this function's `spec` block
+    pub requires: Vec<String>,
+    /// Move Prover postconditions (from `fence`'s `ENSURES:` rules), rendered as
+    /// `ensures ...;` in this function's `spec` block
+    pub ensures: Vec<String>,
 }
 
 /// Move function visibility
@@ -126,8 +159,9 @@ pub enum MoveStatement {
     Return(String),
     /// anchor → event::emit
     Emit { event_type: String, fields: Vec<(String, String)> },
-    /// fence/bone → assert! (constraint enforcement)
-    Assert { condition: String, error_code: u64 },
+    /// fence/bone → assert! (constraint enforcement), referencing a named
+    /// `E_<NAME>` abort-code constant rather than a bare number
+    Assert { condition: String, error_name: String },
     /// Raw Move expression (for complex transpilations)
     Raw(String),
     /// spin → transfer_to_sender or share_object
@@ -167,6 +201,198 @@ pub fn hash_to_move(hash: &str) -> Result<MoveModule> {
     source_to_move(&source)
 }
 
+/// Decompile a Move module back into a canonical CODIE `Program`.
+///
+/// This is the inverse of [`codie_to_move`]: structs reform into `bone`/`blob`
+/// nodes, `public entry fun`s reform into `pin` specifications, plain functions
+/// reform into `cali`, and the `assert!`/`requires`/`ensures` the transpiler
+/// injected reform into a `fence` block right before the entity they guard.
+/// It is a *reform/normalize* pass, not a lossless reverse — running
+/// `codie -> move -> codie -> move` should reach a fixed point (the second
+/// `move` matches the first) even though the first `codie -> move` step can
+/// lose source-only details like the original identifier casing.
+pub fn move_to_codie(module: &MoveModule) -> CodieAst {
+    let mut body = Vec::new();
+
+    for s in &module.structs {
+        body.push(struct_to_codie_node(s));
+        if !s.invariants.is_empty() {
+            body.push(CodieAst::Constraint {
+                rules: s.invariants.iter()
+                    .map(|rule| CodieAst::Immutable { rule: format!("INVARIANT: {}", rule) })
+                    .collect(),
+            });
+        }
+    }
+
+    for f in &module.functions {
+        if let Some(fence) = function_fence_node(f) {
+            body.push(fence);
+        }
+        body.push(function_to_codie_node(f));
+    }
+
+    CodieAst::Program {
+        name: module.name.to_uppercase(),
+        hash: None,
+        body,
+    }
+}
+
+/// Reform a `bone`/`blob` struct from its Move shape.
+fn struct_to_codie_node(s: &MoveStruct) -> CodieAst {
+    if s.abilities.contains(&MoveAbility::Drop) {
+        // blob: flexible struct, fields reform into elf bindings (id is synthetic)
+        let fields: Vec<CodieAst> = s.fields.iter()
+            .filter(|f| f.name != "id")
+            .map(|f| CodieAst::Variable {
+                name: f.name.clone(),
+                type_hint: Some(move_type_to_codie(&f.type_name)),
+                value: Box::new(CodieAst::Empty),
+            })
+            .collect();
+        CodieAst::Flexible { name: Some(s.name.clone()), body: fields }
+    } else {
+        // bone: linear resource, the struct name IS the rule
+        CodieAst::Immutable { rule: s.name.clone() }
+    }
+}
+
+/// Reform the `fence` block guarding a function, if it had one.
+fn function_fence_node(f: &MoveFunction) -> Option<CodieAst> {
+    let mut rules: Vec<CodieAst> = Vec::new();
+    for condition in &f.requires {
+        rules.push(CodieAst::Immutable { rule: format!("REQUIRES: {}", condition) });
+    }
+    for condition in &f.ensures {
+        rules.push(CodieAst::Immutable { rule: format!("ENSURES: {}", condition) });
+    }
+
+    // Plain bone constraints show up only as asserts that requires/ensures didn't
+    // already account for.
+    let accounted: std::collections::HashSet<&String> =
+        f.requires.iter().chain(f.ensures.iter()).collect();
+    for stmt in &f.body {
+        if let MoveStatement::Assert { condition, .. } = stmt {
+            if accounted.contains(condition) {
+                continue;
+            }
+            rules.push(CodieAst::Immutable { rule: assert_condition_to_rule(condition) });
+        }
+    }
+
+    if rules.is_empty() {
+        None
+    } else {
+        Some(CodieAst::Constraint { rules })
+    }
+}
+
+/// Reform a `pin`/`cali`/`biz` function from its Move shape.
+fn function_to_codie_node(f: &MoveFunction) -> CodieAst {
+    let params: Vec<&MoveParam> = f.params.iter()
+        .filter(|p| !(p.name == "ctx" && p.type_name == "TxContext"))
+        .collect();
+
+    match f.visibility {
+        // pin: public entry fun → Specification, params become identifier-typed fields
+        MoveVisibility::Public => {
+            let fields = params.iter()
+                .map(|p| (p.name.clone(), CodieAst::Identifier(p.type_name.clone())))
+                .collect();
+            CodieAst::Specification { name: Some(f.name.clone()), fields }
+        }
+        // biz: public fun with a single return → Goal
+        MoveVisibility::PublicPackage => {
+            let expression = f.body.iter()
+                .find_map(|stmt| match stmt {
+                    MoveStatement::Return(value) => Some(move_expr_to_codie(value)),
+                    _ => None,
+                })
+                .unwrap_or(CodieAst::Empty);
+            CodieAst::Goal { expression: Box::new(expression), anchor_hash: None }
+        }
+        // cali: module-private fun → Function
+        MoveVisibility::Internal => {
+            let codie_params: Vec<(String, Option<CodieType>)> = params.iter()
+                .map(|p| (p.name.clone(), Some(move_type_to_codie(&p.type_name))))
+                .collect();
+            let returns = f.return_type.as_ref()
+                .map(|t| Box::new(CodieAst::Identifier(format!("<{}>", t))));
+            CodieAst::Function {
+                name: f.name.clone(),
+                params: codie_params,
+                body: move_body_to_codie(&f.body),
+                returns,
+            }
+        }
+    }
+}
+
+/// Reform the non-constraint statements of a function body.
+fn move_body_to_codie(body: &[MoveStatement]) -> Vec<CodieAst> {
+    body.iter().filter_map(|stmt| match stmt {
+        MoveStatement::Let { name, type_name, value } => Some(CodieAst::Variable {
+            name: name.clone(),
+            type_hint: type_name.as_ref().map(|t| move_type_to_codie(t)),
+            value: Box::new(move_expr_to_codie(value)),
+        }),
+        MoveStatement::Return(value) => Some(CodieAst::Return {
+            value: Box::new(move_expr_to_codie(value)),
+        }),
+        MoveStatement::Comment(msg) => Some(match msg.strip_prefix("TODO: ") {
+            Some(todo) => CodieAst::Incomplete { hash: None, comment: Some(todo.to_string()) },
+            None => CodieAst::Comment(msg.clone()),
+        }),
+        // Asserts reform into the preceding fence block, not the body
+        MoveStatement::Assert { .. } => None,
+        // Borrow/Emit/Transfer/Raw have no single canonical CODIE shape to
+        // reform into; keep them out of the reformed body rather than guess.
+        MoveStatement::Borrow { .. } | MoveStatement::Emit { .. }
+        | MoveStatement::Transfer { .. } | MoveStatement::Raw(_) => None,
+    }).collect()
+}
+
+/// Inverse of `constraint_to_condition`: recover the original bone rule text
+/// from a rendered assert condition.
+fn assert_condition_to_rule(condition: &str) -> String {
+    if let Some(negated) = condition.strip_prefix("true /* NOT: ").and_then(|s| s.strip_suffix(" */")) {
+        format!("NOT: {}", negated)
+    } else if let Some(generic) = condition.strip_prefix("true /* constraint: ").and_then(|s| s.strip_suffix(" */")) {
+        generic.to_string()
+    } else {
+        condition.to_string()
+    }
+}
+
+/// Inverse of `codie_type_to_move`: map a rendered Move type back to a CodieType.
+fn move_type_to_codie(move_type: &str) -> CodieType {
+    match move_type {
+        "u64" => CodieType::Number,
+        "bool" => CodieType::Bool,
+        "vector<u8>" => CodieType::Text,
+        "address" | "ID" => CodieType::Custom(move_type.to_lowercase()),
+        t if t.starts_with("vector<") && t.ends_with('>') => {
+            CodieType::List(Box::new(move_type_to_codie(&t[7..t.len() - 1])))
+        }
+        other => CodieType::Custom(other.to_string()),
+    }
+}
+
+/// Inverse of `ast_to_move_expr` for the literal/identifier forms it produces.
+fn move_expr_to_codie(expr: &str) -> CodieAst {
+    if let Ok(n) = expr.parse::<u64>() {
+        return CodieAst::Literal(gently_codie::ast::CodieLiteral::Number(n as f64));
+    }
+    if expr == "true" || expr == "false" {
+        return CodieAst::Literal(gently_codie::ast::CodieLiteral::Bool(expr == "true"));
+    }
+    if let Some(inner) = expr.strip_prefix("b\"").and_then(|s| s.strip_suffix('"')) {
+        return CodieAst::Literal(gently_codie::ast::CodieLiteral::String(inner.to_string()));
+    }
+    CodieAst::Identifier(expr.to_string())
+}
+
 // ── Internal transpiler ─────────────────────────────────────────
 
 struct MoveTranspiler {
@@ -174,9 +400,16 @@ struct MoveTranspiler {
     structs: Vec<MoveStruct>,
     functions: Vec<MoveFunction>,
     dependencies: Vec<String>,
-    error_code_counter: u64,
+    /// Abort-code table, one entry per distinct constraint text seen so far
+    errors: Vec<MoveErrorCode>,
     /// Constraints collected from fence blocks, injected into the next function
     pending_constraints: Vec<String>,
+    /// `REQUIRES:` rules collected from fence blocks, injected into the next function's spec
+    pending_requires: Vec<String>,
+    /// `ENSURES:` rules collected from fence blocks, injected into the next function's spec
+    pending_ensures: Vec<String>,
+    /// `INVARIANT:` rules seen before any struct exists yet, attached to the next struct defined
+    pending_invariants: Vec<String>,
 }
 
 impl MoveTranspiler {
@@ -186,14 +419,52 @@ impl MoveTranspiler {
             structs: Vec::new(),
             functions: Vec::new(),
             dependencies: Vec::new(),
-            error_code_counter: 0,
+            errors: Vec::new(),
             pending_constraints: Vec::new(),
+            pending_requires: Vec::new(),
+            pending_ensures: Vec::new(),
+            pending_invariants: Vec::new(),
+        }
+    }
+
+    /// Look up (or assign) the stable `E_<NAME>` abort-code constant for a
+    /// constraint's condition text, registering it in the module's error
+    /// table the first time it's seen.
+    fn register_error(&mut self, rule: &str) -> String {
+        if let Some(existing) = self.errors.iter().find(|e| e.description == rule) {
+            return existing.name.clone();
+        }
+
+        let base_name = error_const_name(rule);
+        let mut name = base_name.clone();
+        let mut suffix = 2;
+        while self.errors.iter().any(|e| e.name == name) {
+            name = format!("{}_{}", base_name, suffix);
+            suffix += 1;
+        }
+
+        let code = stable_error_code(rule);
+        self.errors.push(MoveErrorCode {
+            name: name.clone(),
+            code,
+            description: rule.to_string(),
+        });
+        name
+    }
+
+    /// Attach a struct-level invariant to the most recently defined struct, or
+    /// stash it for the next struct defined if none exists yet.
+    fn attach_invariant(&mut self, condition: String) {
+        if let Some(last) = self.structs.last_mut() {
+            last.invariants.push(condition);
+        } else {
+            self.pending_invariants.push(condition);
         }
     }
 
-    fn next_error_code(&mut self) -> u64 {
-        self.error_code_counter += 1;
-        self.error_code_counter
+    /// Drain any invariants waiting for a struct onto the one just created.
+    fn claim_pending_invariants(&mut self, new_struct: &mut MoveStruct) {
+        new_struct.invariants.append(&mut self.pending_invariants);
     }
 
     fn add_dependency(&mut self, dep: &str) {
@@ -218,6 +489,7 @@ impl MoveTranspiler {
             structs: self.structs.clone(),
             functions: self.functions.clone(),
             dependencies: self.dependencies.clone(),
+            errors: self.errors.clone(),
         })
     }
 
@@ -248,25 +520,31 @@ impl MoveTranspiler {
                 // The rule string becomes the struct name.
                 // Fields come from sub-patterns or we use defaults.
                 let name = extract_struct_name(rule);
-                self.structs.push(MoveStruct {
+                let mut new_struct = MoveStruct {
                     name: to_pascal_case(&name),
                     abilities: vec![MoveAbility::Key, MoveAbility::Store],
                     fields: vec![
                         MoveField { name: "id".to_string(), type_name: "UID".to_string() },
                         MoveField { name: "value".to_string(), type_name: "u64".to_string() },
                     ],
-                });
+                    invariants: Vec::new(),
+                };
+                self.claim_pending_invariants(&mut new_struct);
+                self.structs.push(new_struct);
             }
 
             // blob → flexible struct (has key + store + drop — can be destroyed)
             CodieAst::Flexible { name, body } => {
                 let struct_name = name.as_deref().unwrap_or("Data");
                 let fields = self.extract_fields_from_body(body);
-                self.structs.push(MoveStruct {
+                let mut new_struct = MoveStruct {
                     name: to_pascal_case(struct_name),
                     abilities: vec![MoveAbility::Key, MoveAbility::Store, MoveAbility::Drop],
                     fields,
-                });
+                    invariants: Vec::new(),
+                };
+                self.claim_pending_invariants(&mut new_struct);
+                self.structs.push(new_struct);
             }
 
             // pin → public entry function (transaction entry point, PTB-callable)
@@ -296,7 +574,22 @@ impl MoveTranspiler {
             CodieAst::Constraint { rules } => {
                 for rule in rules {
                     if let CodieAst::Immutable { rule: text } = rule {
-                        self.pending_constraints.push(text.clone());
+                        match classify_spec_rule(text) {
+                            SpecClause::Requires(condition) => {
+                                self.pending_requires.push(condition.clone());
+                                self.pending_constraints.push(condition);
+                            }
+                            SpecClause::Ensures(condition) => {
+                                self.pending_ensures.push(condition.clone());
+                                self.pending_constraints.push(condition);
+                            }
+                            SpecClause::Invariant(condition) => {
+                                self.attach_invariant(condition);
+                            }
+                            SpecClause::Plain(text) => {
+                                self.pending_constraints.push(text);
+                            }
+                        }
                     }
                 }
             }
@@ -310,6 +603,7 @@ impl MoveTranspiler {
                     fields: vec![
                         MoveField { name: "hash".to_string(), type_name: "vector<u8>".to_string() },
                     ],
+                    invariants: Vec::new(),
                 });
             }
 
@@ -323,6 +617,8 @@ impl MoveTranspiler {
                     params: vec![],
                     return_type: None,
                     body: vec![MoveStatement::Comment(format!("TODO: {}", msg))],
+                    requires: Vec::new(),
+                    ensures: Vec::new(),
                 });
             }
 
@@ -378,6 +674,8 @@ impl MoveTranspiler {
                     }],
                     return_type: None,
                     body: stmts,
+                    requires: Vec::new(),
+                    ensures: Vec::new(),
                 });
             }
 
@@ -438,6 +736,8 @@ impl MoveTranspiler {
             params,
             return_type: None,
             body,
+            requires: Vec::new(),
+            ensures: Vec::new(),
         }
     }
 
@@ -483,6 +783,8 @@ impl MoveTranspiler {
             params: move_params,
             return_type,
             body: stmts,
+            requires: Vec::new(),
+            ensures: Vec::new(),
         }
     }
 
@@ -497,6 +799,8 @@ impl MoveTranspiler {
             params: vec![],
             return_type,
             body: vec![MoveStatement::Return(return_expr)],
+            requires: Vec::new(),
+            ensures: Vec::new(),
         }
     }
 
@@ -533,10 +837,11 @@ impl MoveTranspiler {
                     for rule in rules {
                         match rule {
                             CodieAst::Immutable { rule: text } => {
-                                let code = self.next_error_code();
+                                let condition = constraint_to_condition(text);
+                                let error_name = self.register_error(&condition);
                                 stmts.push(MoveStatement::Assert {
-                                    condition: constraint_to_condition(text),
-                                    error_code: code,
+                                    condition,
+                                    error_name,
                                 });
                             }
                             other => {
@@ -548,10 +853,11 @@ impl MoveTranspiler {
 
                 // bone inside function → assert! constraint
                 CodieAst::Immutable { rule } => {
-                    let code = self.next_error_code();
+                    let condition = constraint_to_condition(rule);
+                    let error_name = self.register_error(&condition);
                     stmts.push(MoveStatement::Assert {
-                        condition: constraint_to_condition(rule),
-                        error_code: code,
+                        condition,
+                        error_name,
                     });
                 }
 
@@ -739,19 +1045,21 @@ impl MoveTranspiler {
         }
     }
 
-    /// Inject pending constraints as assert! statements at the start of a function
+    /// Inject pending constraints as assert! statements at the start of a function,
+    /// and carry any `REQUIRES:`/`ENSURES:` rules onto the function's `spec` block.
     fn inject_constraints(&mut self, func: &mut MoveFunction) {
+        func.requires.append(&mut self.pending_requires);
+        func.ensures.append(&mut self.pending_ensures);
+
         if self.pending_constraints.is_empty() {
             return;
         }
         let constraints: Vec<String> = self.pending_constraints.drain(..).collect();
         let mut asserts: Vec<MoveStatement> = constraints.into_iter()
             .map(|rule| {
-                self.error_code_counter += 1;
-                MoveStatement::Assert {
-                    condition: constraint_to_condition(&rule),
-                    error_code: self.error_code_counter,
-                }
+                let condition = constraint_to_condition(&rule);
+                let error_name = self.register_error(&condition);
+                MoveStatement::Assert { condition, error_name }
             })
             .collect();
         // Prepend asserts before existing body
@@ -819,6 +1127,20 @@ impl MoveTranspiler {
 
         writeln!(out).unwrap();
 
+        // Abort-code table: one stable E_<NAME> constant per distinct fence
+        // constraint, named after the constraint text and numbered by hashing
+        // it so codes stay put across unrelated edits elsewhere in the source.
+        if !self.errors.is_empty() {
+            writeln!(out, "    // Abort codes:").unwrap();
+            for err in &self.errors {
+                writeln!(out, "    //   {} = {}", err.name, err.description).unwrap();
+            }
+            for err in &self.errors {
+                writeln!(out, "    const {}: u64 = {};", err.name, err.code).unwrap();
+            }
+            writeln!(out).unwrap();
+        }
+
         // Structs (bone = linear resource, blob = flexible)
         for s in &self.structs {
             let abilities: Vec<String> = s.abilities.iter().map(|a| a.to_string()).collect();
@@ -828,6 +1150,16 @@ impl MoveTranspiler {
             }
             writeln!(out, "    }}").unwrap();
             writeln!(out).unwrap();
+
+            // Move Prover invariants from fence's INVARIANT: rules
+            if !s.invariants.is_empty() {
+                writeln!(out, "    spec {} {{", s.name).unwrap();
+                for invariant in &s.invariants {
+                    writeln!(out, "        invariant {};", invariant).unwrap();
+                }
+                writeln!(out, "    }}").unwrap();
+                writeln!(out).unwrap();
+            }
         }
 
         // Functions
@@ -881,8 +1213,8 @@ impl MoveTranspiler {
                             writeln!(out, "        }});").unwrap();
                         }
                     }
-                    MoveStatement::Assert { condition, error_code } => {
-                        writeln!(out, "        assert!({}, {});", condition, error_code).unwrap();
+                    MoveStatement::Assert { condition, error_name } => {
+                        writeln!(out, "        assert!({}, {});", condition, error_name).unwrap();
                     }
                     MoveStatement::Raw(code) => {
                         writeln!(out, "        {}", code).unwrap();
@@ -898,6 +1230,19 @@ impl MoveTranspiler {
 
             writeln!(out, "    }}").unwrap();
             writeln!(out).unwrap();
+
+            // Move Prover contract from fence's REQUIRES:/ENSURES: rules
+            if !f.requires.is_empty() || !f.ensures.is_empty() {
+                writeln!(out, "    spec {} {{", f.name).unwrap();
+                for precondition in &f.requires {
+                    writeln!(out, "        requires {};", precondition).unwrap();
+                }
+                for postcondition in &f.ensures {
+                    writeln!(out, "        ensures {};", render_ensures_clause(postcondition)).unwrap();
+                }
+                writeln!(out, "    }}").unwrap();
+                writeln!(out).unwrap();
+            }
         }
 
         writeln!(out, "}}").unwrap();
@@ -1091,6 +1436,86 @@ fn constraint_to_condition(rule: &str) -> String {
     }
 }
 
+/// Derive a stable abort code for a constraint's condition text by hashing
+/// it, so re-running the transpiler keeps codes put across unrelated edits
+/// elsewhere in the source (rather than a sequential counter, which would
+/// renumber every downstream constraint whenever one is added or removed).
+fn stable_error_code(rule: &str) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    rule.hash(&mut hasher);
+    1000 + (hasher.finish() % 9000)
+}
+
+/// Derive an `E_<NAME>` Move constant identifier from a constraint's
+/// condition text, e.g. "amount > 0" → "E_AMOUNT_0".
+fn error_const_name(rule: &str) -> String {
+    let cleaned = rule
+        .strip_prefix("NOT:")
+        .or_else(|| rule.strip_prefix("not:"))
+        .or_else(|| rule.strip_prefix("NOT "))
+        .unwrap_or(rule)
+        .trim();
+
+    let name: String = cleaned
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_uppercase() } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .take(4)
+        .collect::<Vec<_>>()
+        .join("_");
+
+    if name.is_empty() {
+        "E_CONSTRAINT".to_string()
+    } else {
+        format!("E_{}", name)
+    }
+}
+
+/// A bone/fence rule reclassified as a Move Prover design-by-contract clause.
+/// Recognizes a `REQUIRES:`/`ENSURES:`/`INVARIANT:` prefix (case-insensitive);
+/// anything else is a plain rule that only ever produces a runtime `assert!`.
+enum SpecClause {
+    Requires(String),
+    Ensures(String),
+    Invariant(String),
+    Plain(String),
+}
+
+fn classify_spec_rule(rule: &str) -> SpecClause {
+    let trimmed = rule.trim();
+    if let Some(rest) = strip_prefix_ci(trimmed, "REQUIRES:") {
+        SpecClause::Requires(rest.trim().to_string())
+    } else if let Some(rest) = strip_prefix_ci(trimmed, "ENSURES:") {
+        SpecClause::Ensures(rest.trim().to_string())
+    } else if let Some(rest) = strip_prefix_ci(trimmed, "INVARIANT:") {
+        SpecClause::Invariant(rest.trim().to_string())
+    } else {
+        SpecClause::Plain(rule.to_string())
+    }
+}
+
+fn strip_prefix_ci<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    if s.len() >= prefix.len() && s.as_bytes()[..prefix.len()].eq_ignore_ascii_case(prefix.as_bytes()) {
+        Some(&s[prefix.len()..])
+    } else {
+        None
+    }
+}
+
+/// Render an `ENSURES:` rule as a Move Prover postcondition expression, mapping
+/// a reserved `__result` identifier (or a bare `result`) onto Move's `result`.
+/// A rule with no result reference is a pure value expression, wrapped as
+/// `result == <expr>`.
+fn render_ensures_clause(rule: &str) -> String {
+    let mapped = rule.replace("__result", "result");
+    if mapped.contains("result") {
+        mapped
+    } else {
+        format!("result == {}", mapped)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1135,6 +1560,7 @@ mod tests {
                 MoveField { name: "id".to_string(), type_name: "UID".to_string() },
                 MoveField { name: "owner".to_string(), type_name: "address".to_string() },
             ],
+            invariants: Vec::new(),
         };
         assert!(!bone_struct.abilities.contains(&MoveAbility::Copy));
         assert!(!bone_struct.abilities.contains(&MoveAbility::Drop));
@@ -1150,6 +1576,7 @@ mod tests {
                 MoveField { name: "id".to_string(), type_name: "UID".to_string() },
                 MoveField { name: "data".to_string(), type_name: "vector<u8>".to_string() },
             ],
+            invariants: Vec::new(),
         };
         assert!(blob_struct.abilities.contains(&MoveAbility::Drop));
         assert!(!blob_struct.abilities.contains(&MoveAbility::Copy));
@@ -1209,6 +1636,47 @@ mod tests {
         assert!(module.source.contains("fun validate"));
     }
 
+    #[test]
+    fn test_move_to_codie_round_trip_reaches_fixed_point() {
+        let ast = CodieAst::Program {
+            name: "LOGIN".to_string(),
+            hash: None,
+            body: vec![
+                CodieAst::Immutable { rule: "AuthToken".to_string() },
+                CodieAst::Function {
+                    name: "validate".to_string(),
+                    params: vec![("user".to_string(), Some(CodieType::Text))],
+                    body: vec![
+                        CodieAst::Return {
+                            value: Box::new(CodieAst::Literal(
+                                gently_codie::ast::CodieLiteral::Bool(true)
+                            )),
+                        },
+                    ],
+                    returns: Some(Box::new(CodieAst::Literal(
+                        gently_codie::ast::CodieLiteral::Bool(true)
+                    ))),
+                },
+            ],
+        };
+
+        let module1 = codie_to_move(&ast).unwrap();
+        let reformed1 = move_to_codie(&module1);
+        let module2 = codie_to_move(&reformed1).unwrap();
+
+        // The struct/function shape survives the round trip
+        assert_eq!(module2.structs.len(), 1);
+        assert_eq!(module2.structs[0].name, "AuthToken");
+        assert_eq!(module2.functions.len(), 1);
+        assert_eq!(module2.functions[0].name, "validate");
+
+        // codie -> move -> codie -> move is a fixed point: reforming again
+        // produces byte-identical Move source
+        let reformed2 = move_to_codie(&module2);
+        let module3 = codie_to_move(&reformed2).unwrap();
+        assert_eq!(module2.source, module3.source);
+    }
+
     #[test]
     fn test_transpile_with_constraints() {
         let ast = CodieAst::Program {
@@ -1239,6 +1707,93 @@ mod tests {
         assert!(has_assert, "fence constraints should inject assert! statements");
     }
 
+    #[test]
+    fn test_fence_constraints_get_stable_named_abort_codes() {
+        let build = || {
+            CodieAst::Program {
+                name: "SECURE".to_string(),
+                hash: None,
+                body: vec![
+                    CodieAst::Constraint {
+                        rules: vec![
+                            CodieAst::Immutable { rule: "amount > 0".to_string() },
+                            CodieAst::Immutable { rule: "amount < 1000".to_string() },
+                        ],
+                    },
+                    CodieAst::Specification {
+                        name: Some("mint".to_string()),
+                        fields: vec![
+                            ("amount".to_string(), CodieAst::Identifier("u64".to_string())),
+                        ],
+                    },
+                ],
+            }
+        };
+
+        let module1 = codie_to_move(&build()).unwrap();
+        let module2 = codie_to_move(&build()).unwrap();
+
+        // Two distinct constraints get two distinct, named abort codes.
+        assert_eq!(module1.errors.len(), 2);
+        assert_ne!(module1.errors[0].name, module1.errors[1].name);
+        assert_ne!(module1.errors[0].code, module1.errors[1].code);
+        assert!(module1.errors.iter().all(|e| e.name.starts_with("E_")));
+
+        // Re-running the transpiler on identical input yields identical codes
+        // (hashed from the constraint text, not a sequential counter).
+        assert_eq!(module1.errors, module2.errors);
+
+        // The rendered assert! references the named constant, and the module
+        // declares it alongside a human-readable mapping comment.
+        let amount_err = module1.errors.iter().find(|e| e.description == "amount > 0").unwrap();
+        assert!(module1.source.contains(&format!("assert!(amount > 0, {});", amount_err.name)));
+        assert!(module1.source.contains(&format!("const {}: u64 = {};", amount_err.name, amount_err.code)));
+        assert!(module1.source.contains("// Abort codes:"));
+    }
+
+    #[test]
+    fn test_fence_requires_ensures_emit_spec_block() {
+        let ast = CodieAst::Program {
+            name: "VAULT".to_string(),
+            hash: None,
+            body: vec![
+                CodieAst::Immutable { rule: "Vault".to_string() },
+                CodieAst::Constraint {
+                    rules: vec![
+                        CodieAst::Immutable { rule: "INVARIANT: value >= 0".to_string() },
+                        CodieAst::Immutable { rule: "REQUIRES: amount > 0".to_string() },
+                        CodieAst::Immutable { rule: "ENSURES: __result == amount".to_string() },
+                    ],
+                },
+                CodieAst::Specification {
+                    name: Some("deposit".to_string()),
+                    fields: vec![
+                        ("amount".to_string(), CodieAst::Identifier("u64".to_string())),
+                    ],
+                },
+            ],
+        };
+
+        let module = codie_to_move(&ast).unwrap();
+
+        // The struct gets its invariant (no per-function assert — it's struct-wide)
+        assert_eq!(module.structs[0].name, "Vault");
+        assert_eq!(module.structs[0].invariants, vec!["value >= 0".to_string()]);
+        assert!(module.source.contains("spec Vault {"));
+        assert!(module.source.contains("invariant value >= 0;"));
+
+        // The function carries both requires and ensures through to its spec block
+        let func = &module.functions[0];
+        assert_eq!(func.requires, vec!["amount > 0".to_string()]);
+        assert_eq!(func.ensures, vec!["__result == amount".to_string()]);
+        let has_assert = func.body.iter().any(|s| matches!(s, MoveStatement::Assert { .. }));
+        assert!(has_assert, "requires/ensures should still assert! at runtime");
+
+        assert!(module.source.contains("spec deposit {"));
+        assert!(module.source.contains("requires amount > 0;"));
+        assert!(module.source.contains("ensures result == amount;"));
+    }
+
     #[test]
     fn test_transpile_blob_struct() {
         let ast = CodieAst::Program {
@@ -0,0 +1,104 @@
+//! Fixture-driven conformance harness for the CODIE → Move transpiler.
+//!
+//! Drop a `name.codie` + `name.move` pair into `tests/fixtures/` and this
+//! test picks it up automatically — no Rust changes needed. A fixture name
+//! listed in `tests/fixtures/test_ignore.txt` still runs (and still prints
+//! its diff) but won't fail the suite, for known-failing cases awaiting a
+//! real fix. Run with `BLESS=1 cargo test -p gently-chain --test conformance`
+//! to rewrite every golden `.move` file from the current transpiler output.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use gently_chain::source_to_move;
+
+const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+const IGNORE_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/test_ignore.txt");
+
+#[test]
+fn conformance() {
+    let bless = std::env::var("BLESS").map(|v| v == "1").unwrap_or(false);
+    let ignored = load_ignore_list();
+
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(FIXTURES_DIR)
+        .expect("tests/fixtures directory should exist")
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("codie"))
+        .collect();
+    fixtures.sort();
+
+    assert!(!fixtures.is_empty(), "tests/fixtures has no .codie golden pairs");
+
+    let mut failures = Vec::new();
+
+    for codie_path in fixtures {
+        let name = codie_path.file_stem().unwrap().to_string_lossy().to_string();
+        let move_path = codie_path.with_extension("move");
+
+        let source = fs::read_to_string(&codie_path)
+            .unwrap_or_else(|e| panic!("reading {}: {}", codie_path.display(), e));
+        let rendered = match source_to_move(&source) {
+            Ok(module) => module.source,
+            Err(e) => format!("<transpile error: {}>", e),
+        };
+
+        if bless {
+            fs::write(&move_path, &rendered)
+                .unwrap_or_else(|e| panic!("writing {}: {}", move_path.display(), e));
+            continue;
+        }
+
+        let expected = fs::read_to_string(&move_path)
+            .unwrap_or_else(|e| panic!("reading {}: {}", move_path.display(), e));
+
+        if rendered != expected {
+            let diff = line_diff(&expected, &rendered);
+            if ignored.contains(&name) {
+                eprintln!("(ignored) fixture '{}' does not conform:\n{}", name, diff);
+            } else {
+                failures.push(format!("fixture '{}' does not conform:\n{}", name, diff));
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!(
+            "{} conformance fixture(s) failed (rerun with BLESS=1 to accept new output if intentional):\n\n{}",
+            failures.len(),
+            failures.join("\n\n")
+        );
+    }
+}
+
+fn load_ignore_list() -> HashSet<String> {
+    fs::read_to_string(IGNORE_FILE)
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// A readable line-by-line diff between golden and rendered output.
+fn line_diff(expected: &str, actual: &str) -> String {
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+    let max = expected_lines.len().max(actual_lines.len());
+
+    let mut out = String::new();
+    for i in 0..max {
+        let e = expected_lines.get(i).copied();
+        let a = actual_lines.get(i).copied();
+        if e != a {
+            if let Some(e) = e {
+                out.push_str(&format!("  - {}\n", e));
+            }
+            if let Some(a) = a {
+                out.push_str(&format!("  + {}\n", a));
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,278 @@
+//! Ledger
+//!
+//! Tamper-evident audit ledger for completed gateway responses. Each record's
+//! `chain_hash` is `SHA256(prev_chain_hash + prompt_hash + response_hash)`,
+//! chaining back to a fixed genesis seed, so `verify()` can walk the ledger
+//! and point at the first record whose link doesn't reproduce - proof the
+//! ledger wasn't reordered, edited, or had a record dropped in the middle.
+//!
+//! This is a deliberately *separate* chain from `AuditLog`'s: `AuditLog`
+//! chains every internal event (request received, routed, rejected, ...)
+//! using `compute_chain_hash`, so consecutive links depend on event types a
+//! ledger consumer never sees; `Ledger` only ever sees completed responses
+//! and needs a chain a client holding nothing but a sequence of
+//! `GatewayResponse`s can walk on its own. Flattening them into one chain
+//! would mean Ledger verification requires the full interleaved audit
+//! trail, defeating that purpose. Each record still carries `audit_root` -
+//! the `GatewayResponse.chain_hash` computed from `AuditLog::last_hash()` at
+//! append time - so the two chains can be cross-checked: `audit_root`
+//! reproducing from the audit log's own export proves this ledger entry
+//! corresponds to a response the audit log actually processed, not just a
+//! plausible-looking standalone forgery.
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit::{AuditEvent, AuditLog};
+use crate::hash_chain;
+use crate::types::GatewayResponse;
+
+/// Fixed seed the first record's `chain_hash` is computed from.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// One appended ledger entry. Stores `prompt_hash`/`response_hash` alongside
+/// the resulting `chain_hash` so verification never needs to re-derive them
+/// from the original request/response bodies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerRecord {
+    pub request_id: String,
+    pub prompt_hash: Option<String>,
+    pub response_hash: Option<String>,
+    pub chain_hash: String,
+    /// `response.chain_hash` at append time, i.e. the `AuditLog` chain tip
+    /// this record was produced against. Lets a verifier cross-check this
+    /// ledger's self-contained chain against the audit log's own export
+    /// instead of trusting either one in isolation.
+    pub audit_root: String,
+}
+
+/// Insertion-ordered, append-only ledger of chained records.
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+    records: Vec<LedgerRecord>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Self { records: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn records(&self) -> &[LedgerRecord] {
+        &self.records
+    }
+
+    /// Latest chain hash, i.e. the root a client can cheaply attest the
+    /// whole ledger against. `GENESIS_HASH` if nothing has been appended yet.
+    pub fn root(&self) -> String {
+        self.records
+            .last()
+            .map(|r| r.chain_hash.clone())
+            .unwrap_or_else(|| GENESIS_HASH.to_string())
+    }
+
+    /// Append `response` as the next record, chaining off the current root.
+    /// Missing `prompt_hash`/`response_hash` are treated as empty strings in
+    /// the concatenation, consistently between append and verify. Returns
+    /// the new chain hash (the new root).
+    pub fn append(&mut self, response: &GatewayResponse) -> String {
+        let prev = self.root();
+        let chain_hash = hash_chain(
+            &prev,
+            response.prompt_hash.as_deref().unwrap_or(""),
+            response.response_hash.as_deref().unwrap_or(""),
+        );
+
+        self.records.push(LedgerRecord {
+            request_id: response.request_id.clone(),
+            prompt_hash: response.prompt_hash.clone(),
+            response_hash: response.response_hash.clone(),
+            chain_hash: chain_hash.clone(),
+            audit_root: response.chain_hash.clone().unwrap_or_default(),
+        });
+
+        chain_hash
+    }
+
+    /// Walk the whole ledger recomputing each `chain_hash` from
+    /// `prev + prompt_hash + response_hash`. Returns the index of the first
+    /// record whose stored `chain_hash` doesn't match the recomputed one, or
+    /// `Ok(())` if every link is intact.
+    pub fn verify(&self) -> Result<(), usize> {
+        self.verify_range(0, self.records.len())
+    }
+
+    /// Same as `verify`, but limited to `[start, end)`. `prev` for the first
+    /// record in the range is still taken from the record immediately
+    /// before it (or `GENESIS_HASH` if `start == 0`), so a partial check
+    /// still validates that the range links correctly into the rest of the
+    /// ledger.
+    pub fn verify_range(&self, start: usize, end: usize) -> Result<(), usize> {
+        let end = end.min(self.records.len());
+        let mut prev = if start == 0 {
+            GENESIS_HASH.to_string()
+        } else {
+            self.records[start - 1].chain_hash.clone()
+        };
+
+        for index in start..end {
+            let record = &self.records[index];
+            let expected = hash_chain(
+                &prev,
+                record.prompt_hash.as_deref().unwrap_or(""),
+                record.response_hash.as_deref().unwrap_or(""),
+            );
+            if expected != record.chain_hash {
+                return Err(index);
+            }
+            prev = record.chain_hash.clone();
+        }
+
+        Ok(())
+    }
+
+    /// Cross-check every record's `audit_root` against `audit`'s own export:
+    /// for each record, `audit` must contain a `ResponseSent` event for the
+    /// same `request_id` whose `chain_hash` field reproduces `audit_root`
+    /// exactly. This is what backs the claim that a ledger record
+    /// corresponds to a response the audit log actually processed, rather
+    /// than a standalone forgery with a plausible-looking `audit_root`.
+    /// Returns the index of the first record that doesn't reconcile, or
+    /// `Ok(())` if every record's `audit_root` is backed by the audit log.
+    pub fn verify_against_audit(&self, audit: &AuditLog) -> Result<(), usize> {
+        for (index, record) in self.records.iter().enumerate() {
+            let reconciles = audit.all_events().any(|entry| {
+                matches!(
+                    &entry.event,
+                    AuditEvent::ResponseSent { request_id, chain_hash, .. }
+                        if *request_id == record.request_id && *chain_hash == record.audit_root
+                )
+            });
+            if !reconciles {
+                return Err(index);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(request_id: &str, prompt_hash: &str, response_hash: &str) -> GatewayResponse {
+        let mut r = GatewayResponse::new(request_id, "content");
+        r.prompt_hash = Some(prompt_hash.to_string());
+        r.response_hash = Some(response_hash.to_string());
+        r.chain_hash = Some(format!("audit-tip-for-{request_id}"));
+        r
+    }
+
+    #[test]
+    fn append_records_the_audit_chain_tip_alongside_its_own_chain() {
+        let mut ledger = Ledger::new();
+        let resp = response("r1", "ph1", "rh1");
+        ledger.append(&resp);
+        assert_eq!(ledger.records()[0].audit_root, resp.chain_hash.unwrap());
+    }
+
+    #[test]
+    fn missing_audit_chain_tip_is_treated_as_empty() {
+        let mut ledger = Ledger::new();
+        let resp = GatewayResponse::new("r1", "content");
+        assert!(resp.chain_hash.is_none());
+        ledger.append(&resp);
+        assert_eq!(ledger.records()[0].audit_root, "");
+    }
+
+    #[test]
+    fn append_chains_off_genesis() {
+        let mut ledger = Ledger::new();
+        let first_root = ledger.append(&response("r1", "ph1", "rh1"));
+        assert_eq!(first_root, hash_chain(GENESIS_HASH, "ph1", "rh1"));
+        assert_eq!(ledger.root(), first_root);
+    }
+
+    #[test]
+    fn verify_detects_tampering() {
+        let mut ledger = Ledger::new();
+        ledger.append(&response("r1", "ph1", "rh1"));
+        ledger.append(&response("r2", "ph2", "rh2"));
+        assert_eq!(ledger.verify(), Ok(()));
+
+        ledger.records[0].response_hash = Some("tampered".to_string());
+        assert_eq!(ledger.verify(), Err(0));
+    }
+
+    #[test]
+    fn missing_hashes_are_treated_as_empty() {
+        let mut ledger = Ledger::new();
+        let response = GatewayResponse::new("r1", "content");
+        let root = ledger.append(&response);
+        assert_eq!(root, hash_chain(GENESIS_HASH, "", ""));
+        assert_eq!(ledger.verify(), Ok(()));
+    }
+
+    /// Builds a response/audit pair the way `Gateway::process` does: hash the
+    /// audit log's current tip into `response.chain_hash`, then log a
+    /// `ResponseSent` event carrying that same `chain_hash`.
+    fn response_with_audit_entry(audit: &mut AuditLog, request_id: &str, prompt_hash: &str, response_hash: &str) -> GatewayResponse {
+        let mut r = response(request_id, prompt_hash, response_hash);
+        let prev_hash = audit.last_hash().unwrap_or_default();
+        r.chain_hash = Some(hash_chain(&prev_hash, prompt_hash, response_hash));
+
+        audit.log(AuditEvent::ResponseSent {
+            request_id: request_id.to_string(),
+            response_hash: response_hash.to_string(),
+            chain_hash: r.chain_hash.clone().unwrap_or_default(),
+            tokens_used: 0,
+        });
+
+        r
+    }
+
+    #[test]
+    fn verify_against_audit_passes_when_audit_root_reconciles() {
+        let mut audit = AuditLog::new();
+        let mut ledger = Ledger::new();
+
+        let r1 = response_with_audit_entry(&mut audit, "r1", "ph1", "rh1");
+        ledger.append(&r1);
+        let r2 = response_with_audit_entry(&mut audit, "r2", "ph2", "rh2");
+        ledger.append(&r2);
+
+        assert_eq!(ledger.verify_against_audit(&audit), Ok(()));
+    }
+
+    #[test]
+    fn verify_against_audit_catches_a_forged_audit_root() {
+        let mut audit = AuditLog::new();
+        let mut ledger = Ledger::new();
+
+        let r1 = response_with_audit_entry(&mut audit, "r1", "ph1", "rh1");
+        ledger.append(&r1);
+
+        ledger.records[0].audit_root = "forged".to_string();
+        assert_eq!(ledger.verify_against_audit(&audit), Err(0));
+    }
+
+    #[test]
+    fn verify_range_checks_a_window() {
+        let mut ledger = Ledger::new();
+        ledger.append(&response("r1", "ph1", "rh1"));
+        ledger.append(&response("r2", "ph2", "rh2"));
+        ledger.append(&response("r3", "ph3", "rh3"));
+
+        assert_eq!(ledger.verify_range(1, 3), Ok(()));
+
+        ledger.records[2].response_hash = Some("tampered".to_string());
+        assert_eq!(ledger.verify_range(1, 3), Err(2));
+        assert_eq!(ledger.verify_range(0, 1), Ok(()));
+    }
+}
@@ -31,6 +31,9 @@ pub mod router;
 pub mod filter;
 pub mod audit;
 pub mod session;
+pub mod telemetry;
+pub mod ledger;
+pub mod sse;
 
 pub use types::*;
 pub use provider::{Provider, ProviderType, ProviderStatus};
@@ -38,6 +41,9 @@ pub use router::{Router, RoutingStrategy, RouteDecision};
 pub use filter::{InputFilter, OutputFilter, FilterResult, FafoFilter, AuthFilter, ContentFilter, RateLimitFilter};
 pub use audit::{AuditLog, AuditEntry, AuditEvent};
 pub use session::{Session, SessionState, SessionManager};
+pub use telemetry::{init_otlp, RequestSpan};
+pub use ledger::{Ledger, LedgerRecord};
+pub use sse::{stream_handler, stream_response, SharedGateway};
 
 use thiserror::Error;
 use sha2::{Sha256, Digest};
@@ -82,6 +88,8 @@ pub struct Gateway {
     sessions: SessionManager,
     /// Gateway metrics
     metrics: GatewayMetrics,
+    /// Tamper-evident ledger of completed responses
+    ledger: Ledger,
 }
 
 impl Gateway {
@@ -94,6 +102,7 @@ impl Gateway {
             audit: AuditLog::new(),
             sessions: SessionManager::new(),
             metrics: GatewayMetrics::default(),
+            ledger: Ledger::new(),
         }
     }
 
@@ -104,6 +113,8 @@ impl Gateway {
 
     /// Process a request through the gateway
     pub async fn process(&mut self, mut request: GatewayRequest) -> Result<GatewayResponse> {
+        let otel_span = telemetry::RequestSpan::start(&request);
+
         // 1. Hash the incoming request
         request.prompt_hash = Some(hash_content(&request.prompt));
 
@@ -116,6 +127,7 @@ impl Gateway {
                         request_id: request.id.clone(),
                         reason: reason.clone(),
                     });
+                    otel_span.finish_err(&reason);
                     return Err(GatewayError::Rejected(reason));
                 }
                 FilterResult::Modify(modified) => request = modified,
@@ -130,18 +142,36 @@ impl Gateway {
         });
 
         // 4. Route to appropriate provider (local-first)
-        let route = self.router.route(&request)?;
+        let route = match self.router.route(&request) {
+            Ok(route) => route,
+            Err(e) => {
+                otel_span.finish_err(&e.to_string());
+                return Err(e);
+            }
+        };
 
         self.audit.log(AuditEvent::RequestRouted {
             request_id: request.id.clone(),
             provider: route.provider.to_string(),
         });
 
-        // 5. Execute request
-        let mut response = route.provider_instance.complete(&request).await?;
+        // 5. Execute request, wrapped in a provider-dispatch span
+        let provider_span = otel_span.provider_span(&route.provider.to_string());
+        let mut response = match route.provider_instance.complete(&request).await {
+            Ok(response) => {
+                provider_span.finish_ok();
+                response
+            }
+            Err(e) => {
+                provider_span.finish_err(&e.to_string());
+                otel_span.finish_err(&e.to_string());
+                return Err(e);
+            }
+        };
 
         // 6. Hash the response
         response.response_hash = Some(hash_content(&response.content));
+        response.prompt_hash = request.prompt_hash.clone();
 
         // 7. Compute chain hash
         if let (Some(prompt_hash), Some(response_hash)) = (&request.prompt_hash, &response.response_hash) {
@@ -149,6 +179,18 @@ impl Gateway {
             response.chain_hash = Some(hash_chain(&prev_hash, prompt_hash, response_hash));
         }
 
+        // Append to the tamper-evident ledger - this is what `Gateway::ledger()`
+        // exposes for clients to verify/attest to without needing the audit
+        // log's internals. Each record still carries `audit_root`, the
+        // `response.chain_hash` just computed above, so the two chains can be
+        // cross-checked against each other; see `ledger` module docs.
+        self.ledger.append(&response);
+
+        // Wrap any tool calls the provider made in their own spans
+        for call in &response.tool_calls {
+            otel_span.tool_call_span(call).finish_ok();
+        }
+
         // 8. Run output filters (metrics, audit, transformation)
         for filter in &self.output_filters {
             match filter.filter(&request, &response) {
@@ -158,6 +200,7 @@ impl Gateway {
                         request_id: request.id.clone(),
                         reason: reason.clone(),
                     });
+                    otel_span.finish_err(&reason);
                     return Err(GatewayError::Rejected(reason));
                 }
                 FilterResult::Modify(modified) => {
@@ -175,6 +218,8 @@ impl Gateway {
             tokens_used: response.tokens_used,
         });
 
+        otel_span.finish_ok(&response);
+
         // 10. Update metrics
         self.metrics.requests_total += 1;
         self.metrics.tokens_total += response.tokens_used;
@@ -192,6 +237,11 @@ impl Gateway {
         &self.audit
     }
 
+    /// Get the tamper-evident response ledger
+    pub fn ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
     /// Get session manager
     pub fn sessions(&self) -> &SessionManager {
         &self.sessions
@@ -206,6 +256,103 @@ impl Gateway {
     pub fn add_output_filter(&mut self, filter: Box<dyn OutputFilter + Send + Sync>) {
         self.output_filters.push(filter);
     }
+
+    /// First half of a streamed request: run input filters, audit the
+    /// request, and route it, the same as `process` does before dispatch.
+    /// Returns the (possibly filter-modified) request plus the routed
+    /// provider, so the caller can stream `Provider::complete_stream`
+    /// without holding `&mut Gateway` for the whole stream's lifetime.
+    pub fn prepare_stream(&mut self, mut request: GatewayRequest) -> Result<(GatewayRequest, std::sync::Arc<dyn Provider>)> {
+        request.prompt_hash = Some(hash_content(&request.prompt));
+
+        for filter in &self.input_filters {
+            match filter.filter(&request) {
+                FilterResult::Pass => continue,
+                FilterResult::Reject(reason) => {
+                    self.audit.log(AuditEvent::RequestRejected {
+                        request_id: request.id.clone(),
+                        reason: reason.clone(),
+                    });
+                    return Err(GatewayError::Rejected(reason));
+                }
+                FilterResult::Modify(modified) => request = modified,
+            }
+        }
+
+        self.audit.log(AuditEvent::RequestReceived {
+            request_id: request.id.clone(),
+            prompt_hash: request.prompt_hash.clone().unwrap_or_default(),
+            session_id: request.session_id.clone(),
+        });
+
+        let route = self.router.route(&request)?;
+        self.audit.log(AuditEvent::RequestRouted {
+            request_id: request.id.clone(),
+            provider: route.provider.to_string(),
+        });
+
+        Ok((request, route.provider_instance))
+    }
+
+    /// Second half of a streamed request: once the caller has assembled the
+    /// full content from the stream's deltas, compute `response_hash`/
+    /// `chain_hash` over it exactly as `process` would, append it to the
+    /// ledger, run it through `output_filters`, and audit/account for it.
+    ///
+    /// By the time this runs, every chunk has already been flushed to the
+    /// client, so a `Reject` here can't stop delivery the way it can in
+    /// `process` - but it still logs `ResponseRejected` and refuses to
+    /// finish the response as if nothing happened, instead of silently
+    /// skipping enforcement for streamed traffic the way this used to.
+    pub fn finish_stream(
+        &mut self,
+        request: &GatewayRequest,
+        content: String,
+        tokens_used: usize,
+        latency_ms: u64,
+    ) -> Result<GatewayResponse> {
+        let mut response = GatewayResponse::new(request.id.clone(), content);
+        response.response_hash = Some(hash_content(&response.content));
+        response.prompt_hash = request.prompt_hash.clone();
+        response.tokens_used = tokens_used;
+        response.latency_ms = latency_ms;
+
+        if let (Some(prompt_hash), Some(response_hash)) = (&request.prompt_hash, &response.response_hash) {
+            let prev_hash = self.audit.last_hash().unwrap_or_default();
+            response.chain_hash = Some(hash_chain(&prev_hash, prompt_hash, response_hash));
+        }
+
+        self.ledger.append(&response);
+
+        for filter in &self.output_filters {
+            match filter.filter(request, &response) {
+                FilterResult::Pass => continue,
+                FilterResult::Reject(reason) => {
+                    self.audit.log(AuditEvent::ResponseRejected {
+                        request_id: request.id.clone(),
+                        reason: reason.clone(),
+                    });
+                    return Err(GatewayError::Rejected(reason));
+                }
+                FilterResult::Modify(_) => {
+                    // Output filters can't modify a streamed response - its
+                    // content already reached the client chunk by chunk.
+                }
+            }
+        }
+
+        self.audit.log(AuditEvent::ResponseSent {
+            request_id: request.id.clone(),
+            response_hash: response.response_hash.clone().unwrap_or_default(),
+            chain_hash: response.chain_hash.clone().unwrap_or_default(),
+            tokens_used: response.tokens_used,
+        });
+
+        self.metrics.requests_total += 1;
+        self.metrics.tokens_total += response.tokens_used;
+
+        Ok(response)
+    }
 }
 
 impl Default for Gateway {
@@ -283,6 +430,7 @@ impl GatewayBuilder {
             audit: AuditLog::new(),
             sessions: SessionManager::new(),
             metrics: GatewayMetrics::default(),
+            ledger: Ledger::new(),
         }
     }
 }
@@ -7,11 +7,16 @@
 //! 2. Embedder (local ONNX) - Primary for embeddings
 //! 3. External APIs - For customer happiness only
 
+use crate::types::StreamChunk;
 use crate::{GatewayRequest, GatewayResponse, Result, GatewayError};
 use async_trait::async_trait;
+use futures::stream::{self, BoxStream};
 use std::fmt;
 use std::time::Instant;
 
+/// Stream of incremental response chunks, as produced by `Provider::complete_stream`.
+pub type ChunkStream = BoxStream<'static, Result<StreamChunk>>;
+
 /// Provider trait - All AI providers implement this
 #[async_trait]
 pub trait Provider: Send + Sync {
@@ -27,6 +32,27 @@ pub trait Provider: Send + Sync {
     /// Complete a request
     async fn complete(&self, request: &GatewayRequest) -> Result<GatewayResponse>;
 
+    /// Stream a request token-by-token. Providers that support real
+    /// streaming (`capabilities().streaming`) should override this; the
+    /// default falls back to `complete` and replays the full content as a
+    /// single delta followed by the terminal chunk, so every provider is
+    /// usable from the same streaming call site even without true streaming.
+    async fn complete_stream(&self, request: &GatewayRequest) -> Result<ChunkStream> {
+        let started = Instant::now();
+        let response = self.complete(request).await?;
+        let request_id = response.request_id.clone();
+        let chunks = vec![
+            Ok(StreamChunk::delta(request_id.clone(), response.content)),
+            Ok(StreamChunk::finish(
+                request_id,
+                "stop",
+                response.tokens_used,
+                started.elapsed().as_millis() as u64,
+            )),
+        ];
+        Ok(Box::pin(stream::iter(chunks)))
+    }
+
     /// Get provider capabilities
     fn capabilities(&self) -> ProviderCapabilities;
 
@@ -0,0 +1,107 @@
+//! Server-Sent Events streaming endpoint
+//!
+//! Exposes `Provider::complete_stream` over axum as a `text/event-stream`
+//! response: each `StreamChunk` is flushed to the client as soon as the
+//! provider produces it, while `response_hash`/`chain_hash` are still
+//! computed over the fully assembled content once the terminal chunk
+//! arrives, via `Gateway::finish_stream`.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_stream::stream;
+use axum::extract::State;
+use axum::response::sse::{Event, Sse};
+use axum::response::IntoResponse;
+use axum::Json;
+use futures::stream::{Stream, StreamExt};
+use tokio::sync::Mutex;
+
+use crate::types::{GatewayRequest, StreamChunk};
+use crate::Gateway;
+
+/// Shared gateway handle axum routes are mounted against.
+pub type SharedGateway = Arc<Mutex<Gateway>>;
+
+/// `POST /stream` - stream a gateway response as Server-Sent Events.
+pub async fn stream_handler(
+    State(gateway): State<SharedGateway>,
+    Json(request): Json<GatewayRequest>,
+) -> impl IntoResponse {
+    Sse::new(stream_response(gateway, request))
+}
+
+/// Build the SSE event stream for `request`: route it, forward every
+/// `StreamChunk` the provider emits, and finish the gateway's bookkeeping
+/// once the terminal chunk (the one carrying `finish_reason`) is seen.
+pub fn stream_response(
+    gateway: SharedGateway,
+    request: GatewayRequest,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    stream! {
+        let started = Instant::now();
+        let request_id = request.id.clone();
+
+        let prepared = {
+            let mut gw = gateway.lock().await;
+            gw.prepare_stream(request)
+        };
+
+        let (request, provider) = match prepared {
+            Ok(pair) => pair,
+            Err(e) => {
+                yield error_event(&request_id, &e.to_string(), started.elapsed().as_millis() as u64);
+                return;
+            }
+        };
+
+        let mut provider_stream = match provider.complete_stream(&request).await {
+            Ok(s) => s,
+            Err(e) => {
+                yield error_event(&request.id, &e.to_string(), started.elapsed().as_millis() as u64);
+                return;
+            }
+        };
+
+        let mut full_content = String::new();
+        let mut final_tokens = 0usize;
+
+        while let Some(chunk) = provider_stream.next().await {
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    yield error_event(&request.id, &e.to_string(), started.elapsed().as_millis() as u64);
+                    return;
+                }
+            };
+
+            full_content.push_str(&chunk.delta);
+            let terminal = chunk.is_terminal();
+            if terminal {
+                final_tokens = chunk.tokens_used;
+            }
+
+            yield Ok(chunk_event(&chunk));
+
+            if terminal {
+                break;
+            }
+        }
+
+        let latency_ms = started.elapsed().as_millis() as u64;
+        let mut gw = gateway.lock().await;
+        if let Err(e) = gw.finish_stream(&request, full_content, final_tokens, latency_ms) {
+            yield error_event(&request.id, &e.to_string(), latency_ms);
+        }
+    }
+}
+
+fn chunk_event(chunk: &StreamChunk) -> Event {
+    Event::default().json_data(chunk).unwrap_or_else(|_| Event::default().data("{}"))
+}
+
+fn error_event(request_id: &str, error: &str, latency_ms: u64) -> Result<Event, Infallible> {
+    let chunk = StreamChunk::finish(request_id.to_string(), format!("error: {}", error), 0, latency_ms);
+    Ok(chunk_event(&chunk))
+}
@@ -0,0 +1,217 @@
+//! Telemetry
+//!
+//! OpenTelemetry instrumentation for the gateway's request/response
+//! lifecycle: one pipeline shared by traces and metrics instead of ad-hoc
+//! logging bolted onto `Gateway::process`; logs are not exported. Every
+//! request opens a span keyed by `id`/`session_id`, and the same call records
+//! routing/token counters and a latency histogram. Provider dispatch and tool
+//! calls open spans of their own; these start from the current OTEL context,
+//! not from the request span, so they are not linked to it as children. The
+//! exporter is pluggable: by default everything is a no-op (the global OTEL
+//! providers stay unset), and `init_otlp` ships traces and metrics to any
+//! OTLP-compatible backend over gRPC.
+
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use opentelemetry::metrics::{Counter, Histogram};
+use opentelemetry::trace::{Span as OtelSpan, Status, Tracer};
+use opentelemetry::{global, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+
+use crate::types::{GatewayRequest, GatewayResponse, ProviderPreference, TaskType, ToolCall};
+
+const INSTRUMENTATION_NAME: &str = "gently-gateway";
+
+/// Gateway-wide metric instruments, built once against whatever global
+/// `MeterProvider` is installed (a no-op meter if nothing was configured).
+struct Instruments {
+    requests_total: Counter<u64>,
+    tokens_total: Counter<u64>,
+    latency_ms: Histogram<f64>,
+}
+
+static INSTRUMENTS: OnceLock<Instruments> = OnceLock::new();
+
+fn instruments() -> &'static Instruments {
+    INSTRUMENTS.get_or_init(|| {
+        let meter = global::meter(INSTRUMENTATION_NAME);
+        Instruments {
+            requests_total: meter.u64_counter("gateway.requests_total").build(),
+            tokens_total: meter.u64_counter("gateway.tokens_total").build(),
+            latency_ms: meter.f64_histogram("gateway.latency_ms").build(),
+        }
+    })
+}
+
+/// Install an OTLP pipeline (traces + metrics) shipping to `endpoint`, e.g.
+/// `"http://localhost:4317"`. Leaves the no-op default in place on failure
+/// rather than panicking, so a misconfigured/unreachable collector never
+/// takes down the gateway.
+pub fn init_otlp(endpoint: &str) -> Result<(), String> {
+    let span_exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()
+        .map_err(|e| format!("failed to build OTLP span exporter: {}", e))?;
+    let tracer_provider = opentelemetry_sdk::trace::TracerProvider::builder()
+        .with_batch_exporter(span_exporter, opentelemetry_sdk::runtime::Tokio)
+        .build();
+    global::set_tracer_provider(tracer_provider);
+
+    let metric_exporter = opentelemetry_otlp::MetricExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()
+        .map_err(|e| format!("failed to build OTLP metric exporter: {}", e))?;
+    let meter_provider = opentelemetry_sdk::metrics::SdkMeterProvider::builder()
+        .with_periodic_exporter(metric_exporter)
+        .build();
+    global::set_meter_provider(meter_provider);
+
+    Ok(())
+}
+
+/// A request's root span plus the start time used to compute `latency_ms`.
+/// One of these is opened per `Gateway::process` call and closed (via
+/// `finish_ok` or `finish_err`) once the response (or error) is known.
+pub struct RequestSpan {
+    span: global::BoxedSpan,
+    started: Instant,
+}
+
+impl RequestSpan {
+    /// Open the root span for `request`, tagged with its routing-relevant
+    /// attributes up front (`task_type`, preferred provider if any).
+    pub fn start(request: &GatewayRequest) -> Self {
+        let tracer = global::tracer(INSTRUMENTATION_NAME);
+        let mut span = tracer.start("gateway.process");
+        span.set_attribute(KeyValue::new("request.id", request.id.clone()));
+        if let Some(session_id) = &request.session_id {
+            span.set_attribute(KeyValue::new("session.id", session_id.clone()));
+        }
+        span.set_attribute(KeyValue::new("task_type", task_type_label(request.task_type)));
+        if let Some(pref) = &request.preferred_provider {
+            span.set_attribute(KeyValue::new("preferred_provider", provider_preference_label(pref)));
+        }
+
+        instruments().requests_total.add(
+            1,
+            &[
+                KeyValue::new("task_type", task_type_label(request.task_type)),
+                KeyValue::new(
+                    "provider_preference",
+                    request
+                        .preferred_provider
+                        .as_ref()
+                        .map(provider_preference_label)
+                        .unwrap_or("unset"),
+                ),
+            ],
+        );
+
+        Self { span, started: Instant::now() }
+    }
+
+    /// Open a span wrapping provider dispatch (not parented to this span).
+    pub fn provider_span(&self, provider: &str) -> ChildSpan {
+        ChildSpan::start("gateway.provider_dispatch", &[KeyValue::new("provider", provider.to_string())])
+    }
+
+    /// Open a span wrapping execution of a single tool call (not parented to
+    /// this span).
+    pub fn tool_call_span(&self, call: &ToolCall) -> ChildSpan {
+        ChildSpan::start(
+            "gateway.tool_call",
+            &[
+                KeyValue::new("tool_call.id", call.id.clone()),
+                KeyValue::new("tool_call.name", call.name.clone()),
+            ],
+        )
+    }
+
+    /// Record the resolved provider/model/token counts on the root span,
+    /// record the token and latency metrics, and close the span as success.
+    pub fn finish_ok(mut self, response: &GatewayResponse) {
+        let latency_ms = self.started.elapsed().as_millis() as f64;
+
+        self.span.set_attribute(KeyValue::new("provider", response.provider.clone()));
+        self.span.set_attribute(KeyValue::new("model", response.model.clone()));
+        self.span.set_attribute(KeyValue::new("input_tokens", response.input_tokens as i64));
+        self.span.set_attribute(KeyValue::new("output_tokens", response.output_tokens as i64));
+        self.span.set_attribute(KeyValue::new("latency_ms", latency_ms));
+        self.span.set_status(Status::Ok);
+
+        instruments()
+            .tokens_total
+            .add(response.tokens_used as u64, &[KeyValue::new("provider", response.provider.clone())]);
+        instruments()
+            .latency_ms
+            .record(latency_ms, &[KeyValue::new("provider", response.provider.clone())]);
+
+        self.span.end();
+    }
+
+    /// Record failure on the root span (still recording latency) and close it.
+    pub fn finish_err(mut self, error: &str) {
+        let latency_ms = self.started.elapsed().as_millis() as f64;
+        self.span.set_attribute(KeyValue::new("latency_ms", latency_ms));
+        self.span.set_status(Status::error(error.to_string()));
+        instruments().latency_ms.record(latency_ms, &[KeyValue::new("error", true)]);
+        self.span.end();
+    }
+}
+
+/// A short-lived span for one step of a request (provider dispatch, a single
+/// tool call), started from the current OTEL context rather than the request span.
+pub struct ChildSpan {
+    span: global::BoxedSpan,
+}
+
+impl ChildSpan {
+    fn start(name: &'static str, attributes: &[KeyValue]) -> Self {
+        let tracer = global::tracer(INSTRUMENTATION_NAME);
+        let mut span = tracer.start(name);
+        for attr in attributes {
+            span.set_attribute(attr.clone());
+        }
+        Self { span }
+    }
+
+    pub fn finish_ok(mut self) {
+        self.span.set_status(Status::Ok);
+        self.span.end();
+    }
+
+    pub fn finish_err(mut self, error: &str) {
+        self.span.set_status(Status::error(error.to_string()));
+        self.span.end();
+    }
+}
+
+fn task_type_label(task_type: TaskType) -> &'static str {
+    match task_type {
+        TaskType::Chat => "chat",
+        TaskType::CodeGen => "code_gen",
+        TaskType::CodeReview => "code_review",
+        TaskType::Embedding => "embedding",
+        TaskType::Summary => "summary",
+        TaskType::QA => "qa",
+        TaskType::Translation => "translation",
+        TaskType::Security => "security",
+        TaskType::Creative => "creative",
+        TaskType::ToolUse => "tool_use",
+        TaskType::Agent => "agent",
+    }
+}
+
+fn provider_preference_label(pref: &ProviderPreference) -> &'static str {
+    match pref {
+        ProviderPreference::LocalOnly => "local_only",
+        ProviderPreference::LocalFirst => "local_first",
+        ProviderPreference::Specific(_) => "specific",
+        ProviderPreference::Any => "any",
+        ProviderPreference::CostOptimized => "cost_optimized",
+        ProviderPreference::QualityOptimized => "quality_optimized",
+    }
+}
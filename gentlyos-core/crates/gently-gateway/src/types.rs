@@ -129,6 +129,10 @@ pub struct GatewayResponse {
     pub latency_ms: u64,
     /// Response timestamp
     pub timestamp: DateTime<Utc>,
+    /// Hash of the request's prompt, carried over from `GatewayRequest` so
+    /// the response is self-contained for ledger verification
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_hash: Option<String>,
     /// Hash of response content
     #[serde(skip_serializing_if = "Option::is_none")]
     pub response_hash: Option<String>,
@@ -156,6 +160,7 @@ impl GatewayResponse {
             output_tokens: 0,
             latency_ms: 0,
             timestamp: Utc::now(),
+            prompt_hash: None,
             response_hash: None,
             chain_hash: None,
             tool_calls: Vec::new(),
@@ -268,6 +273,67 @@ impl Default for TaskType {
     }
 }
 
+/// One incremental chunk of a streamed `GatewayResponse`. Intermediate
+/// chunks carry only a content delta (and/or a partial tool call); the
+/// terminal chunk (the one with `finish_reason` set) carries the totals that
+/// only make sense once the whole response has been assembled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamChunk {
+    /// Request ID this chunk belongs to
+    pub request_id: String,
+    /// Incremental content since the previous chunk (empty on the terminal chunk)
+    #[serde(default)]
+    pub delta: String,
+    /// Set on the last chunk of the stream, e.g. "stop", "tool_use", "length"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finish_reason: Option<String>,
+    /// Partial tool call being streamed in, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_call_delta: Option<ToolCall>,
+    /// Total tokens used so far; only meaningful (and final) on the terminal chunk
+    #[serde(default)]
+    pub tokens_used: usize,
+    /// Elapsed time since the request started, in milliseconds; only
+    /// meaningful (and final) on the terminal chunk
+    #[serde(default)]
+    pub latency_ms: u64,
+}
+
+impl StreamChunk {
+    /// A non-terminal content delta chunk
+    pub fn delta(request_id: impl Into<String>, delta: impl Into<String>) -> Self {
+        Self {
+            request_id: request_id.into(),
+            delta: delta.into(),
+            finish_reason: None,
+            tool_call_delta: None,
+            tokens_used: 0,
+            latency_ms: 0,
+        }
+    }
+
+    /// The terminal chunk, carrying final totals
+    pub fn finish(
+        request_id: impl Into<String>,
+        finish_reason: impl Into<String>,
+        tokens_used: usize,
+        latency_ms: u64,
+    ) -> Self {
+        Self {
+            request_id: request_id.into(),
+            delta: String::new(),
+            finish_reason: Some(finish_reason.into()),
+            tool_call_delta: None,
+            tokens_used,
+            latency_ms,
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        self.finish_reason.is_some()
+    }
+}
+
 /// Tool call from assistant
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolCall {
@@ -729,6 +729,7 @@ fn parse_dimension(s: &str) -> Option<Dimension> {
         "where" => Some(Dimension::Where),
         "when" => Some(Dimension::When),
         "why" => Some(Dimension::Why),
+        "how" => Some(Dimension::How),
         _ => None,
     }
 }
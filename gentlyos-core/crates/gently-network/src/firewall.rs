@@ -4,17 +4,67 @@
 
 use crate::{Error, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
 use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// Callback invoked when a rule resolves to `RuleAction::Prompt`: given the
+/// connection's IP, port, and direction, decide whether to allow or deny it.
+pub type PromptFn = dyn Fn(&str, u16, Direction) -> PromptResponse + Send + Sync;
+
+/// Answer to a `RuleAction::Prompt` callback. The `ForSession` variants also
+/// persist the decision into `allowed_ips`/`blocked_ips`, so later checks
+/// for the same IP short-circuit without prompting again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptResponse {
+    Allow,
+    AllowForSession,
+    Deny,
+    DenyForSession,
+}
 
 /// Software firewall for GentlyOS
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Firewall {
     rules: Vec<FirewallRule>,
     blocked_ips: HashSet<String>,
-    allowed_ips: HashSet<String>,
+    /// IPs allowed outside the rule loop, each tagged with the minimum
+    /// layer (if any) that was required to grant it. `None` means the
+    /// allow is unconditional (e.g. localhost, or a plain `allow()` call);
+    /// `Some(min)` means it came from a `min_layer`-gated rule's prompt and
+    /// must be re-checked against the caller's layer on every lookup (see
+    /// `decide`), so a session-allow granted under one layer can't be
+    /// reused to bypass the same rule under a lower one.
+    allowed_ips: HashMap<String, Option<Layer>>,
     default_action: RuleAction,
     enabled: bool,
+    #[serde(skip)]
+    prompt_handler: Option<Arc<PromptFn>>,
+    /// Auto-blocks raised by `conn_tracker`, tagged with their expiry so
+    /// `sweep` can lift them; distinct from manual entries in `blocked_ips`,
+    /// which never expire on their own.
+    #[serde(skip)]
+    auto_blocked: HashMap<String, Instant>,
+    #[serde(skip, default = "Firewall::default_conn_tracker")]
+    conn_tracker: Arc<ConnTracker>,
+}
+
+impl std::fmt::Debug for Firewall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Firewall")
+            .field("rules", &self.rules)
+            .field("blocked_ips", &self.blocked_ips)
+            .field("allowed_ips", &self.allowed_ips)
+            .field("default_action", &self.default_action)
+            .field("enabled", &self.enabled)
+            .field("prompt_handler", &self.prompt_handler.is_some())
+            .field("auto_blocked", &self.auto_blocked.len())
+            .finish()
+    }
 }
 
 impl Firewall {
@@ -23,9 +73,12 @@ impl Firewall {
         let mut fw = Self {
             rules: Vec::new(),
             blocked_ips: HashSet::new(),
-            allowed_ips: HashSet::new(),
+            allowed_ips: HashMap::new(),
             default_action: RuleAction::Deny,
             enabled: true,
+            prompt_handler: None,
+            auto_blocked: HashMap::new(),
+            conn_tracker: Firewall::default_conn_tracker(),
         };
 
         // Always allow localhost
@@ -35,8 +88,64 @@ impl Firewall {
         fw
     }
 
-    /// Check if a connection should be allowed
-    pub fn check(&self, ip: &str, port: u16, direction: Direction) -> RuleAction {
+    /// Register the callback invoked when a rule resolves to `Prompt`.
+    pub fn set_prompt_handler<F>(&mut self, handler: F)
+    where
+        F: Fn(&str, u16, Direction) -> PromptResponse + Send + Sync + 'static,
+    {
+        self.prompt_handler = Some(Arc::new(handler));
+    }
+
+    /// Reconfigure the auto-block thresholds used by `check`'s connection
+    /// tracking: `threshold` denied attempts within `window` triggers a
+    /// `ban_duration` auto-block.
+    pub fn set_conn_tracker_limits(&mut self, threshold: u32, window: Duration, ban_duration: Duration) {
+        self.conn_tracker = Arc::new(ConnTracker::new(threshold, window, ban_duration));
+    }
+
+    /// Default connection-tracker limits: 10 denied attempts in 60s
+    /// triggers a 5-minute auto-block.
+    fn default_conn_tracker() -> Arc<ConnTracker> {
+        Arc::new(ConnTracker::new(10, Duration::from_secs(60), Duration::from_secs(300)))
+    }
+
+    /// Check if a connection should be allowed. When a matching rule
+    /// resolves to `RuleAction::Prompt`, invokes the registered callback (or
+    /// `default_prompt` if none is registered) and maps its answer to
+    /// `Allow`/`Deny`, persisting "for session" answers into
+    /// `allowed_ips`/`blocked_ips` so future checks for the same IP
+    /// short-circuit without prompting again.
+    ///
+    /// Every decision is recorded into the connection tracker; an IP that
+    /// racks up too many denials within its tracking window is
+    /// auto-blocked (see `ConnTracker`) until `sweep` lifts the ban.
+    pub fn check(&mut self, ip: &str, port: u16, direction: Direction) -> RuleAction {
+        self.check_impl(None, ip, port, direction)
+    }
+
+    /// Like `check`, but also enforces `min_layer` on rules: a rule that
+    /// restricts itself to a minimum privilege layer only matches when
+    /// `layer.has_access(min_layer)` holds. Lets policies like "outbound to
+    /// this subnet is allowed only for `OsAdmin` and above" coexist with
+    /// plain IP/port rules.
+    pub fn check_for(&mut self, layer: Layer, ip: &str, port: u16, direction: Direction) -> RuleAction {
+        self.check_impl(Some(layer), ip, port, direction)
+    }
+
+    fn check_impl(&mut self, layer: Option<Layer>, ip: &str, port: u16, direction: Direction) -> RuleAction {
+        let decision = self.decide(layer, ip, port, direction);
+
+        if self.enabled {
+            let now = Instant::now();
+            if self.conn_tracker.record(ip, decision == RuleAction::Allow || decision == RuleAction::Log, now) {
+                self.auto_blocked.insert(ip.to_string(), now + self.conn_tracker.ban_duration);
+            }
+        }
+
+        decision
+    }
+
+    fn decide(&mut self, layer: Option<Layer>, ip: &str, port: u16, direction: Direction) -> RuleAction {
         if !self.enabled {
             return RuleAction::Allow;
         }
@@ -46,14 +155,31 @@ impl Firewall {
             return RuleAction::Deny;
         }
 
-        // Check explicit allows
-        if self.allowed_ips.contains(ip) {
-            return RuleAction::Allow;
+        // Check auto-blocks raised by the connection tracker (lazily expired)
+        if let Some(expires_at) = self.auto_blocked.get(ip) {
+            if *expires_at > Instant::now() {
+                return RuleAction::Deny;
+            }
+        }
+
+        // Check explicit allows. A `Some(min)` entry only short-circuits
+        // for callers whose layer still has access to it; otherwise it
+        // falls through to the rule loop below, which re-applies the
+        // min_layer gate for this specific caller.
+        match self.allowed_ips.get(ip) {
+            Some(None) => return RuleAction::Allow,
+            Some(Some(min)) if layer.is_some_and(|caller| caller.has_access(*min)) => {
+                return RuleAction::Allow;
+            }
+            _ => {}
         }
 
         // Check rules
         for rule in &self.rules {
-            if rule.matches(ip, port, direction) {
+            if rule.matches_for(layer, ip, port, direction) {
+                if rule.action == RuleAction::Prompt {
+                    return self.resolve_prompt(ip, port, direction, rule.min_layer);
+                }
                 return rule.action;
             }
         }
@@ -62,15 +188,51 @@ impl Firewall {
         self.default_action
     }
 
+    /// Lift expired auto-blocks. Manual entries in `blocked_ips` are never
+    /// touched here — only bans raised by the connection tracker expire.
+    pub fn sweep(&mut self, now: Instant) {
+        self.auto_blocked.retain(|_, expires_at| *expires_at > now);
+    }
+
+    /// Invoke the prompt callback (or `default_prompt` if none is
+    /// registered), apply any "for session" persistence, and return the
+    /// resulting `Allow`/`Deny` decision.
+    fn resolve_prompt(
+        &mut self,
+        ip: &str,
+        port: u16,
+        direction: Direction,
+        min_layer: Option<Layer>,
+    ) -> RuleAction {
+        let response = match &self.prompt_handler {
+            Some(handler) => handler(ip, port, direction),
+            None => default_prompt(ip, port, direction),
+        };
+
+        match response {
+            PromptResponse::Allow => RuleAction::Allow,
+            PromptResponse::Deny => RuleAction::Deny,
+            PromptResponse::AllowForSession => {
+                self.allowed_ips.insert(ip.to_string(), min_layer);
+                self.blocked_ips.remove(ip);
+                RuleAction::Allow
+            }
+            PromptResponse::DenyForSession => {
+                self.block(ip);
+                RuleAction::Deny
+            }
+        }
+    }
+
     /// Block an IP
     pub fn block(&mut self, ip: &str) {
         self.blocked_ips.insert(ip.to_string());
         self.allowed_ips.remove(ip);
     }
 
-    /// Allow an IP
+    /// Allow an IP unconditionally, regardless of the caller's layer.
     pub fn allow(&mut self, ip: &str) {
-        self.allowed_ips.insert(ip.to_string());
+        self.allowed_ips.insert(ip.to_string(), None);
         self.blocked_ips.remove(ip);
     }
 
@@ -94,8 +256,10 @@ impl Firewall {
         &self.blocked_ips
     }
 
-    /// Get allowed IPs
-    pub fn allowed(&self) -> &HashSet<String> {
+    /// Get allowed IPs, each paired with the minimum layer (if any)
+    /// required to reuse the allow without re-matching the rule that
+    /// granted it.
+    pub fn allowed(&self) -> &HashMap<String, Option<Layer>> {
         &self.allowed_ips
     }
 
@@ -111,6 +275,194 @@ impl Default for Firewall {
     }
 }
 
+impl Firewall {
+    /// Re-read `path` and atomically replace the live ruleset with it.
+    ///
+    /// Parses and validates the whole file before touching `self`, so a
+    /// malformed edit leaves the previously-good ruleset intact instead of
+    /// falling back to deny-all. Logs the rule names that were added and
+    /// removed relative to the current config.
+    pub fn reload_from(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let text = std::fs::read_to_string(path.as_ref()).map_err(Error::Io)?;
+        let config = FirewallConfig::parse(&text)?;
+
+        let old_names: HashSet<&str> = self.rules.iter().map(|r| r.name.as_str()).collect();
+        let new_names: HashSet<&str> = config.rules.iter().map(|r| r.name.as_str()).collect();
+        for added in new_names.difference(&old_names) {
+            tracing::info!("firewall rule added: {added}");
+        }
+        for removed in old_names.difference(&new_names) {
+            tracing::info!("firewall rule removed: {removed}");
+        }
+
+        let prompt_handler = self.prompt_handler.take();
+        let auto_blocked = std::mem::take(&mut self.auto_blocked);
+        let conn_tracker = self.conn_tracker.clone();
+        *self = config.into_firewall();
+        self.prompt_handler = prompt_handler;
+        self.auto_blocked = auto_blocked;
+        self.conn_tracker = conn_tracker;
+        Ok(())
+    }
+
+    /// Spawn a task that watches `path` and hot-reloads the firewall behind
+    /// `live` whenever the file's contents change. Polls on `debounce` so
+    /// that several rapid writes (e.g. an editor's save-then-flush) collapse
+    /// into a single reload; a parse failure is logged and otherwise
+    /// ignored, leaving the previous ruleset in effect.
+    pub async fn watch(live: Arc<RwLock<Firewall>>, path: impl Into<PathBuf>, debounce: std::time::Duration) {
+        let path = path.into();
+        let mut last_modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+
+        loop {
+            tokio::time::sleep(debounce).await;
+
+            let modified = match std::fs::metadata(&path).and_then(|m| m.modified()) {
+                Ok(m) => m,
+                Err(e) => {
+                    tracing::warn!("firewall config watch: failed to stat {}: {e}", path.display());
+                    continue;
+                }
+            };
+            if Some(modified) == last_modified {
+                continue;
+            }
+            last_modified = Some(modified);
+
+            let mut fw = live.write().await;
+            if let Err(e) = fw.reload_from(&path) {
+                tracing::warn!("firewall config reload failed, keeping previous ruleset: {e}");
+            } else {
+                tracing::info!("firewall config reloaded from {}", path.display());
+            }
+        }
+    }
+}
+
+/// On-disk representation of a [`Firewall`], loaded by [`Firewall::reload_from`].
+/// Mirrors `Firewall`'s persisted fields, except that `allowed_ips` is a
+/// plain set (config-file allows carry no layer tag), so a serialized
+/// `Firewall` with layer-tagged allows does not load as a `FirewallConfig`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FirewallConfig {
+    #[serde(default)]
+    pub rules: Vec<FirewallRule>,
+    #[serde(default)]
+    pub blocked_ips: HashSet<String>,
+    #[serde(default)]
+    pub allowed_ips: HashSet<String>,
+    #[serde(default = "FirewallConfig::default_action")]
+    pub default_action: RuleAction,
+    #[serde(default = "FirewallConfig::default_enabled")]
+    pub enabled: bool,
+}
+
+impl FirewallConfig {
+    fn default_action() -> RuleAction {
+        RuleAction::Deny
+    }
+
+    fn default_enabled() -> bool {
+        true
+    }
+
+    /// Parse and validate a config from JSON text without touching any
+    /// live `Firewall`.
+    pub fn parse(text: &str) -> Result<Self> {
+        let config: FirewallConfig =
+            serde_json::from_str(text).map_err(|e| Error::InvalidRule(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        for rule in &self.rules {
+            if rule.name.is_empty() {
+                return Err(Error::InvalidRule("rule name must not be empty".to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    fn into_firewall(self) -> Firewall {
+        Firewall {
+            rules: self.rules,
+            blocked_ips: self.blocked_ips,
+            // Config-file allows are unconditional, same as `Firewall::allow`.
+            allowed_ips: self.allowed_ips.into_iter().map(|ip| (ip, None)).collect(),
+            default_action: self.default_action,
+            enabled: self.enabled,
+            prompt_handler: None,
+            auto_blocked: HashMap::new(),
+            conn_tracker: Firewall::default_conn_tracker(),
+        }
+    }
+}
+
+/// Number of independently-locked buckets `ConnTracker` shards its
+/// per-IP state across, so recording a decision for one source IP never
+/// contends with another IP hashed into a different shard.
+const CONN_TRACKER_SHARDS: usize = 16;
+
+/// Tracks recent denied attempts per source IP so `Firewall::check` can
+/// auto-block abusive peers instead of only applying static rules.
+///
+/// State is sharded across `CONN_TRACKER_SHARDS` independently-locked
+/// buckets keyed by a hash of the IP, so recording contention under many
+/// concurrent source IPs doesn't serialize on a single lock.
+struct ConnTracker {
+    shards: Vec<Mutex<HashMap<String, VecDeque<Instant>>>>,
+    /// Denied attempts within `window` before an IP is auto-blocked.
+    threshold: u32,
+    /// Sliding window denied attempts are counted over.
+    window: Duration,
+    /// How long an auto-block raised by this tracker lasts.
+    ban_duration: Duration,
+}
+
+impl ConnTracker {
+    fn new(threshold: u32, window: Duration, ban_duration: Duration) -> Self {
+        Self {
+            shards: (0..CONN_TRACKER_SHARDS).map(|_| Mutex::new(HashMap::new())).collect(),
+            threshold,
+            window,
+            ban_duration,
+        }
+    }
+
+    fn shard_for(&self, ip: &str) -> &Mutex<HashMap<String, VecDeque<Instant>>> {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        ip.hash(&mut hasher);
+        &self.shards[(hasher.finish() as usize) % self.shards.len()]
+    }
+
+    /// Record a `check` decision for `ip`. Denied attempts are pushed onto
+    /// that IP's sliding window; anything older than `window` is evicted.
+    /// Returns `true` once the window holds more than `threshold` denied
+    /// attempts, signaling that `ip` should be auto-blocked.
+    fn record(&self, ip: &str, allowed: bool, now: Instant) -> bool {
+        let mut shard = self.shard_for(ip).lock().unwrap();
+        let attempts = shard.entry(ip.to_string()).or_default();
+
+        if !allowed {
+            attempts.push_back(now);
+        }
+        while let Some(&oldest) = attempts.front() {
+            if now.duration_since(oldest) > self.window {
+                attempts.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        let crossed = attempts.len() as u32 > self.threshold;
+        if attempts.is_empty() {
+            shard.remove(ip);
+        }
+        crossed
+    }
+}
+
 /// A firewall rule
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FirewallRule {
@@ -121,6 +473,12 @@ pub struct FirewallRule {
     pub direction: Option<Direction>,
     pub action: RuleAction,
     pub enabled: bool,
+    /// Lowest privilege layer a caller must hold for this rule to match
+    /// (e.g. `Some(Layer::OsAdmin)` restricts the rule to OsAdmin and
+    /// above). `None` means the rule applies regardless of layer. Only
+    /// enforced by `Firewall::check_for`; plain `check` has no layer to
+    /// test against, so layer-gated rules never match there.
+    pub min_layer: Option<Layer>,
 }
 
 impl FirewallRule {
@@ -134,6 +492,7 @@ impl FirewallRule {
             direction: None,
             action,
             enabled: true,
+            min_layer: None,
         }
     }
 
@@ -143,6 +502,12 @@ impl FirewallRule {
         self
     }
 
+    /// Restrict this rule to callers whose `Layer` has access to `min_layer`.
+    pub fn with_min_layer(mut self, min_layer: Layer) -> Self {
+        self.min_layer = Some(min_layer);
+        self
+    }
+
     /// Match against port
     pub fn with_port(mut self, port: u16) -> Self {
         self.port = Some(port);
@@ -198,13 +563,31 @@ impl FirewallRule {
         true
     }
 
+    /// Like `matches`, but also gates on `min_layer`: a layer-restricted
+    /// rule only matches when `caller` is known and has access to it.
+    fn matches_for(&self, caller: Option<Layer>, ip: &str, port: u16, direction: Direction) -> bool {
+        if !self.matches(ip, port, direction) {
+            return false;
+        }
+
+        match (self.min_layer, caller) {
+            (Some(min), Some(caller)) => caller.has_access(min),
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+    }
+
     fn ip_matches(&self, ip: &str, pattern: &str) -> bool {
         if pattern == "*" {
             return true;
         }
 
+        if let Some((base, prefix_len)) = pattern.split_once('/') {
+            return cidr_matches(ip, base, prefix_len);
+        }
+
         if pattern.contains('*') {
-            // Simple wildcard matching
+            // Simple wildcard matching (IPv4 dotted-quad only)
             let parts: Vec<&str> = pattern.split('.').collect();
             let ip_parts: Vec<&str> = ip.split('.').collect();
 
@@ -220,17 +603,56 @@ impl FirewallRule {
 
             true
         } else {
-            ip == pattern
+            // Parse both sides when possible so that equivalent IPv6
+            // spellings (e.g. "::1" vs "0:0:0:0:0:0:0:1") compare equal.
+            match (ip.parse::<IpAddr>(), pattern.parse::<IpAddr>()) {
+                (Ok(ip_addr), Ok(pattern_addr)) => ip_addr == pattern_addr,
+                _ => ip == pattern,
+            }
         }
     }
 }
 
+/// Match `ip` against a CIDR block `base/prefix_len` (e.g. `10.0.0.0/8` or
+/// `2001:db8::/32`). Returns `false` on any parse failure or on an
+/// IPv4/IPv6 family mismatch between `ip` and `base`.
+fn cidr_matches(ip: &str, base: &str, prefix_len: &str) -> bool {
+    let Ok(ip_addr) = ip.parse::<IpAddr>() else { return false };
+    let Ok(base_addr) = base.parse::<IpAddr>() else { return false };
+    let Ok(prefix) = prefix_len.parse::<u32>() else { return false };
+
+    match (ip_addr, base_addr) {
+        (IpAddr::V4(ip4), IpAddr::V4(base4)) => {
+            if prefix > 32 {
+                return false;
+            }
+            let mask = if prefix == 0 { 0 } else { u32::MAX << (32 - prefix) };
+            (u32::from(ip4) & mask) == (u32::from(base4) & mask)
+        }
+        (IpAddr::V6(ip6), IpAddr::V6(base6)) => {
+            if prefix > 128 {
+                return false;
+            }
+            let mask = if prefix == 0 { 0 } else { u128::MAX << (128 - prefix) };
+            (u128::from(ip6) & mask) == (u128::from(base6) & mask)
+        }
+        _ => false,
+    }
+}
+
 /// Rule action
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RuleAction {
     Allow,
     Deny,
     Log,  // Allow but log
+    Prompt,  // Ask the registered callback (or default_prompt) what to do
+}
+
+/// Fallback used by `Firewall::resolve_prompt` when no callback is
+/// registered: deny, matching the firewall's default-deny posture.
+fn default_prompt(_ip: &str, _port: u16, _direction: Direction) -> PromptResponse {
+    PromptResponse::Deny
 }
 
 /// Connection direction
@@ -240,13 +662,38 @@ pub enum Direction {
     Outbound,
 }
 
+/// Privilege layer a caller is acting as. L0 = highest privilege, L5 =
+/// lowest. Mirrors `gently-core::layer::Layer` / cage-web's
+/// `tier_auth::Layer` but kept local to avoid workspace coupling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum Layer {
+    Admin     = 0, // L0
+    GentlyDev = 1, // L1
+    DevLevel  = 2, // L2
+    OsAdmin   = 3, // L3
+    RootUser  = 4, // L4
+    User      = 5, // L5
+}
+
+impl Layer {
+    pub fn level(self) -> u8 {
+        self as u8
+    }
+
+    /// Does this layer have at least the privilege of `required`?
+    pub fn has_access(self, required: Layer) -> bool {
+        self.level() <= required.level()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_firewall_default_deny() {
-        let fw = Firewall::new();
+        let mut fw = Firewall::new();
 
         // Localhost allowed
         assert_eq!(fw.check("127.0.0.1", 80, Direction::Inbound), RuleAction::Allow);
@@ -273,4 +720,166 @@ mod tests {
         assert!(!rule.matches("142.250.1.1", 443, Direction::Inbound));
         assert!(!rule.matches("8.8.8.8", 443, Direction::Outbound));
     }
+
+    #[test]
+    fn test_prompt_without_handler_denies() {
+        let mut fw = Firewall::new();
+        fw.rules.push(FirewallRule::new("prompt_unknown", RuleAction::Prompt));
+
+        assert_eq!(fw.check("1.2.3.4", 80, Direction::Outbound), RuleAction::Deny);
+    }
+
+    #[test]
+    fn test_cidr_v4_matching() {
+        let rule = FirewallRule::new("private_net", RuleAction::Deny).with_ip("10.0.0.0/8");
+
+        assert!(rule.matches("10.1.2.3", 443, Direction::Outbound));
+        assert!(!rule.matches("11.1.2.3", 443, Direction::Outbound));
+    }
+
+    #[test]
+    fn test_cidr_v6_matching() {
+        let rule = FirewallRule::new("doc_net", RuleAction::Deny).with_ip("2001:db8::/32");
+
+        assert!(rule.matches("2001:db8::1", 443, Direction::Outbound));
+        assert!(!rule.matches("2001:db9::1", 443, Direction::Outbound));
+    }
+
+    #[test]
+    fn test_ipv6_exact_matching() {
+        let rule = FirewallRule::new("loopback", RuleAction::Allow).with_ip("::1");
+
+        assert!(rule.matches("::1", 80, Direction::Inbound));
+        assert!(rule.matches("0:0:0:0:0:0:0:1", 80, Direction::Inbound));
+        assert!(!rule.matches("::2", 80, Direction::Inbound));
+    }
+
+    #[test]
+    fn test_prompt_handler_allow_for_session() {
+        let mut fw = Firewall::new();
+        fw.rules.push(FirewallRule::new("prompt_unknown", RuleAction::Prompt));
+        fw.set_prompt_handler(|_, _, _| PromptResponse::AllowForSession);
+
+        assert_eq!(fw.check("1.2.3.4", 80, Direction::Outbound), RuleAction::Allow);
+        // Persisted into allowed_ips, so the next check short-circuits before the rule.
+        fw.rules.clear();
+        assert_eq!(fw.check("1.2.3.4", 80, Direction::Outbound), RuleAction::Allow);
+    }
+
+    #[test]
+    fn test_config_parse_rejects_empty_rule_name() {
+        let json = r#"{"rules":[{"name":"","ip_pattern":null,"port":null,"port_range":null,"direction":null,"action":"Deny","enabled":true}]}"#;
+        assert!(FirewallConfig::parse(json).is_err());
+    }
+
+    #[test]
+    fn test_reload_from_replaces_ruleset() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("firewall.json");
+        std::fs::write(
+            &path,
+            r#"{"rules":[],"blocked_ips":["9.9.9.9"],"allowed_ips":[],"default_action":"Deny","enabled":true}"#,
+        )
+        .unwrap();
+
+        let mut fw = Firewall::new();
+        fw.reload_from(&path).unwrap();
+
+        assert_eq!(fw.check("9.9.9.9", 80, Direction::Outbound), RuleAction::Deny);
+        assert_eq!(fw.check("127.0.0.1", 80, Direction::Inbound), RuleAction::Allow);
+    }
+
+    #[test]
+    fn test_reload_from_keeps_previous_ruleset_on_malformed_edit() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("firewall.json");
+        std::fs::write(&path, "not valid json").unwrap();
+
+        let mut fw = Firewall::new();
+        fw.allow("8.8.8.8");
+        assert!(fw.reload_from(&path).is_err());
+
+        // Previous good ruleset must still be in effect, not deny-all.
+        assert_eq!(fw.check("8.8.8.8", 53, Direction::Outbound), RuleAction::Allow);
+    }
+
+    #[test]
+    fn test_repeated_denials_trigger_auto_block() {
+        let mut fw = Firewall::new();
+        fw.set_conn_tracker_limits(3, Duration::from_secs(60), Duration::from_secs(300));
+
+        // Denied by the default-deny policy each time; once it crosses the
+        // threshold the IP gets auto-blocked even against a rule that
+        // would otherwise allow it.
+        for _ in 0..4 {
+            fw.check("6.6.6.6", 80, Direction::Outbound);
+        }
+        fw.add_rule(FirewallRule::new("allow_6", RuleAction::Allow).with_ip("6.6.6.6"));
+
+        assert_eq!(fw.check("6.6.6.6", 80, Direction::Outbound), RuleAction::Deny);
+    }
+
+    #[test]
+    fn test_sweep_lifts_expired_auto_block_but_not_manual_block() {
+        let mut fw = Firewall::new();
+        fw.set_conn_tracker_limits(1, Duration::from_secs(60), Duration::from_secs(1));
+        fw.block("5.5.5.5"); // manual, should survive sweep
+
+        fw.check("6.6.6.6", 80, Direction::Outbound);
+        fw.check("6.6.6.6", 80, Direction::Outbound);
+        assert_eq!(fw.check("6.6.6.6", 80, Direction::Outbound), RuleAction::Deny);
+
+        fw.sweep(Instant::now() + Duration::from_secs(2));
+        fw.add_rule(FirewallRule::new("allow_6", RuleAction::Allow).with_ip("6.6.6.6"));
+
+        assert_eq!(fw.check("6.6.6.6", 80, Direction::Outbound), RuleAction::Allow);
+        assert_eq!(fw.check("5.5.5.5", 80, Direction::Outbound), RuleAction::Deny);
+    }
+
+    #[test]
+    fn test_min_layer_gates_rule_match() {
+        let mut fw = Firewall::new();
+        fw.add_rule(
+            FirewallRule::new("admin_subnet", RuleAction::Allow)
+                .with_ip("10.0.0.0/8")
+                .with_min_layer(Layer::OsAdmin),
+        );
+
+        // OsAdmin (L3) has access; User (L5) does not.
+        assert_eq!(fw.check_for(Layer::OsAdmin, "10.1.2.3", 443, Direction::Outbound), RuleAction::Allow);
+        assert_eq!(fw.check_for(Layer::User, "10.1.2.3", 443, Direction::Outbound), RuleAction::Deny);
+
+        // Without a known layer, a layer-gated rule never matches.
+        assert_eq!(fw.check("10.1.2.3", 443, Direction::Outbound), RuleAction::Deny);
+    }
+
+    #[test]
+    fn test_allow_for_session_still_gates_by_layer() {
+        let mut fw = Firewall::new();
+        fw.rules.push(
+            FirewallRule::new("prompt_admin_subnet", RuleAction::Prompt)
+                .with_ip("10.0.0.0/8")
+                .with_min_layer(Layer::OsAdmin),
+        );
+        fw.set_prompt_handler(|_, _, _| PromptResponse::AllowForSession);
+
+        // OsAdmin triggers the prompt and gets allowed for the session.
+        assert_eq!(
+            fw.check_for(Layer::OsAdmin, "10.1.2.3", 443, Direction::Outbound),
+            RuleAction::Allow
+        );
+
+        // A later User-layer request for the same IP must not reuse that
+        // session-allow: it never had access to the OsAdmin-gated rule.
+        assert_eq!(
+            fw.check_for(Layer::User, "10.1.2.3", 443, Direction::Outbound),
+            RuleAction::Deny
+        );
+
+        // OsAdmin (and above) can still reuse the fast path.
+        assert_eq!(
+            fw.check_for(Layer::Admin, "10.1.2.3", 443, Direction::Outbound),
+            RuleAction::Allow
+        );
+    }
 }
@@ -20,7 +20,7 @@ pub mod monitor;
 pub mod capture;
 pub mod mitm;
 
-pub use firewall::{Firewall, FirewallRule, RuleAction};
+pub use firewall::{Firewall, FirewallConfig, FirewallRule, RuleAction, PromptFn, PromptResponse, Layer};
 pub use visualizer::NetworkVisualizer;
 pub use monitor::{NetworkMonitor, NetworkEvent};
 pub use capture::{PacketCapture, CaptureSession, Packet, filters, display_filters};
@@ -54,7 +54,7 @@ impl NetworkVisualizer {
         lines.push("│  ACTIVE CONNECTIONS:                                                        │".to_string());
 
         // Show allowed IPs
-        for ip in self.firewall.allowed().iter().take(3) {
+        for ip in self.firewall.allowed().keys().take(3) {
             lines.push(format!("│  ├── {:20} ████ TRUSTED                                     │", ip));
         }
 
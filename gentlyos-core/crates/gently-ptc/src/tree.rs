@@ -5,6 +5,7 @@
 
 use anyhow::{Result, anyhow};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 
 /// Scale level of a node in the universal tree.
@@ -83,6 +84,356 @@ impl Tree {
     pub fn node_count(&self) -> usize {
         self.nodes.len()
     }
+
+    /// Validate the tree's shape, returning a human-readable issue per
+    /// problem found (empty if the tree is well-formed). Checks for:
+    /// children referencing node IDs that don't exist, cycles, and nodes
+    /// that aren't reachable from any root.
+    pub fn validate(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        for node in self.nodes.values() {
+            for child_id in &node.children {
+                if !self.nodes.contains_key(child_id) {
+                    issues.push(format!(
+                        "node '{}' references missing child '{}'",
+                        node.id, child_id
+                    ));
+                }
+            }
+        }
+
+        let mut visited = std::collections::HashSet::new();
+        for id in self.nodes.keys() {
+            if visited.contains(id) {
+                continue;
+            }
+            let mut path = Vec::new();
+            self.detect_cycle(id, &mut path, &mut visited, &mut issues);
+        }
+
+        let mut reachable = std::collections::HashSet::new();
+        for root_id in &self.root_ids {
+            self.mark_reachable(root_id, &mut reachable);
+        }
+        let mut unreachable: Vec<&String> = self
+            .nodes
+            .keys()
+            .filter(|id| !reachable.contains(id.as_str()))
+            .collect();
+        unreachable.sort();
+        for id in unreachable {
+            issues.push(format!("node '{}' is unreachable from any root", id));
+        }
+
+        issues
+    }
+
+    fn detect_cycle<'a>(
+        &'a self,
+        id: &'a str,
+        path: &mut Vec<&'a str>,
+        visited: &mut std::collections::HashSet<&'a str>,
+        issues: &mut Vec<String>,
+    ) {
+        if path.contains(&id) {
+            issues.push(format!("cycle detected in tree at node '{}'", id));
+            return;
+        }
+        if !visited.insert(id) {
+            return;
+        }
+
+        let Some(node) = self.nodes.get(id) else {
+            return;
+        };
+
+        path.push(id);
+        for child_id in &node.children {
+            self.detect_cycle(child_id, path, visited, issues);
+        }
+        path.pop();
+    }
+
+    fn mark_reachable<'a>(&'a self, id: &'a str, reachable: &mut std::collections::HashSet<&'a str>) {
+        if !reachable.insert(id) {
+            return;
+        }
+        if let Some(node) = self.nodes.get(id) {
+            for child_id in &node.children {
+                self.mark_reachable(child_id, reachable);
+            }
+        }
+    }
+
+    /// All ancestor IDs of `id` (nodes that transitively list it as a child),
+    /// in no particular order.
+    pub fn ancestors(&self, id: &str) -> Vec<String> {
+        let mut result = Vec::new();
+        let mut frontier = vec![id.to_string()];
+        while let Some(current) = frontier.pop() {
+            for node in self.nodes.values() {
+                if node.children.iter().any(|c| c == &current) && !result.contains(&node.id) {
+                    result.push(node.id.clone());
+                    frontier.push(node.id.clone());
+                }
+            }
+        }
+        result
+    }
+
+    /// All descendant IDs of `id`, reached by following `children`.
+    pub fn descendants(&self, id: &str) -> Vec<String> {
+        let mut result = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        seen.insert(id.to_string());
+        let mut frontier = vec![id.to_string()];
+        while let Some(current) = frontier.pop() {
+            let Some(node) = self.nodes.get(&current) else {
+                continue;
+            };
+            for child_id in &node.children {
+                if seen.insert(child_id.clone()) {
+                    result.push(child_id.clone());
+                    frontier.push(child_id.clone());
+                }
+            }
+        }
+        result
+    }
+
+    /// IDs from a root down to `id`, inclusive, or an empty vec if `id` isn't
+    /// reachable from any root.
+    pub fn path_to(&self, id: &str) -> Vec<String> {
+        for root_id in &self.root_ids {
+            let mut path = Vec::new();
+            if self.find_path(root_id, id, &mut path) {
+                return path;
+            }
+        }
+        Vec::new()
+    }
+
+    fn find_path(&self, current: &str, target: &str, path: &mut Vec<String>) -> bool {
+        path.push(current.to_string());
+        if current == target {
+            return true;
+        }
+        if let Some(node) = self.nodes.get(current) {
+            for child_id in &node.children {
+                if self.find_path(child_id, target, path) {
+                    return true;
+                }
+            }
+        }
+        path.pop();
+        false
+    }
+
+    /// All node IDs at a given `NodeScale`.
+    pub fn find_by_scale(&self, scale: NodeScale) -> Vec<String> {
+        self.nodes
+            .values()
+            .filter(|n| n.scale == scale)
+            .map(|n| n.id.clone())
+            .collect()
+    }
+
+    /// Rank node IDs by keyword overlap with `query`, searching each node's
+    /// `name` and the `keywords` array in its `metadata` (if present).
+    /// Returns only nodes with at least one matching term, scored by the
+    /// count of matching terms and sorted highest-first.
+    pub fn search(&self, query: &str) -> Vec<(String, f32)> {
+        let query_terms: std::collections::HashSet<String> =
+            query.to_lowercase().split_whitespace().map(|s| s.to_string()).collect();
+        if query_terms.is_empty() {
+            return Vec::new();
+        }
+
+        let mut scored: Vec<(String, f32)> = self
+            .nodes
+            .values()
+            .filter_map(|node| {
+                let terms = self.index_terms(node);
+                let overlap = terms.intersection(&query_terms).count();
+                if overlap == 0 {
+                    None
+                } else {
+                    Some((node.id.clone(), overlap as f32))
+                }
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored
+    }
+
+    /// Searchable terms for a node: its lowercased name words plus any
+    /// `metadata.keywords` strings.
+    fn index_terms(&self, node: &UniversalNode) -> std::collections::HashSet<String> {
+        let mut terms: std::collections::HashSet<String> = node
+            .name
+            .to_lowercase()
+            .split_whitespace()
+            .map(|s| s.to_string())
+            .collect();
+
+        if let Some(keywords) = node.metadata.get("keywords").and_then(|v| v.as_array()) {
+            for keyword in keywords {
+                if let Some(s) = keyword.as_str() {
+                    terms.insert(s.to_lowercase());
+                }
+            }
+        }
+
+        terms
+    }
+
+    /// Compute a content hash for every node, bottom-up.
+    ///
+    /// A leaf's hash is `SHA256(id || name || scale.depth() || canonical_json(metadata))`;
+    /// an internal node's hash folds in its own fields plus each child's hash,
+    /// in declared order, so reordering or editing any descendant changes every
+    /// ancestor's hash. Metadata is serialized with sorted keys so two
+    /// logically-equal trees hash identically regardless of field order.
+    ///
+    /// Errors (rather than panics) if `children` references an ID absent from
+    /// `nodes`, or if the tree contains a cycle.
+    pub fn content_hashes(&self) -> Result<HashMap<String, String>> {
+        let mut hashes = HashMap::new();
+        let mut visiting = Vec::new();
+        for id in self.nodes.keys() {
+            self.hash_node(id, &mut hashes, &mut visiting)?;
+        }
+        Ok(hashes)
+    }
+
+    fn hash_node<'a>(
+        &'a self,
+        id: &'a str,
+        hashes: &mut HashMap<String, String>,
+        visiting: &mut Vec<&'a str>,
+    ) -> Result<String> {
+        if let Some(hash) = hashes.get(id) {
+            return Ok(hash.clone());
+        }
+
+        if visiting.contains(&id) {
+            return Err(anyhow!("cycle detected in tree at node '{}'", id));
+        }
+
+        let node = self
+            .nodes
+            .get(id)
+            .ok_or_else(|| anyhow!("node '{}' is referenced as a child but not present in nodes", id))?;
+
+        visiting.push(id);
+
+        let mut hasher = Sha256::new();
+        hasher.update(node.id.as_bytes());
+        hasher.update(node.name.as_bytes());
+        hasher.update(node.scale.depth().to_string().as_bytes());
+        hasher.update(canonical_json(&node.metadata).as_bytes());
+
+        for child_id in &node.children {
+            let child_hash = self.hash_node(child_id, hashes, visiting)?;
+            hasher.update(child_hash.as_bytes());
+        }
+
+        visiting.pop();
+
+        let hash = hex::encode(hasher.finalize());
+        hashes.insert(id.to_string(), hash.clone());
+        Ok(hash)
+    }
+
+    /// Hash of a single node, computed fresh (not cached across calls).
+    pub fn node_hash(&self, id: &str) -> Result<String> {
+        let mut hashes = HashMap::new();
+        let mut visiting = Vec::new();
+        self.hash_node(id, &mut hashes, &mut visiting)
+    }
+
+    /// Hash over `root_ids`, in order - the tree's overall content hash.
+    pub fn root_hash(&self) -> Result<String> {
+        let hashes = self.content_hashes()?;
+        let mut hasher = Sha256::new();
+        for root_id in &self.root_ids {
+            let hash = hashes
+                .get(root_id)
+                .ok_or_else(|| anyhow!("root '{}' is not present in nodes", root_id))?;
+            hasher.update(hash.as_bytes());
+        }
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// Diff against `other`, returning the IDs of nodes whose subtree hash
+    /// changed. Walks both trees top-down from their root IDs, only
+    /// descending into a node's children when that node's hash differs
+    /// between the two trees - an unchanged subtree is skipped entirely.
+    pub fn diff(&self, other: &Tree) -> Result<Vec<String>> {
+        let self_hashes = self.content_hashes()?;
+        let other_hashes = other.content_hashes()?;
+
+        let mut changed = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        let mut queue: Vec<String> = self.root_ids.clone();
+        for root_id in &other.root_ids {
+            if !queue.contains(root_id) {
+                queue.push(root_id.clone());
+            }
+        }
+
+        while let Some(id) = queue.pop() {
+            if !seen.insert(id.clone()) {
+                continue;
+            }
+
+            let self_hash = self_hashes.get(&id);
+            let other_hash = other_hashes.get(&id);
+            if self_hash == other_hash {
+                continue;
+            }
+
+            changed.push(id.clone());
+
+            let mut children: Vec<String> = Vec::new();
+            if let Some(node) = self.nodes.get(&id) {
+                children.extend(node.children.iter().cloned());
+            }
+            if let Some(node) = other.nodes.get(&id) {
+                for child in &node.children {
+                    if !children.contains(child) {
+                        children.push(child.clone());
+                    }
+                }
+            }
+            queue.extend(children);
+        }
+
+        Ok(changed)
+    }
+}
+
+/// Serialize `value` to JSON with object keys sorted, so logically-equal
+/// values (differing only in key order) produce identical bytes.
+fn canonical_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let entries: Vec<String> = keys
+                .into_iter()
+                .map(|k| format!("{}:{}", serde_json::to_string(k).unwrap_or_default(), canonical_json(&map[k])))
+                .collect();
+            format!("{{{}}}", entries.join(","))
+        }
+        serde_json::Value::Array(items) => {
+            let entries: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", entries.join(","))
+        }
+        other => serde_json::to_string(other).unwrap_or_default(),
+    }
 }
 
 /// Parse a `NodeScale` from a string.
@@ -276,4 +627,169 @@ mod tests {
         let bad_json = serde_json::json!({ "roots": ["x"] });
         assert!(load_from_json(&bad_json).is_err());
     }
+
+    #[test]
+    fn test_root_hash_stable_under_metadata_key_order() {
+        let json_a = serde_json::json!({
+            "nodes": [
+                { "id": "root", "name": "GentlyOS", "scale": "System", "children": [],
+                  "metadata": { "a": 1, "b": 2 } }
+            ],
+            "roots": ["root"]
+        });
+        let json_b = serde_json::json!({
+            "nodes": [
+                { "id": "root", "name": "GentlyOS", "scale": "System", "children": [],
+                  "metadata": { "b": 2, "a": 1 } }
+            ],
+            "roots": ["root"]
+        });
+
+        let tree_a = load_from_json(&json_a).expect("should parse tree");
+        let tree_b = load_from_json(&json_b).expect("should parse tree");
+        assert_eq!(tree_a.root_hash().unwrap(), tree_b.root_hash().unwrap());
+    }
+
+    #[test]
+    fn test_diff_detects_changed_leaf() {
+        let json = sample_tree_json();
+        let tree_a = load_from_json(&json).expect("should parse tree");
+
+        let mut json_b = sample_tree_json();
+        json_b["nodes"][3]["metadata"] = serde_json::json!({ "keywords": ["threat"] });
+        let tree_b = load_from_json(&json_b).expect("should parse tree");
+
+        let changed = tree_a.diff(&tree_b).unwrap();
+        assert!(changed.contains(&"fafo".to_string()));
+        assert!(changed.contains(&"security".to_string()));
+        assert!(changed.contains(&"root".to_string()));
+        assert!(!changed.contains(&"search".to_string()));
+        assert!(!changed.contains(&"berlin".to_string()));
+    }
+
+    #[test]
+    fn test_diff_empty_for_identical_trees() {
+        let json = sample_tree_json();
+        let tree_a = load_from_json(&json).expect("should parse tree");
+        let tree_b = load_from_json(&json).expect("should parse tree");
+        assert!(tree_a.diff(&tree_b).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_missing_child_is_an_error_not_a_panic() {
+        let json = serde_json::json!({
+            "nodes": [
+                { "id": "root", "name": "GentlyOS", "scale": "System",
+                  "children": ["ghost"], "metadata": {} }
+            ],
+            "roots": ["root"]
+        });
+        let tree = load_from_json(&json).expect("should parse tree");
+        assert!(tree.root_hash().is_err());
+    }
+
+    #[test]
+    fn test_cycle_is_an_error_not_a_panic() {
+        let mut nodes = HashMap::new();
+        nodes.insert(
+            "a".to_string(),
+            UniversalNode {
+                id: "a".to_string(),
+                name: "A".to_string(),
+                scale: NodeScale::Module,
+                children: vec!["b".to_string()],
+                metadata: serde_json::Value::Null,
+            },
+        );
+        nodes.insert(
+            "b".to_string(),
+            UniversalNode {
+                id: "b".to_string(),
+                name: "B".to_string(),
+                scale: NodeScale::Module,
+                children: vec!["a".to_string()],
+                metadata: serde_json::Value::Null,
+            },
+        );
+        let tree = Tree { nodes, root_ids: vec!["a".to_string()] };
+        assert!(tree.root_hash().is_err());
+    }
+
+    #[test]
+    fn test_validate_reports_missing_child() {
+        let json = serde_json::json!({
+            "nodes": [
+                { "id": "root", "name": "GentlyOS", "scale": "System",
+                  "children": ["ghost"], "metadata": {} }
+            ],
+            "roots": ["root"]
+        });
+        let tree = load_from_json(&json).expect("should parse tree");
+        let issues = tree.validate();
+        assert!(issues.iter().any(|i| i.contains("missing child")));
+    }
+
+    #[test]
+    fn test_validate_reports_unreachable_node() {
+        let mut json = sample_tree_json();
+        json["nodes"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!({
+                "id": "orphan", "name": "Orphan", "scale": "Module",
+                "children": [], "metadata": {}
+            }));
+        let tree = load_from_json(&json).expect("should parse tree");
+        let issues = tree.validate();
+        assert!(issues.iter().any(|i| i.contains("orphan") && i.contains("unreachable")));
+    }
+
+    #[test]
+    fn test_validate_clean_tree_has_no_issues() {
+        let json = sample_tree_json();
+        let tree = load_from_json(&json).expect("should parse tree");
+        assert!(tree.validate().is_empty());
+    }
+
+    #[test]
+    fn test_ancestors_and_descendants() {
+        let json = sample_tree_json();
+        let tree = load_from_json(&json).expect("should parse tree");
+
+        let mut ancestors = tree.ancestors("fafo");
+        ancestors.sort();
+        assert_eq!(ancestors, vec!["root".to_string(), "security".to_string()]);
+        let mut descendants = tree.descendants("root");
+        descendants.sort();
+        let mut expected = vec!["security", "search", "fafo", "berlin"];
+        expected.sort();
+        assert_eq!(descendants, expected);
+    }
+
+    #[test]
+    fn test_path_to() {
+        let json = sample_tree_json();
+        let tree = load_from_json(&json).expect("should parse tree");
+        assert_eq!(tree.path_to("fafo"), vec!["root", "security", "fafo"]);
+        assert!(tree.path_to("nonexistent").is_empty());
+    }
+
+    #[test]
+    fn test_find_by_scale() {
+        let json = sample_tree_json();
+        let tree = load_from_json(&json).expect("should parse tree");
+        let mut modules = tree.find_by_scale(NodeScale::Module);
+        modules.sort();
+        assert_eq!(modules, vec!["berlin".to_string(), "fafo".to_string()]);
+    }
+
+    #[test]
+    fn test_search_ranks_by_keyword_overlap() {
+        let json = sample_tree_json();
+        let tree = load_from_json(&json).expect("should parse tree");
+        let results = tree.search("crypto key");
+        assert_eq!(results[0].0, "berlin");
+        assert_eq!(results[0].1, 2.0);
+        assert!(tree.search("nonexistentterm").is_empty());
+    }
 }
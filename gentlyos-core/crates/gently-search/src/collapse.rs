@@ -23,12 +23,12 @@
 //! - **COLLAPSE**: Remove dimensions from output (aggregate)
 //! - **ENUMERATE**: Expand dimensions into columns
 
-use crate::hyperspace::{Dimension, DimensionFilter, DimensionValue, FilterOp, HyperspaceQuery};
+use crate::hyperspace::{Aggregate, Dimension, DimensionFilter, DimensionValue, FilterOp, HyperspaceQuery};
 use gently_alexandria::ConceptId;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// A single row in a collapsed result table
@@ -36,6 +36,10 @@ use uuid::Uuid;
 pub struct CollapsedRow {
     /// Values for each enumerated dimension
     pub values: HashMap<Dimension, String>,
+    /// Synthetic columns produced by rolling up a collapsed dimension, keyed
+    /// by name (e.g. "Where_count").
+    #[serde(default)]
+    pub aggregates: HashMap<String, String>,
     /// Source concept IDs that contributed to this row
     pub source_concepts: Vec<ConceptId>,
     /// Quality score (from inference, if available)
@@ -49,6 +53,7 @@ impl CollapsedRow {
     pub fn new() -> Self {
         Self {
             values: HashMap::new(),
+            aggregates: HashMap::new(),
             source_concepts: Vec::new(),
             quality_score: 0.0,
             created_at: Utc::now(),
@@ -79,13 +84,90 @@ impl Default for CollapsedRow {
     }
 }
 
-/// Cryptographic proof of collapse operation
+/// One step of a Merkle audit path: the sibling hash needed to recompute the
+/// parent, and which side of the parent it sits on.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MerkleStep {
+    pub sibling: [u8; 32],
+    pub sibling_is_left: bool,
+}
+
+/// Sibling-hash audit path from a single row's leaf up to the Merkle root.
+pub type MerklePath = Vec<MerkleStep>;
+
+/// Domain tag prefixed onto leaf hashes, so a leaf hash can never collide
+/// with an internal-node hash (see `hash_pair`'s `0x01` tag) - without this,
+/// a crafted `CollapsedRow` whose `hash_row` happens to equal some
+/// `hash_pair(a, b)` could be substituted for an internal node (or vice
+/// versa) without `verify`/`verify_row` noticing. This is the same
+/// second-preimage weakness CVE-2012-2459 exploited in Bitcoin's Merkle
+/// trees.
+const LEAF_DOMAIN_TAG: u8 = 0x00;
+/// Domain tag prefixed onto internal-node hashes; see `LEAF_DOMAIN_TAG`.
+const NODE_DOMAIN_TAG: u8 = 0x01;
+
+/// Hash a single `CollapsedRow` into a Merkle leaf. Dimension/value pairs
+/// are sorted by `Dimension` first so the hash doesn't depend on `HashMap`
+/// iteration order.
+fn hash_row(row: &CollapsedRow) -> [u8; 32] {
+    let mut entries: Vec<(&Dimension, &String)> = row.values.iter().collect();
+    entries.sort_by_key(|(dim, _)| **dim);
+
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_DOMAIN_TAG]);
+    for (dim, val) in entries {
+        hasher.update(format!("{:?}:{}", dim, val).as_bytes());
+    }
+    let result = hasher.finalize();
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&result);
+    hash
+}
+
+fn hash_pair(left: [u8; 32], right: [u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_DOMAIN_TAG]);
+    hasher.update(left);
+    hasher.update(right);
+    let result = hasher.finalize();
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&result);
+    hash
+}
+
+/// Fold one level of a Merkle tree into the next, duplicating the last node
+/// when the level has an odd count (standard Merkle padding).
+fn merkle_level_up(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level.chunks(2)
+        .map(|pair| {
+            let left = pair[0];
+            let right = if pair.len() == 2 { pair[1] } else { pair[0] };
+            hash_pair(left, right)
+        })
+        .collect()
+}
+
+fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    if leaves.is_empty() {
+        return [0u8; 32];
+    }
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = merkle_level_up(&level);
+    }
+    level[0]
+}
+
+/// Cryptographic proof of a collapse operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CollapseProof {
     /// Hash of the query
     pub query_hash: [u8; 32],
-    /// Hash of the result
+    /// Merkle root over every result row's leaf hash
     pub result_hash: [u8; 32],
+    /// Per-row leaf hashes, in result order, kept so a caller can generate
+    /// `row_proof` audit paths without re-hashing the original rows.
+    pub leaf_hashes: Vec<[u8; 32]>,
     /// Timestamp
     pub timestamp: DateTime<Utc>,
     /// Number of source concepts
@@ -98,11 +180,13 @@ impl CollapseProof {
     /// Create a new proof from query and result
     pub fn new(query: &HyperspaceQuery, rows: &[CollapsedRow]) -> Self {
         let query_hash = Self::hash_query(query);
-        let result_hash = Self::hash_rows(rows);
+        let leaf_hashes: Vec<[u8; 32]> = rows.iter().map(hash_row).collect();
+        let result_hash = merkle_root(&leaf_hashes);
 
         Self {
             query_hash,
             result_hash,
+            leaf_hashes,
             timestamp: Utc::now(),
             source_count: rows.iter().map(|r| r.source_concepts.len()).sum(),
             row_count: rows.len(),
@@ -129,23 +213,56 @@ impl CollapseProof {
         hash
     }
 
-    fn hash_rows(rows: &[CollapsedRow]) -> [u8; 32] {
-        let mut hasher = Sha256::new();
-        for row in rows {
-            for (dim, val) in &row.values {
-                hasher.update(format!("{:?}:{}", dim, val).as_bytes());
-            }
+    /// Verify the proof's Merkle root matches a result
+    pub fn verify(&self, rows: &[CollapsedRow]) -> bool {
+        let leaves: Vec<[u8; 32]> = rows.iter().map(hash_row).collect();
+        merkle_root(&leaves) == self.result_hash
+    }
+
+    /// Build the sibling-hash audit path from row `index` up to the root, so
+    /// a client that only trusts `result_hash` can validate that one row (or
+    /// a streamed subset) belongs to the committed result without being
+    /// shipped every row.
+    pub fn row_proof(&self, index: usize) -> Option<MerklePath> {
+        if index >= self.leaf_hashes.len() {
+            return None;
         }
-        let result = hasher.finalize();
-        let mut hash = [0u8; 32];
-        hash.copy_from_slice(&result);
-        hash
+
+        let mut path = Vec::new();
+        let mut level = self.leaf_hashes.clone();
+        let mut idx = index;
+
+        while level.len() > 1 {
+            let sibling_idx = idx ^ 1;
+            let sibling = if sibling_idx < level.len() { level[sibling_idx] } else { level[idx] };
+            path.push(MerkleStep {
+                sibling,
+                sibling_is_left: idx % 2 == 1,
+            });
+
+            level = merkle_level_up(&level);
+            idx /= 2;
+        }
+
+        Some(path)
     }
 
-    /// Verify the proof matches a result
-    pub fn verify(&self, rows: &[CollapsedRow]) -> bool {
-        let computed = Self::hash_rows(rows);
-        self.result_hash == computed
+    /// Recompute a row's audit path against `root`, returning whether it
+    /// proves membership without needing any of the other rows. `_index`
+    /// (the row's original position) isn't needed for the recomputation
+    /// itself — `path`'s `sibling_is_left` flags already carry the
+    /// left/right order at each level — but is kept in the signature since
+    /// callers naturally have it alongside the path from `row_proof`.
+    pub fn verify_row(root: [u8; 32], row: &CollapsedRow, _index: usize, path: &MerklePath) -> bool {
+        let mut hash = hash_row(row);
+        for step in path {
+            hash = if step.sibling_is_left {
+                hash_pair(step.sibling, hash)
+            } else {
+                hash_pair(hash, step.sibling)
+            };
+        }
+        hash == root
     }
 }
 
@@ -247,10 +364,23 @@ pub struct CollapseStats {
     pub concepts_filtered: usize,
     /// Unique rows generated
     pub rows_generated: usize,
+    /// Distinct groups formed by collapsing, before `max_rows` truncation
+    pub groups_before: usize,
+    /// Rows actually emitted, after `max_rows` truncation
+    pub rows_after: usize,
     /// Dimensions collapsed
     pub dimensions_collapsed: usize,
     /// Dimensions enumerated
     pub dimensions_enumerated: usize,
+    /// How many pinned dimensions were resolved via a prebuilt index in
+    /// `collapse_indexed`, rather than a full scan. Always 0 for `collapse`.
+    pub index_hits: usize,
+    /// Total rows matched by the query (after filtering and grouping), as
+    /// opposed to `rows_after` which is just this page/call's row count.
+    /// Equal to `rows_after` for `collapse`/`collapse_indexed`, but for
+    /// `collapse_paged` reflects the whole result so a caller can tell how
+    /// many pages remain.
+    pub total_matched: usize,
     /// Average quality score
     pub avg_quality: f32,
     /// Processing time in milliseconds
@@ -258,6 +388,7 @@ pub struct CollapseStats {
 }
 
 /// Engine for collapsing 5W queries into tables
+#[derive(Debug, Clone, Copy)]
 pub struct CollapseEngine {
     /// Minimum quality score to include
     quality_threshold: f32,
@@ -299,34 +430,122 @@ impl CollapseEngine {
     pub fn collapse(&self, query: &HyperspaceQuery, data: &[CollapsedRow]) -> CollapseResult {
         let start = std::time::Instant::now();
 
-        // Apply filters
         let filtered: Vec<CollapsedRow> = data.iter()
             .filter(|row| self.matches_query(row, query))
             .filter(|row| row.quality_score >= self.quality_threshold)
             .cloned()
-            .take(self.max_rows)
             .collect();
 
+        self.finish(query, filtered, data.len(), 0, start)
+    }
+
+    /// Collapse a query using prebuilt per-dimension value→row-index indexes
+    /// (`index[dim][value]` gives the row indices where `dim` has `value`).
+    /// Each pinned dimension's candidate set is looked up directly instead of
+    /// scanning `data`, and the sets are intersected starting from the
+    /// smallest (the semi-join driving side) before any `CollapsedRow` is
+    /// cloned. Only the remaining `FILTER` predicates are then evaluated
+    /// against the surviving candidates. Falls back to a full scan when the
+    /// query has no pins to drive the lookup.
+    pub fn collapse_indexed(
+        &self,
+        query: &HyperspaceQuery,
+        data: &[CollapsedRow],
+        index: &HashMap<Dimension, HashMap<String, Vec<usize>>>,
+    ) -> CollapseResult {
+        let start = std::time::Instant::now();
+
+        let (candidates, index_hits) = self.candidate_indices(query, data, index);
+        let filtered: Vec<CollapsedRow> = candidates.iter()
+            .map(|&i| &data[i])
+            .filter(|row| self.matches_filters(row, query))
+            .filter(|row| row.quality_score >= self.quality_threshold)
+            .cloned()
+            .collect();
+
+        self.finish(query, filtered, data.len(), index_hits, start)
+    }
+
+    /// Resolve `query.pin` against `index`, intersecting per-dimension
+    /// candidate row-index sets starting from the smallest. Returns the
+    /// surviving row indices (sorted) and how many pinned dimensions were
+    /// actually serviced by the index. A pinned value absent from the index
+    /// means no row can match, so that short-circuits to an empty result.
+    /// With no pins at all, every row is a candidate (nothing to intersect).
+    fn candidate_indices(
+        &self,
+        query: &HyperspaceQuery,
+        data: &[CollapsedRow],
+        index: &HashMap<Dimension, HashMap<String, Vec<usize>>>,
+    ) -> (Vec<usize>, usize) {
+        let mut sets: Vec<&Vec<usize>> = Vec::new();
+        for (dim, pin_value) in &query.pin {
+            match index.get(dim).and_then(|by_value| by_value.get(&pin_value.value)) {
+                Some(rows) => sets.push(rows),
+                None => return (Vec::new(), 0),
+            }
+        }
+
+        if sets.is_empty() {
+            return ((0..data.len()).collect(), 0);
+        }
+
+        sets.sort_by_key(|s| s.len());
+        let index_hits = sets.len();
+
+        let mut candidates: HashSet<usize> = sets[0].iter().copied().collect();
+        for set in &sets[1..] {
+            let next: HashSet<usize> = set.iter().copied().collect();
+            candidates = candidates.intersection(&next).copied().collect();
+        }
+
+        let mut candidates: Vec<usize> = candidates.into_iter().collect();
+        candidates.sort_unstable();
+        (candidates, index_hits)
+    }
+
+    /// Build the final `CollapseResult`/`CollapseStats` from an already
+    /// pin-and-filter-matched, quality-thresholded set of rows. Shared by
+    /// `collapse` and `collapse_indexed`, which differ only in how they
+    /// arrive at `filtered`.
+    fn finish(
+        &self,
+        query: &HyperspaceQuery,
+        filtered: Vec<CollapsedRow>,
+        concepts_searched: usize,
+        index_hits: usize,
+        start: std::time::Instant,
+    ) -> CollapseResult {
+        let concepts_filtered = filtered.len();
+        let groups = self.group_rows(query, filtered);
+        let groups_before = groups.len();
+        let rows: Vec<CollapsedRow> = groups.into_iter().take(self.max_rows).collect();
+        let rows_after = rows.len();
+
         // Generate BONE if we have a PIN
-        let new_bone = if self.generate_bones && !query.pin.is_empty() && !filtered.is_empty() {
-            Some(self.generate_bone(query, &filtered))
+        let new_bone = if self.generate_bones && !query.pin.is_empty() && !rows.is_empty() {
+            Some(self.generate_bone(query, &rows))
         } else {
             None
         };
 
-        let proof = CollapseProof::new(query, &filtered);
-        let avg_quality = if filtered.is_empty() {
+        let proof = CollapseProof::new(query, &rows);
+        let avg_quality = if rows.is_empty() {
             0.0
         } else {
-            filtered.iter().map(|r| r.quality_score).sum::<f32>() / filtered.len() as f32
+            rows.iter().map(|r| r.quality_score).sum::<f32>() / rows.len() as f32
         };
 
         let stats = CollapseStats {
-            concepts_searched: data.len(),
-            concepts_filtered: filtered.len(),
-            rows_generated: filtered.len(),
+            concepts_searched,
+            concepts_filtered,
+            rows_generated: rows_after,
+            groups_before,
+            rows_after,
             dimensions_collapsed: query.collapse.len(),
             dimensions_enumerated: query.enumerate.len(),
+            index_hits,
+            total_matched: rows_after,
             avg_quality,
             processing_ms: start.elapsed().as_millis() as u64,
         };
@@ -334,7 +553,7 @@ impl CollapseEngine {
         CollapseResult {
             id: Uuid::new_v4(),
             columns: query.enumerate.clone(),
-            rows: filtered,
+            rows,
             new_bone,
             proof,
             source_query: query.natural_source.clone(),
@@ -342,6 +561,107 @@ impl CollapseEngine {
         }
     }
 
+    /// Collapse a query into a `CollapseCursor` that yields `CollapseResult`
+    /// pages of up to `page_size` rows instead of materializing the whole
+    /// result at once. Filtering and grouping still run eagerly (grouping
+    /// needs every matching row to form its groups), but `max_rows` no
+    /// longer applies — paging exists precisely so a caller can walk past a
+    /// result larger than any single `collapse()` call would return.
+    pub fn collapse_paged(
+        &self,
+        query: &HyperspaceQuery,
+        data: &[CollapsedRow],
+        page_size: usize,
+    ) -> CollapseCursor {
+        let filtered: Vec<CollapsedRow> = data.iter()
+            .filter(|row| self.matches_query(row, query))
+            .filter(|row| row.quality_score >= self.quality_threshold)
+            .cloned()
+            .collect();
+
+        let concepts_filtered = filtered.len();
+        let rows = self.group_rows(query, filtered);
+        let query_hash = CollapseProof::hash_query(query);
+
+        CollapseCursor {
+            engine: *self,
+            query: query.clone(),
+            query_hash,
+            rows,
+            page_size: page_size.max(1),
+            offset: 0,
+            concepts_searched: data.len(),
+            concepts_filtered,
+        }
+    }
+
+    /// Group rows by the tuple of their `enumerate` dimension values,
+    /// folding each group into one output row: `source_concepts` are
+    /// unioned, `quality_score` becomes the group mean, and each dimension
+    /// in `query.collapse` is rolled up into a synthetic `{Dim}_{agg}`
+    /// column via its configured `Aggregate` (default `Count`). With no
+    /// collapsed dimensions this is a no-op pass-through, matching the
+    /// pre-aggregation behavior for queries that don't use COLLAPSE.
+    fn group_rows(&self, query: &HyperspaceQuery, rows: Vec<CollapsedRow>) -> Vec<CollapsedRow> {
+        if query.collapse.is_empty() {
+            return rows;
+        }
+
+        let mut groups: Vec<(Vec<(Dimension, String)>, Vec<CollapsedRow>)> = Vec::new();
+        for row in rows {
+            let key: Vec<(Dimension, String)> = query.enumerate.iter()
+                .map(|d| (*d, row.values.get(d).cloned().unwrap_or_default()))
+                .collect();
+
+            if let Some((_, members)) = groups.iter_mut().find(|(k, _)| *k == key) {
+                members.push(row);
+            } else {
+                groups.push((key, vec![row]));
+            }
+        }
+
+        groups.into_iter()
+            .map(|(key, members)| self.fold_group(query, key, members))
+            .collect()
+    }
+
+    /// Fold one group of rows sharing the same `enumerate` values into a
+    /// single `CollapsedRow`.
+    fn fold_group(
+        &self,
+        query: &HyperspaceQuery,
+        key: Vec<(Dimension, String)>,
+        members: Vec<CollapsedRow>,
+    ) -> CollapsedRow {
+        let mut out = CollapsedRow::new();
+        for (dim, value) in key {
+            out.set(dim, value);
+        }
+
+        let mut seen = HashSet::new();
+        for member in &members {
+            for concept in &member.source_concepts {
+                if seen.insert(*concept) {
+                    out.source_concepts.push(*concept);
+                }
+            }
+        }
+
+        out.quality_score = members.iter().map(|m| m.quality_score).sum::<f32>() / members.len() as f32;
+        out.created_at = members.iter().map(|m| m.created_at).max().unwrap_or_else(Utc::now);
+
+        for dim in &query.collapse {
+            let aggregate = query.collapse_aggregates.get(dim).copied().unwrap_or(Aggregate::Count);
+            let values: Vec<&str> = members.iter()
+                .filter_map(|m| m.values.get(dim).map(|s| s.as_str()))
+                .collect();
+            let column = format!("{:?}_{}", dim, aggregate.suffix());
+            out.aggregates.insert(column, aggregate.apply(&values));
+        }
+
+        out
+    }
+
     /// Check if a row matches the query
     fn matches_query(&self, row: &CollapsedRow, query: &HyperspaceQuery) -> bool {
         // Check pinned dimensions
@@ -355,7 +675,13 @@ impl CollapseEngine {
             }
         }
 
-        // Check filters
+        self.matches_filters(row, query)
+    }
+
+    /// Check a row against `query.filter` only (not `query.pin`). Split out
+    /// of `matches_query` so `collapse_indexed` can skip re-checking pins
+    /// that the index already guaranteed for its candidate set.
+    fn matches_filters(&self, row: &CollapsedRow, query: &HyperspaceQuery) -> bool {
         for filter in &query.filter {
             if let Some(row_value) = row.values.get(&filter.dimension) {
                 if !self.matches_filter(row_value, filter) {
@@ -401,6 +727,124 @@ impl Default for CollapseEngine {
     }
 }
 
+/// Opaque resume token for a `CollapseCursor` page: the offset to resume
+/// reading from, plus the `query_hash` it was issued against, so resuming
+/// with a query whose pins, collapsed or enumerated dimensions differ is
+/// rejected instead of silently returning rows from the wrong result set.
+/// `query_hash` does not cover `filter` or `collapse_aggregates`, so a
+/// change to those alone is not detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollapseResumeToken {
+    pub offset: usize,
+    pub query_hash: [u8; 32],
+}
+
+/// Iterator over a collapse result's pages, each yielded as an independent
+/// `CollapseResult` (with its own Merkle-rooted `CollapseProof`) instead of
+/// materializing the whole result up front. Returned by
+/// `CollapseEngine::collapse_paged`.
+pub struct CollapseCursor {
+    engine: CollapseEngine,
+    query: HyperspaceQuery,
+    query_hash: [u8; 32],
+    rows: Vec<CollapsedRow>,
+    page_size: usize,
+    offset: usize,
+    concepts_searched: usize,
+    concepts_filtered: usize,
+}
+
+impl CollapseCursor {
+    /// The resume token for the next page, or `None` once the cursor is
+    /// exhausted.
+    pub fn resume_token(&self) -> Option<CollapseResumeToken> {
+        if self.offset >= self.rows.len() {
+            None
+        } else {
+            Some(CollapseResumeToken {
+                offset: self.offset,
+                query_hash: self.query_hash,
+            })
+        }
+    }
+
+    /// Total rows matched across every page.
+    pub fn total_matched(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Rebuild a cursor over `data` and resume it at `token`'s offset,
+    /// returning `None` if `token.query_hash` doesn't match `query` (the
+    /// query changed since the token was issued, so the offset can no
+    /// longer be trusted to mean the same thing).
+    pub fn resume(
+        engine: &CollapseEngine,
+        query: &HyperspaceQuery,
+        data: &[CollapsedRow],
+        page_size: usize,
+        token: CollapseResumeToken,
+    ) -> Option<CollapseCursor> {
+        let mut cursor = engine.collapse_paged(query, data, page_size);
+        if cursor.query_hash != token.query_hash {
+            return None;
+        }
+        cursor.offset = token.offset.min(cursor.rows.len());
+        Some(cursor)
+    }
+}
+
+impl Iterator for CollapseCursor {
+    type Item = CollapseResult;
+
+    fn next(&mut self) -> Option<CollapseResult> {
+        if self.offset >= self.rows.len() {
+            return None;
+        }
+
+        let end = (self.offset + self.page_size).min(self.rows.len());
+        let page_rows: Vec<CollapsedRow> = self.rows[self.offset..end].to_vec();
+        self.offset = end;
+
+        let rows_after = page_rows.len();
+        let proof = CollapseProof::new(&self.query, &page_rows);
+        let avg_quality = if page_rows.is_empty() {
+            0.0
+        } else {
+            page_rows.iter().map(|r| r.quality_score).sum::<f32>() / page_rows.len() as f32
+        };
+
+        let new_bone = if self.engine.generate_bones && !self.query.pin.is_empty() && !page_rows.is_empty() {
+            Some(self.engine.generate_bone(&self.query, &page_rows))
+        } else {
+            None
+        };
+
+        let stats = CollapseStats {
+            concepts_searched: self.concepts_searched,
+            concepts_filtered: self.concepts_filtered,
+            rows_generated: rows_after,
+            groups_before: self.rows.len(),
+            rows_after,
+            dimensions_collapsed: self.query.collapse.len(),
+            dimensions_enumerated: self.query.enumerate.len(),
+            index_hits: 0,
+            total_matched: self.rows.len(),
+            avg_quality,
+            processing_ms: 0,
+        };
+
+        Some(CollapseResult {
+            id: Uuid::new_v4(),
+            columns: self.query.enumerate.clone(),
+            rows: page_rows,
+            new_bone,
+            proof,
+            source_query: self.query.natural_source.clone(),
+            stats,
+        })
+    }
+}
+
 /// Builder for creating collapsed rows from various sources
 pub struct RowBuilder {
     row: CollapsedRow,
@@ -712,6 +1156,53 @@ mod tests {
         assert_eq!(result.proof.row_count, 1);
     }
 
+    #[test]
+    fn test_leaf_and_node_hashes_are_domain_separated() {
+        // A leaf hash must never land in the same space as an internal-node
+        // hash, even when hashed over the exact same bytes a node's
+        // concatenation would have produced.
+        let row = RowBuilder::new().who("alice").what("a").build();
+        let leaf = hash_row(&row);
+
+        let mut raw = Sha256::new();
+        raw.update(leaf);
+        raw.update(leaf);
+        let mut would_be_node_without_tagging = [0u8; 32];
+        would_be_node_without_tagging.copy_from_slice(&raw.finalize());
+
+        let tagged_node = hash_pair(leaf, leaf);
+        assert_ne!(tagged_node, would_be_node_without_tagging);
+    }
+
+    #[test]
+    fn test_merkle_row_proof_roundtrip() {
+        let data = vec![
+            RowBuilder::new().who("alice").what("a").quality(0.8).build(),
+            RowBuilder::new().who("bob").what("b").quality(0.7).build(),
+            RowBuilder::new().who("carol").what("c").quality(0.6).build(),
+        ];
+
+        let query = crate::hyperspace::HyperspaceQueryBuilder::new()
+            .enumerate_dim(Dimension::Who)
+            .enumerate_dim(Dimension::What)
+            .build();
+
+        let engine = CollapseEngine::new().with_bone_generation(false);
+        let result = engine.collapse(&query, &data);
+
+        for (index, row) in result.rows.iter().enumerate() {
+            let path = result.proof.row_proof(index).expect("row should have a proof");
+            assert!(CollapseProof::verify_row(result.proof.result_hash, row, index, &path));
+        }
+
+        // A row proof should fail to verify against a row it doesn't belong to.
+        let bad_path = result.proof.row_proof(0).unwrap();
+        assert!(!CollapseProof::verify_row(result.proof.result_hash, &result.rows[1], 0, &bad_path));
+
+        // Out-of-range indices have no proof.
+        assert!(result.proof.row_proof(result.rows.len()).is_none());
+    }
+
     #[test]
     fn test_quality_threshold() {
         let data = vec![
@@ -784,4 +1275,219 @@ mod tests {
         assert_eq!(result.stats.dimensions_enumerated, 1);
         assert!((result.stats.avg_quality - 0.7).abs() < 0.01);
     }
+
+    #[test]
+    fn test_collapse_aggregates_grouped_rows() {
+        let data = vec![
+            RowBuilder::new().who("alice").r#where("security").quality(0.8).build(),
+            RowBuilder::new().who("alice").r#where("auth").quality(0.6).build(),
+            RowBuilder::new().who("bob").r#where("security").quality(0.9).build(),
+        ];
+
+        // Two rows for "alice" collapse on WHERE into one grouped row.
+        let query = crate::hyperspace::HyperspaceQueryBuilder::new()
+            .enumerate_dim(Dimension::Who)
+            .collapse_dim_with_aggregate(Dimension::Where, crate::hyperspace::Aggregate::Concat)
+            .build();
+
+        let engine = CollapseEngine::new().with_bone_generation(false);
+        let result = engine.collapse(&query, &data);
+
+        // alice's two rows fold into one, bob's row stays separate.
+        assert_eq!(result.row_count(), 2);
+        assert_eq!(result.stats.groups_before, 2);
+        assert_eq!(result.stats.rows_after, 2);
+
+        let alice_row = result.rows.iter().find(|r| r.get(Dimension::Who) == Some("alice")).unwrap();
+        assert_eq!(alice_row.aggregates.get("Where_concat").unwrap(), "security, auth");
+        assert!((alice_row.quality_score - 0.7).abs() < 0.01);
+    }
+
+    fn build_where_index(data: &[CollapsedRow]) -> HashMap<Dimension, HashMap<String, Vec<usize>>> {
+        let mut by_value: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, row) in data.iter().enumerate() {
+            if let Some(value) = row.get(Dimension::Where) {
+                by_value.entry(value.to_string()).or_default().push(i);
+            }
+        }
+        let mut index = HashMap::new();
+        index.insert(Dimension::Where, by_value);
+        index
+    }
+
+    #[test]
+    fn test_collapse_indexed_matches_scan_and_reports_index_hits() {
+        let data = vec![
+            RowBuilder::new().who("user_001").what("jwt-validation").r#where("security").quality(0.85).build(),
+            RowBuilder::new().who("user_002").what("auth-middleware").r#where("security").quality(0.75).build(),
+            RowBuilder::new().who("user_003").what("logging").r#where("observability").quality(0.8).build(),
+        ];
+        let index = build_where_index(&data);
+
+        let query = crate::hyperspace::HyperspaceQueryBuilder::new()
+            .pin(Dimension::Where, "security")
+            .enumerate_dim(Dimension::Who)
+            .enumerate_dim(Dimension::What)
+            .build();
+
+        let engine = CollapseEngine::new().with_bone_generation(false);
+        let scanned = engine.collapse(&query, &data);
+        let indexed = engine.collapse_indexed(&query, &data, &index);
+
+        assert_eq!(indexed.row_count(), scanned.row_count());
+        assert_eq!(indexed.row_count(), 2);
+        assert_eq!(indexed.stats.index_hits, 1);
+        assert_eq!(scanned.stats.index_hits, 0);
+
+        let indexed_who: std::collections::HashSet<_> =
+            indexed.rows.iter().filter_map(|r| r.get(Dimension::Who)).collect();
+        assert!(indexed_who.contains("user_001"));
+        assert!(indexed_who.contains("user_002"));
+    }
+
+    #[test]
+    fn test_collapse_indexed_unpinned_falls_back_to_full_scan() {
+        let data = vec![
+            RowBuilder::new().who("a").r#where("x").quality(0.8).build(),
+            RowBuilder::new().who("b").r#where("y").quality(0.9).build(),
+        ];
+        let index = build_where_index(&data);
+
+        let query = crate::hyperspace::HyperspaceQueryBuilder::new()
+            .enumerate_dim(Dimension::Who)
+            .build();
+
+        let engine = CollapseEngine::new().with_bone_generation(false);
+        let result = engine.collapse_indexed(&query, &data, &index);
+
+        assert_eq!(result.row_count(), 2);
+        assert_eq!(result.stats.index_hits, 0);
+    }
+
+    #[test]
+    fn test_collapse_indexed_pin_absent_from_index_yields_no_rows() {
+        let data = vec![RowBuilder::new().who("a").r#where("x").quality(0.8).build()];
+        let index = build_where_index(&data);
+
+        let query = crate::hyperspace::HyperspaceQueryBuilder::new()
+            .pin(Dimension::Where, "does-not-exist")
+            .enumerate_dim(Dimension::Who)
+            .build();
+
+        let engine = CollapseEngine::new().with_bone_generation(false);
+        let result = engine.collapse_indexed(&query, &data, &index);
+
+        assert_eq!(result.row_count(), 0);
+    }
+
+    fn paging_dataset() -> Vec<CollapsedRow> {
+        (0..5)
+            .map(|i| {
+                RowBuilder::new()
+                    .who(format!("user_{i}"))
+                    .what("login")
+                    .quality(0.8)
+                    .build()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_collapse_paged_pages_cover_the_same_rows_as_collapse() {
+        let data = paging_dataset();
+        let query = crate::hyperspace::HyperspaceQueryBuilder::new()
+            .enumerate_dim(Dimension::Who)
+            .build();
+
+        let engine = CollapseEngine::new().with_bone_generation(false).with_max_rows(100);
+        let whole = engine.collapse(&query, &data);
+
+        let mut paged_who: Vec<String> = Vec::new();
+        let mut pages = 0;
+        for page in engine.collapse_paged(&query, &data, 2) {
+            assert!(page.rows.len() <= 2);
+            paged_who.extend(page.rows.iter().filter_map(|r| r.get(Dimension::Who)).map(str::to_string));
+            pages += 1;
+        }
+
+        assert_eq!(pages, 3); // 5 rows in pages of 2: 2, 2, 1
+        let mut whole_who: Vec<String> = whole.rows.iter().filter_map(|r| r.get(Dimension::Who)).map(str::to_string).collect();
+        paged_who.sort();
+        whole_who.sort();
+        assert_eq!(paged_who, whole_who);
+    }
+
+    #[test]
+    fn test_collapse_paged_proof_verifies_per_page() {
+        let data = paging_dataset();
+        let query = crate::hyperspace::HyperspaceQueryBuilder::new()
+            .enumerate_dim(Dimension::Who)
+            .build();
+
+        let engine = CollapseEngine::new().with_bone_generation(false);
+        for page in engine.collapse_paged(&query, &data, 2) {
+            assert!(page.proof.verify(&page.rows));
+        }
+    }
+
+    #[test]
+    fn test_collapse_paged_total_matched_is_stable_across_pages() {
+        let data = paging_dataset();
+        let query = crate::hyperspace::HyperspaceQueryBuilder::new()
+            .enumerate_dim(Dimension::Who)
+            .build();
+
+        let engine = CollapseEngine::new().with_bone_generation(false);
+        let mut seen_rows = 0;
+        for page in engine.collapse_paged(&query, &data, 2) {
+            assert_eq!(page.stats.total_matched, 5);
+            seen_rows += page.stats.rows_after;
+        }
+        assert_eq!(seen_rows, 5);
+    }
+
+    #[test]
+    fn test_collapse_cursor_resume_continues_where_it_left_off() {
+        let data = paging_dataset();
+        let query = crate::hyperspace::HyperspaceQueryBuilder::new()
+            .enumerate_dim(Dimension::Who)
+            .build();
+
+        let engine = CollapseEngine::new().with_bone_generation(false);
+        let mut cursor = engine.collapse_paged(&query, &data, 2);
+        let first = cursor.next().expect("first page");
+        let token = cursor.resume_token().expect("resume token after first page");
+
+        let mut resumed = CollapseCursor::resume(&engine, &query, &data, 2, token)
+            .expect("resume should succeed for a matching query");
+        let second_via_resume = resumed.next().expect("resumed page");
+        let second_via_original = cursor.next().expect("original cursor's next page");
+
+        assert_eq!(
+            second_via_resume.rows.iter().filter_map(|r| r.get(Dimension::Who)).collect::<Vec<_>>(),
+            second_via_original.rows.iter().filter_map(|r| r.get(Dimension::Who)).collect::<Vec<_>>(),
+        );
+        assert_ne!(
+            first.rows.iter().filter_map(|r| r.get(Dimension::Who)).collect::<Vec<_>>(),
+            second_via_resume.rows.iter().filter_map(|r| r.get(Dimension::Who)).collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn test_collapse_cursor_resume_rejects_stale_query_hash() {
+        let data = paging_dataset();
+        let query_a = crate::hyperspace::HyperspaceQueryBuilder::new()
+            .enumerate_dim(Dimension::Who)
+            .build();
+        let query_b = crate::hyperspace::HyperspaceQueryBuilder::new()
+            .enumerate_dim(Dimension::What)
+            .build();
+
+        let engine = CollapseEngine::new().with_bone_generation(false);
+        let mut cursor = engine.collapse_paged(&query_a, &data, 2);
+        cursor.next();
+        let token = cursor.resume_token().expect("resume token after first page");
+
+        assert!(CollapseCursor::resume(&engine, &query_b, &data, 2, token).is_none());
+    }
 }
@@ -3,9 +3,10 @@
 //! Bridges Alexandria knowledge graph to BONEBLOB constraint system.
 //! Extracts constraints from search results, Tesseract positions, and domain routing.
 
-use crate::{ContextRouter, SearchResult, ThoughtIndex};
+use crate::{ContextRouter, SearchResult, Thought, ThoughtIndex};
 use gently_alexandria::{ConceptId, HyperPosition};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 /// A constraint rule extracted from Alexandria
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +19,93 @@ pub struct ConstraintRule {
     pub confidence: f32,
     /// Source of this constraint
     pub source: ConstraintSource,
+    /// The thought this constraint was derived from, if any. Set for
+    /// `from_context`'s keyword/tag constraints so `infer_transitive` has
+    /// somewhere to start propagating from; `None` for constraints with no
+    /// natural single-thought origin (domain routing, user input).
+    pub origin_thought: Option<Uuid>,
+}
+
+/// A contradiction between an `Elimination` rule and a softer constraint that
+/// overlaps it on the same concept, found by `ConstraintBuilder::detect_conflicts`.
+/// `Elimination` always wins (confidence 1.0), so `soft` is the one dropped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstraintConflict {
+    /// The elimination that takes precedence
+    pub elimination: ConstraintRule,
+    /// The soft constraint it contradicts and that gets dropped
+    pub soft: ConstraintRule,
+    /// Token-set overlap between the two payloads that triggered the flag
+    pub overlap: f32,
+}
+
+/// Narrows `ConstraintBuilder::filtered`/`build_bones_prompt_filtered` to a
+/// subset of accumulated constraints, e.g. "only Security-domain
+/// eliminations above 0.7 confidence". Unset fields don't filter on that
+/// dimension. Built with `with_*` the same way as `ConstraintBuilder` itself.
+#[derive(Debug, Clone, Default)]
+pub struct ConstraintFilter {
+    domains: Option<Vec<u8>>,
+    sources: Option<Vec<ConstraintSource>>,
+    min_confidence: f32,
+    text: Option<String>,
+}
+
+impl ConstraintFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep only constraints whose `domain` is one of `domains`. Constraints
+    /// with `domain: None` (applies to all domains) are excluded once this
+    /// is set, since they can't be said to match any specific domain.
+    pub fn with_domains(mut self, domains: Vec<u8>) -> Self {
+        self.domains = Some(domains);
+        self
+    }
+
+    /// Keep only constraints whose `source` variant matches one of `sources`.
+    /// For `Inferred`, only the variant is compared, not the chain contents.
+    pub fn with_sources(mut self, sources: Vec<ConstraintSource>) -> Self {
+        self.sources = Some(sources);
+        self
+    }
+
+    /// Keep only constraints with `confidence >= min_confidence`
+    pub fn with_min_confidence(mut self, min_confidence: f32) -> Self {
+        self.min_confidence = min_confidence;
+        self
+    }
+
+    /// Keep only constraints whose `rule` text contains `text` (case-insensitive)
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    fn matches(&self, rule: &ConstraintRule) -> bool {
+        if let Some(domains) = &self.domains {
+            if !rule.domain.is_some_and(|d| domains.contains(&d)) {
+                return false;
+            }
+        }
+        if let Some(sources) = &self.sources {
+            let matches_source = sources.iter()
+                .any(|s| std::mem::discriminant(s) == std::mem::discriminant(&rule.source));
+            if !matches_source {
+                return false;
+            }
+        }
+        if rule.confidence < self.min_confidence {
+            return false;
+        }
+        if let Some(text) = &self.text {
+            if !rule.rule.to_lowercase().contains(&text.to_lowercase()) {
+                return false;
+            }
+        }
+        true
+    }
 }
 
 /// Where a constraint came from
@@ -35,6 +123,10 @@ pub enum ConstraintSource {
     Wormhole,
     /// User-provided
     User,
+    /// Propagated from another thought's constraint across one or more
+    /// wormhole hops, by `ConstraintBuilder::infer_transitive`. Carries the
+    /// chain of thought IDs the derivation passed through, origin first.
+    Inferred { chain: Vec<Uuid> },
 }
 
 impl ConstraintRule {
@@ -44,6 +136,7 @@ impl ConstraintRule {
             domain,
             confidence,
             source: ConstraintSource::Keyword,
+            origin_thought: None,
         }
     }
 
@@ -53,6 +146,7 @@ impl ConstraintRule {
             domain: None,
             confidence: 1.0,
             source: ConstraintSource::Elimination,
+            origin_thought: None,
         }
     }
 
@@ -62,6 +156,7 @@ impl ConstraintRule {
             domain: Some(domain_id),
             confidence: 0.8,
             source: ConstraintSource::Domain,
+            origin_thought: None,
         }
     }
 
@@ -71,6 +166,35 @@ impl ConstraintRule {
             domain: None,
             confidence: 0.9,
             source: ConstraintSource::User,
+            origin_thought: None,
+        }
+    }
+
+    /// Tag this constraint with the thought it was derived from, so
+    /// `infer_transitive` can propagate it across that thought's wormholes.
+    pub fn with_origin(mut self, thought_id: Uuid) -> Self {
+        self.origin_thought = Some(thought_id);
+        self
+    }
+}
+
+/// Per-section caps on how many constraints `build_bones_prompt` emits, once
+/// diversity-aware selection has picked the best non-redundant subset.
+#[derive(Debug, Clone, Copy)]
+pub struct SectionBudgets {
+    pub eliminations: usize,
+    pub domains: usize,
+    pub keywords: usize,
+    pub others: usize,
+}
+
+impl Default for SectionBudgets {
+    fn default() -> Self {
+        Self {
+            eliminations: 10,
+            domains: 3,
+            keywords: 10,
+            others: 5,
         }
     }
 }
@@ -83,6 +207,40 @@ pub struct ConstraintBuilder {
     accumulated: Vec<ConstraintRule>,
     /// Maximum constraints to accumulate
     max_constraints: usize,
+    /// Trade-off between confidence and redundancy in `build_bones_prompt`'s
+    /// MMR-style selection: `confidence - mmr_lambda * max_overlap_with_selected`.
+    /// 0.0 ignores redundancy entirely (back to plain top-N by confidence);
+    /// higher values favor spreading across distinct constraints.
+    mmr_lambda: f32,
+    /// Per-section output caps for `build_bones_prompt`
+    section_budgets: SectionBudgets,
+    /// Whether `build_bones_prompt` appends a "### Resolved Conflicts" note
+    /// listing soft constraints dropped by `detect_conflicts`
+    annotate_conflicts: bool,
+    /// Memoized `context_router.search` results, keyed by normalized query
+    /// plus the `ThoughtIndex` generation they were computed against
+    search_cache: Vec<SearchCacheEntry>,
+    /// Memoized `domain_router.route` results, keyed by normalized query
+    /// (routing doesn't depend on the index, so no generation to track)
+    route_cache: Vec<RouteCacheEntry>,
+    /// Max entries kept in each of `search_cache`/`route_cache` before the
+    /// least-recently-used entry is evicted
+    cache_capacity: usize,
+    cache_hits: usize,
+    cache_misses: usize,
+}
+
+/// One memoized `context_router.search` call, most-recently-used last
+struct SearchCacheEntry {
+    query: String,
+    generation: u64,
+    results: Vec<SearchResult>,
+}
+
+/// One memoized `domain_router.route` call, most-recently-used last
+struct RouteCacheEntry {
+    query: String,
+    routes: Vec<(u8, f32)>,
 }
 
 impl Default for ConstraintBuilder {
@@ -97,6 +255,14 @@ impl ConstraintBuilder {
             context_router: ContextRouter::new(),
             accumulated: Vec::new(),
             max_constraints: 100,
+            mmr_lambda: 0.5,
+            section_budgets: SectionBudgets::default(),
+            annotate_conflicts: false,
+            search_cache: Vec::new(),
+            route_cache: Vec::new(),
+            cache_capacity: 32,
+            cache_hits: 0,
+            cache_misses: 0,
         }
     }
 
@@ -106,6 +272,151 @@ impl ConstraintBuilder {
         self
     }
 
+    /// Set how many distinct queries' search/route results are memoized
+    /// before the least-recently-used entry is evicted
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache_capacity = capacity;
+        self
+    }
+
+    /// Drop all memoized search/route results. Automatically invalidated
+    /// per-entry for `from_context`'s search cache whenever the backing
+    /// `ThoughtIndex`'s `generation()` has advanced since the entry was
+    /// cached; call this directly to force a full refresh. The hit/miss
+    /// counters reported by `stats()` are not reset.
+    pub fn invalidate_cache(&mut self) {
+        self.search_cache.clear();
+        self.route_cache.clear();
+    }
+
+    /// `context_router.search`, memoized by normalized query text and the
+    /// index's `generation()` so an entry is not reused after thoughts or
+    /// wormholes are added to the index. Removing a thought leaves
+    /// `generation()` unchanged, so an entry cached before a removal is
+    /// still reused.
+    fn cached_search(&mut self, query: &str, index: &ThoughtIndex) -> Vec<SearchResult> {
+        let key = normalize_query(query);
+        let generation = index.generation();
+
+        if let Some(pos) = self.search_cache.iter()
+            .position(|e| e.query == key && e.generation == generation)
+        {
+            self.cache_hits += 1;
+            let entry = self.search_cache.remove(pos);
+            let results = entry.results.clone();
+            self.search_cache.push(entry);
+            return results;
+        }
+
+        self.cache_misses += 1;
+        let results = self.context_router.search(query, index, None);
+
+        if self.search_cache.len() >= self.cache_capacity {
+            self.search_cache.remove(0);
+        }
+        self.search_cache.push(SearchCacheEntry {
+            query: key,
+            generation,
+            results: results.clone(),
+        });
+
+        results
+    }
+
+    /// `domain_router.route`, memoized by normalized query text. Routing
+    /// only depends on the query and the router's static domain list, not
+    /// on the `ThoughtIndex`, so there's no generation to invalidate against.
+    fn cached_route(&mut self, query: &str) -> Vec<(u8, f32)> {
+        let key = normalize_query(query);
+
+        if let Some(pos) = self.route_cache.iter().position(|e| e.query == key) {
+            self.cache_hits += 1;
+            let entry = self.route_cache.remove(pos);
+            let routes = entry.routes.clone();
+            self.route_cache.push(entry);
+            return routes;
+        }
+
+        self.cache_misses += 1;
+        let routes = self.context_router.domain_router.route(query);
+
+        if self.route_cache.len() >= self.cache_capacity {
+            self.route_cache.remove(0);
+        }
+        self.route_cache.push(RouteCacheEntry {
+            query: key,
+            routes: routes.clone(),
+        });
+
+        routes
+    }
+
+    /// Set the redundancy penalty used by `build_bones_prompt`'s diversity-aware
+    /// selection (see `mmr_lambda` field docs)
+    pub fn with_mmr_lambda(mut self, lambda: f32) -> Self {
+        self.mmr_lambda = lambda;
+        self
+    }
+
+    /// Set the per-section output caps used by `build_bones_prompt`
+    pub fn with_section_budgets(mut self, budgets: SectionBudgets) -> Self {
+        self.section_budgets = budgets;
+        self
+    }
+
+    /// Enable or disable the "### Resolved Conflicts" note in `build_bones_prompt`
+    pub fn with_conflict_notes(mut self, show: bool) -> Self {
+        self.annotate_conflicts = show;
+        self
+    }
+
+    /// Scan accumulated rules for opposing directives on the same concept:
+    /// an `Elimination` rule whose payload overlaps (token-Jaccard, after
+    /// stripping the directive verb) above a threshold with a `Keyword`,
+    /// `Tag`, or `User` rule of soft-preference polarity. `Elimination`
+    /// always wins (it carries confidence 1.0 by construction), so every
+    /// conflict names the soft constraint that should be dropped.
+    pub fn detect_conflicts(&self) -> Vec<ConstraintConflict> {
+        let all: Vec<&ConstraintRule> = self.accumulated.iter().collect();
+        self.detect_conflicts_among(&all)
+    }
+
+    /// Get constraints matching `f`. See `ConstraintFilter` for the
+    /// dimensions it can narrow on (domain, source, confidence, text).
+    pub fn filtered(&self, f: &ConstraintFilter) -> Vec<&ConstraintRule> {
+        self.accumulated.iter().filter(|c| f.matches(c)).collect()
+    }
+
+    /// Like `detect_conflicts`, but scoped to an arbitrary subset of rules
+    /// (used by `build_bones_prompt_filtered` so a filtered prompt only
+    /// reports conflicts within its own scope).
+    fn detect_conflicts_among(&self, rules: &[&ConstraintRule]) -> Vec<ConstraintConflict> {
+        const CONFLICT_THRESHOLD: f32 = 0.5;
+
+        let eliminations: Vec<&ConstraintRule> = rules.iter().copied()
+            .filter(|c| c.source == ConstraintSource::Elimination)
+            .collect();
+
+        let mut conflicts = Vec::new();
+        for soft in rules.iter().copied().filter(|c| matches!(c.source,
+            ConstraintSource::Keyword | ConstraintSource::Tag | ConstraintSource::User))
+        {
+            let soft_tokens = token_set(strip_verb_prefix(&soft.rule));
+            for elim in &eliminations {
+                let elim_tokens = token_set(strip_verb_prefix(&elim.rule));
+                let overlap = jaccard(&soft_tokens, &elim_tokens);
+                if overlap >= CONFLICT_THRESHOLD {
+                    conflicts.push(ConstraintConflict {
+                        elimination: (*elim).clone(),
+                        soft: soft.clone(),
+                        overlap,
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+
     /// Clear all accumulated constraints
     pub fn clear(&mut self) {
         self.accumulated.clear();
@@ -130,8 +441,8 @@ impl ConstraintBuilder {
 
     /// Build constraints from Alexandria search context
     pub fn from_context(&mut self, query: &str, index: &ThoughtIndex) {
-        // Use router to search relevant thoughts
-        let results = self.context_router.search(query, index, None);
+        // Use router to search relevant thoughts (memoized; see `cached_search`)
+        let results = self.cached_search(query, index);
 
         // Extract constraints from search results
         for result in results.iter().take(10) {
@@ -144,7 +455,7 @@ impl ConstraintBuilder {
                     format!("PREFER: {}", kw),
                     result.score * 0.5,
                     Some(result.thought.shape.domain),
-                ));
+                ).with_origin(result.thought.id));
             }
 
             // Tags with high relevance become constraints
@@ -155,6 +466,7 @@ impl ConstraintBuilder {
                         domain: Some(result.thought.shape.domain),
                         confidence: result.score * 0.4,
                         source: ConstraintSource::Tag,
+                        origin_thought: Some(result.thought.id),
                     });
                 }
             }
@@ -174,6 +486,7 @@ impl ConstraintBuilder {
                         domain: None,
                         confidence: wormhole.similarity,
                         source: ConstraintSource::Wormhole,
+                        origin_thought: Some(result.thought.id),
                     });
                 }
             }
@@ -192,7 +505,7 @@ impl ConstraintBuilder {
 
     /// Build from domain routing
     pub fn from_domain_routing(&mut self, query: &str) {
-        let routes = self.context_router.domain_router.route(query);
+        let routes = self.cached_route(query);
 
         for (domain_id, score) in routes.iter().take(3) {
             if self.accumulated.len() >= self.max_constraints {
@@ -206,42 +519,247 @@ impl ConstraintBuilder {
                 domain: Some(*domain_id),
                 confidence: *score,
                 source: ConstraintSource::Domain,
+                origin_thought: None,
+            });
+        }
+    }
+
+    /// Like `from_context`, but routes the query to its top domains first and
+    /// builds each domain's constraints independently (one OS thread per
+    /// domain) before merging the partial sets back into `accumulated`. Each
+    /// domain gets a fair fractional quota of the remaining capacity
+    /// (`(max_constraints - already accumulated) / num_domains`, at least 1)
+    /// instead of a single dominant domain starving the rest in a linear
+    /// pass; leftover quota from thin domains is redistributed to the domain
+    /// with the most surplus candidates. Identical rules surviving in more
+    /// than one domain's candidate set collapse to their highest-confidence
+    /// instance. Never pushes the total past `max_constraints`.
+    pub fn from_context_decomposed(&mut self, query: &str, index: &ThoughtIndex) {
+        let domain_routes = self.cached_route(query);
+        if domain_routes.is_empty() {
+            self.from_context(query, index);
+            return;
+        }
+
+        let remaining_capacity = self.max_constraints.saturating_sub(self.accumulated.len());
+        if remaining_capacity == 0 {
+            return;
+        }
+
+        let num_domains = domain_routes.len();
+        let base_quota = (remaining_capacity / num_domains).max(1);
+
+        let router = &self.context_router;
+        let per_domain: Vec<(u8, Vec<ConstraintRule>)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = domain_routes.iter()
+                .map(|(domain_id, _)| {
+                    let domain_id = *domain_id;
+                    scope.spawn(move || (domain_id, domain_candidates(router, query, index, domain_id)))
+                })
+                .collect();
+            handles.into_iter()
+                .map(|h| h.join().expect("domain constraint-building thread panicked"))
+                .collect()
+        });
+
+        // Give each domain its base quota, then hand leftover quota from
+        // domains with fewer candidates than their quota to the domain with
+        // the largest surplus of unused candidates.
+        let mut quotas: Vec<usize> = per_domain.iter().map(|_| base_quota).collect();
+        let mut leftover = 0usize;
+        for (i, (_, candidates)) in per_domain.iter().enumerate() {
+            if candidates.len() < quotas[i] {
+                leftover += quotas[i] - candidates.len();
+                quotas[i] = candidates.len();
+            }
+        }
+        if leftover > 0 {
+            if let Some(richest) = per_domain.iter().enumerate()
+                .max_by_key(|(i, (_, candidates))| candidates.len().saturating_sub(quotas[*i]))
+                .map(|(i, _)| i)
+            {
+                let room = per_domain[richest].1.len().saturating_sub(quotas[richest]);
+                quotas[richest] += leftover.min(room);
+            }
+        }
+
+        let mut merged: std::collections::HashMap<String, ConstraintRule> = std::collections::HashMap::new();
+        for (i, (_, candidates)) in per_domain.into_iter().enumerate() {
+            for rule in candidates.into_iter().take(quotas[i]) {
+                merged.entry(rule.rule.clone())
+                    .and_modify(|existing: &mut ConstraintRule| {
+                        if rule.confidence > existing.confidence {
+                            *existing = rule.clone();
+                        }
+                    })
+                    .or_insert(rule);
+            }
+        }
+
+        let mut merged: Vec<ConstraintRule> = merged.into_values().collect();
+        merged.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap());
+        merged.truncate(remaining_capacity);
+
+        self.accumulated.extend(merged);
+    }
+
+    /// Propagate constraints across wormhole edges: if thought A has an
+    /// elimination or high-confidence keyword constraint, and A is
+    /// wormhole-linked to B with similarity `s`, derive the same constraint
+    /// attached to B at confidence `c*s`. Confidences multiply along a
+    /// derivation chain (provenance-semiring combination) and, when the same
+    /// rule can be derived at B via more than one path, the highest-confidence
+    /// path wins. Recursion is capped at `depth` hops, tracking visited
+    /// thoughts per-path so a wormhole cycle can't loop forever.
+    pub fn infer_transitive(&mut self, index: &ThoughtIndex, depth: usize) {
+        const MIN_QUALIFYING_KEYWORD_CONFIDENCE: f32 = 0.7;
+        const MIN_INFERRED_CONFIDENCE: f32 = 0.1;
+
+        struct Frontier {
+            at: Uuid,
+            rule: ConstraintRule,
+            confidence: f32,
+            chain: Vec<Uuid>,
+            visited: std::collections::HashSet<Uuid>,
+        }
+
+        let mut queue: std::collections::VecDeque<Frontier> = self.accumulated.iter()
+            .filter_map(|c| {
+                let origin = c.origin_thought?;
+                let qualifies = c.source == ConstraintSource::Elimination
+                    || (c.source == ConstraintSource::Keyword && c.confidence >= MIN_QUALIFYING_KEYWORD_CONFIDENCE);
+                if !qualifies {
+                    return None;
+                }
+                let mut visited = std::collections::HashSet::new();
+                visited.insert(origin);
+                Some(Frontier {
+                    at: origin,
+                    rule: c.clone(),
+                    confidence: c.confidence,
+                    chain: vec![origin],
+                    visited,
+                })
+            })
+            .collect();
+
+        // Best confidence derived so far for (target thought, rule text), so
+        // alternative derivation paths to the same (thought, rule) combine by
+        // taking the max rather than both being emitted.
+        let mut best: std::collections::HashMap<(Uuid, String), (f32, Vec<Uuid>, ConstraintRule)> =
+            std::collections::HashMap::new();
+
+        while let Some(front) = queue.pop_front() {
+            if front.chain.len() > depth {
+                continue;
+            }
+            for wormhole in index.wormholes().iter().filter(|w| w.connects(front.at)) {
+                let Some(to) = wormhole.other_end(front.at) else { continue };
+                if front.visited.contains(&to) {
+                    continue;
+                }
+
+                let derived_confidence = front.confidence * wormhole.similarity;
+                if derived_confidence < MIN_INFERRED_CONFIDENCE {
+                    continue;
+                }
+
+                let key = (to, front.rule.rule.clone());
+                let improves = best.get(&key).map_or(true, |(existing, ..)| derived_confidence > *existing);
+                if !improves {
+                    continue;
+                }
+
+                let mut chain = front.chain.clone();
+                chain.push(to);
+                best.insert(key, (derived_confidence, chain.clone(), front.rule.clone()));
+
+                if chain.len() <= depth {
+                    let mut visited = front.visited.clone();
+                    visited.insert(to);
+                    queue.push_back(Frontier {
+                        at: to,
+                        rule: front.rule.clone(),
+                        confidence: derived_confidence,
+                        chain,
+                        visited,
+                    });
+                }
+            }
+        }
+
+        for (confidence, chain, rule) in best.into_values() {
+            if self.accumulated.len() >= self.max_constraints {
+                break;
+            }
+            self.accumulated.push(ConstraintRule {
+                rule: rule.rule,
+                domain: rule.domain,
+                confidence,
+                source: ConstraintSource::Inferred { chain },
+                origin_thought: None,
             });
         }
     }
 
     /// Generate BONES preprompt from accumulated constraints
+    ///
+    /// Each section is filled with a diversity-aware (MMR-style) selection
+    /// rather than a plain top-N by confidence, so near-duplicate constraints
+    /// in the same group don't crowd out more informative ones. See
+    /// `select_diverse` and `mmr_lambda`.
     pub fn build_bones_prompt(&self) -> String {
+        let all: Vec<&ConstraintRule> = self.accumulated.iter().collect();
+        self.render_bones_prompt(&all)
+    }
+
+    /// Like `build_bones_prompt`, but only over constraints matching `f`.
+    /// Conflict detection and diversity selection are likewise scoped to
+    /// just the filtered subset, so a narrow filter (e.g. one domain) gives
+    /// a focused prompt rather than one that still reasons about everything
+    /// accumulated.
+    pub fn build_bones_prompt_filtered(&self, f: &ConstraintFilter) -> String {
+        let filtered = self.filtered(f);
+        self.render_bones_prompt(&filtered)
+    }
+
+    fn render_bones_prompt(&self, rules: &[&ConstraintRule]) -> String {
         let mut prompt = String::from("## CONSTRAINTS (from Alexandria Knowledge)\n\n");
 
-        // Sort by confidence
-        let mut sorted: Vec<_> = self.accumulated.iter().collect();
-        sorted.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap());
+        let conflicts = self.detect_conflicts_among(rules);
+        let dropped: std::collections::HashSet<&str> = conflicts.iter()
+            .map(|c| c.soft.rule.as_str())
+            .collect();
 
-        // Group by source
-        let eliminations: Vec<_> = sorted.iter()
+        // Group by source, excluding soft constraints that lost to an elimination
+        let eliminations: Vec<&ConstraintRule> = rules.iter().copied()
             .filter(|c| c.source == ConstraintSource::Elimination)
             .collect();
 
-        let keywords: Vec<_> = sorted.iter()
-            .filter(|c| c.source == ConstraintSource::Keyword)
+        let keywords: Vec<&ConstraintRule> = rules.iter().copied()
+            .filter(|c| c.source == ConstraintSource::Keyword && !dropped.contains(c.rule.as_str()))
             .collect();
 
-        let domains: Vec<_> = sorted.iter()
+        let domains: Vec<&ConstraintRule> = rules.iter().copied()
             .filter(|c| c.source == ConstraintSource::Domain)
             .collect();
 
-        let others: Vec<_> = sorted.iter()
+        let others: Vec<&ConstraintRule> = rules.iter().copied()
             .filter(|c| !matches!(c.source,
                 ConstraintSource::Elimination |
                 ConstraintSource::Keyword |
-                ConstraintSource::Domain))
+                ConstraintSource::Domain) && !dropped.contains(c.rule.as_str()))
             .collect();
 
+        let eliminations = self.select_diverse(&eliminations, self.section_budgets.eliminations);
+        let domains = self.select_diverse(&domains, self.section_budgets.domains);
+        let keywords = self.select_diverse(&keywords, self.section_budgets.keywords);
+        let others = self.select_diverse(&others, self.section_budgets.others);
+
         // Eliminations first (highest priority)
         if !eliminations.is_empty() {
             prompt.push_str("### MUST NOT (Eliminations)\n");
-            for c in eliminations.iter().take(10) {
+            for c in &eliminations {
                 prompt.push_str(&format!("- {}\n", c.rule));
             }
             prompt.push('\n');
@@ -250,7 +768,7 @@ impl ConstraintBuilder {
         // Domain context
         if !domains.is_empty() {
             prompt.push_str("### Domain Context\n");
-            for c in domains.iter().take(3) {
+            for c in &domains {
                 prompt.push_str(&format!("- {}\n", c.rule));
             }
             prompt.push('\n');
@@ -259,7 +777,7 @@ impl ConstraintBuilder {
         // Keywords
         if !keywords.is_empty() {
             prompt.push_str("### Relevant Terms\n");
-            for c in keywords.iter().take(10) {
+            for c in &keywords {
                 prompt.push_str(&format!("- {}\n", c.rule));
             }
             prompt.push('\n');
@@ -268,15 +786,58 @@ impl ConstraintBuilder {
         // Other constraints
         if !others.is_empty() {
             prompt.push_str("### Additional Context\n");
-            for c in others.iter().take(5) {
+            for c in &others {
                 prompt.push_str(&format!("- {}\n", c.rule));
             }
             prompt.push('\n');
         }
 
+        if self.annotate_conflicts && !conflicts.is_empty() {
+            prompt.push_str("### Resolved Conflicts\n");
+            for c in &conflicts {
+                prompt.push_str(&format!(
+                    "- DROPPED \"{}\" (conflicts with \"{}\", {:.0}% overlap)\n",
+                    c.soft.rule, c.elimination.rule, c.overlap * 100.0,
+                ));
+            }
+            prompt.push('\n');
+        }
+
         prompt
     }
 
+    /// Greedily pick up to `budget` rules out of `pool`, maximizing
+    /// `confidence - mmr_lambda * (max overlap with the already-selected set)`
+    /// at each step (a Maximal Marginal Relevance walk over the redundancy
+    /// graph). Identical-text rules are collapsed to their highest-confidence
+    /// instance before selection begins.
+    fn select_diverse<'a>(&self, pool: &[&'a ConstraintRule], budget: usize) -> Vec<&'a ConstraintRule> {
+        let mut best_by_text: std::collections::HashMap<&str, &ConstraintRule> = std::collections::HashMap::new();
+        for c in pool {
+            best_by_text.entry(c.rule.as_str())
+                .and_modify(|existing| if c.confidence > existing.confidence { *existing = c; })
+                .or_insert(c);
+        }
+
+        let mut candidates: Vec<&ConstraintRule> = best_by_text.into_values().collect();
+        candidates.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap());
+
+        let mut selected: Vec<&ConstraintRule> = Vec::new();
+        while selected.len() < budget && !candidates.is_empty() {
+            let (best_idx, _) = candidates.iter().enumerate()
+                .map(|(i, cand)| {
+                    let max_overlap = selected.iter()
+                        .map(|s| redundancy(cand, s))
+                        .fold(0.0f32, f32::max);
+                    (i, cand.confidence - self.mmr_lambda * max_overlap)
+                })
+                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+                .expect("candidates is non-empty");
+            selected.push(candidates.remove(best_idx));
+        }
+        selected
+    }
+
     /// Get constraint statistics
     pub fn stats(&self) -> ConstraintStats {
         ConstraintStats {
@@ -293,6 +854,8 @@ impl ConstraintBuilder {
                 self.accumulated.iter().map(|c| c.confidence).sum::<f32>()
                     / self.accumulated.len() as f32
             },
+            cache_hits: self.cache_hits,
+            cache_misses: self.cache_misses,
         }
     }
 }
@@ -305,6 +868,121 @@ pub struct ConstraintStats {
     pub keywords: usize,
     pub domains: usize,
     pub avg_confidence: f32,
+    /// Query-cache hits across this builder's lifetime (see `ConstraintBuilder::invalidate_cache`)
+    pub cache_hits: usize,
+    /// Query-cache misses across this builder's lifetime
+    pub cache_misses: usize,
+}
+
+/// Normalize a query string for cache-key comparison so that whitespace and
+/// casing differences that don't change the search/route outcome don't count
+/// as distinct cache entries.
+fn normalize_query(query: &str) -> String {
+    query.trim().to_lowercase()
+}
+
+/// Candidate constraints for one domain, sorted by confidence descending, for
+/// `ConstraintBuilder::from_context_decomposed`'s per-domain threads.
+fn domain_candidates(router: &ContextRouter, query: &str, index: &ThoughtIndex, domain_id: u8) -> Vec<ConstraintRule> {
+    let results = router.search(query, index, None);
+    let mut candidates: Vec<ConstraintRule> = results.iter()
+        .take(10)
+        .filter(|r| r.thought.shape.domain == domain_id)
+        .flat_map(constraints_for_result)
+        .collect();
+    candidates.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap());
+    candidates
+}
+
+/// Build the keyword/tag/wormhole constraints one search result contributes,
+/// the same way `ConstraintBuilder::from_context`'s inner loop does, but as a
+/// pure function so `domain_candidates` can run it off the main thread.
+fn constraints_for_result(result: &SearchResult) -> Vec<ConstraintRule> {
+    let mut rules = Vec::new();
+
+    for kw in &result.thought.shape.keywords {
+        rules.push(ConstraintRule::keyword(
+            format!("PREFER: {}", kw),
+            result.score * 0.5,
+            Some(result.thought.shape.domain),
+        ).with_origin(result.thought.id));
+    }
+
+    if result.score > 0.5 {
+        for tag in &result.thought.tags {
+            rules.push(ConstraintRule {
+                rule: format!("CONSIDER: {}", tag),
+                domain: Some(result.thought.shape.domain),
+                confidence: result.score * 0.4,
+                source: ConstraintSource::Tag,
+                origin_thought: Some(result.thought.id),
+            });
+        }
+    }
+
+    for wormhole in &result.wormholes {
+        let method = match &wormhole.detection_method {
+            crate::wormhole::DetectionMethod::KeywordOverlap => "keywords",
+            crate::wormhole::DetectionMethod::DomainMatch => "domain",
+            crate::wormhole::DetectionMethod::EmbeddingSimilarity => "embedding",
+            crate::wormhole::DetectionMethod::UserLinked => "user-link",
+            crate::wormhole::DetectionMethod::SharedReference => "shared-ref",
+        };
+        rules.push(ConstraintRule {
+            rule: format!("RELATED: {} (via {})", wormhole.to_id, method),
+            domain: None,
+            confidence: wormhole.similarity,
+            source: ConstraintSource::Wormhole,
+            origin_thought: Some(result.thought.id),
+        });
+    }
+
+    rules
+}
+
+/// Redundancy weight between two constraint rules for `select_diverse`'s
+/// graph: token-Jaccard overlap of their `rule` text, plus a bonus if they
+/// share both `domain` and `source` (same kind of claim about the same
+/// domain is more redundant than matching text alone would suggest).
+fn redundancy(a: &ConstraintRule, b: &ConstraintRule) -> f32 {
+    const SAME_DOMAIN_AND_SOURCE_BONUS: f32 = 0.15;
+
+    let mut score = jaccard(&token_set(&a.rule), &token_set(&b.rule));
+    if a.source == b.source && a.domain.is_some() && a.domain == b.domain {
+        score += SAME_DOMAIN_AND_SOURCE_BONUS;
+    }
+    score.min(1.0)
+}
+
+/// Strip a rule's leading directive verb (`PREFER:`, `CONSIDER:`, `MUST NOT`,
+/// `NOT:`, ...) so overlap comparisons key on the payload concept rather than
+/// the directive itself.
+fn strip_verb_prefix(rule: &str) -> &str {
+    for prefix in ["PREFER:", "CONSIDER:", "MUST NOT", "NOT:", "DOMAIN:", "RELATED:"] {
+        if let Some(rest) = rule.strip_prefix(prefix) {
+            return rest.trim();
+        }
+    }
+    rule
+}
+
+/// Lowercased, punctuation-stripped token set of a rule's text, for Jaccard
+/// overlap comparisons.
+fn token_set(text: &str) -> std::collections::HashSet<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|tok| !tok.is_empty())
+        .map(|tok| tok.to_string())
+        .collect()
+}
+
+fn jaccard(a: &std::collections::HashSet<String>, b: &std::collections::HashSet<String>) -> f32 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count() as f32;
+    let union = a.union(b).count() as f32;
+    intersection / union
 }
 
 /// Get human-readable name for domain ID
@@ -432,4 +1110,300 @@ mod tests {
         assert_eq!(domain_name_for_id(35), "Security");
         assert_eq!(domain_name_for_id(255), "Unknown");
     }
+
+    #[test]
+    fn test_diverse_selection_prefers_non_redundant_over_near_duplicates() {
+        let mut builder = ConstraintBuilder::new().with_section_budgets(SectionBudgets {
+            eliminations: 10,
+            domains: 3,
+            keywords: 2,
+            others: 5,
+        });
+
+        // Three near-identical keywords should not crowd out the distinct one.
+        builder.accumulated.push(ConstraintRule::keyword("PREFER: rust programming", 0.70, Some(4)));
+        builder.accumulated.push(ConstraintRule::keyword("PREFER: rust language", 0.69, Some(4)));
+        builder.accumulated.push(ConstraintRule::keyword("PREFER: rust code", 0.68, Some(4)));
+        builder.accumulated.push(ConstraintRule::keyword("PREFER: distributed systems", 0.65, Some(4)));
+
+        let prompt = builder.build_bones_prompt();
+        assert!(prompt.contains("rust"));
+        assert!(prompt.contains("distributed systems"));
+    }
+
+    #[test]
+    fn test_diverse_selection_collapses_identical_text_to_highest_confidence() {
+        let mut builder = ConstraintBuilder::new();
+        builder.accumulated.push(ConstraintRule::keyword("PREFER: same text", 0.4, Some(1)));
+        builder.accumulated.push(ConstraintRule::keyword("PREFER: same text", 0.9, Some(1)));
+
+        let selected = builder.select_diverse(
+            &builder.accumulated.iter().collect::<Vec<_>>(),
+            10,
+        );
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].confidence, 0.9);
+    }
+
+    #[test]
+    fn test_empty_accumulator_returns_header_only() {
+        let builder = ConstraintBuilder::new();
+        assert_eq!(builder.build_bones_prompt(), "## CONSTRAINTS (from Alexandria Knowledge)\n\n");
+    }
+
+    #[test]
+    fn test_detect_conflicts_flags_preference_opposing_an_elimination() {
+        let mut builder = ConstraintBuilder::new();
+        builder.accumulated.push(ConstraintRule::elimination("MUST NOT use global mutable state"));
+        builder.accumulated.push(ConstraintRule::keyword("PREFER: global mutable state", 0.8, None));
+        builder.accumulated.push(ConstraintRule::keyword("PREFER: dependency injection", 0.7, None));
+
+        let conflicts = builder.detect_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].soft.rule, "PREFER: global mutable state");
+        assert!(conflicts[0].overlap >= 0.5);
+    }
+
+    #[test]
+    fn test_build_bones_prompt_drops_conflicting_soft_constraint() {
+        let mut builder = ConstraintBuilder::new();
+        builder.accumulated.push(ConstraintRule::elimination("MUST NOT use global mutable state"));
+        builder.accumulated.push(ConstraintRule::keyword("PREFER: global mutable state", 0.8, None));
+
+        let prompt = builder.build_bones_prompt();
+        assert!(!prompt.contains("PREFER: global mutable state"));
+        assert!(prompt.contains("MUST NOT use global mutable state"));
+    }
+
+    #[test]
+    fn test_infer_transitive_propagates_across_a_wormhole() {
+        use crate::index::IndexState;
+        use crate::wormhole::{DetectionMethod, Wormhole};
+
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let wormhole = Wormhole::new(a, b, 0.8, DetectionMethod::KeywordOverlap);
+
+        let index = ThoughtIndex::from_state(IndexState {
+            wormholes: vec![wormhole],
+            ..Default::default()
+        });
+
+        let mut builder = ConstraintBuilder::new();
+        builder.accumulated.push(ConstraintRule::elimination("MUST NOT X").with_origin(a));
+        builder.infer_transitive(&index, 2);
+
+        let inferred = builder.accumulated.iter()
+            .find(|c| matches!(c.source, ConstraintSource::Inferred { .. }))
+            .expect("should derive a constraint for B");
+        assert!((inferred.confidence - 0.8).abs() < 0.001);
+        match &inferred.source {
+            ConstraintSource::Inferred { chain } => assert_eq!(chain, &vec![a, b]),
+            other => panic!("expected Inferred source, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_infer_transitive_respects_depth_cap() {
+        use crate::index::IndexState;
+        use crate::wormhole::{DetectionMethod, Wormhole};
+
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let c = Uuid::new_v4();
+        let w1 = Wormhole::new(a, b, 0.9, DetectionMethod::KeywordOverlap);
+        let w2 = Wormhole::new(b, c, 0.9, DetectionMethod::KeywordOverlap);
+
+        let index = ThoughtIndex::from_state(IndexState {
+            wormholes: vec![w1, w2],
+            ..Default::default()
+        });
+
+        let mut builder = ConstraintBuilder::new();
+        builder.accumulated.push(ConstraintRule::elimination("MUST NOT Y").with_origin(a));
+        builder.infer_transitive(&index, 1);
+
+        let inferred: Vec<_> = builder.accumulated.iter()
+            .filter(|c| matches!(c.source, ConstraintSource::Inferred { .. }))
+            .collect();
+        assert_eq!(inferred.len(), 1);
+    }
+
+    #[test]
+    fn test_build_bones_prompt_annotates_conflicts_when_enabled() {
+        let mut builder = ConstraintBuilder::new().with_conflict_notes(true);
+        builder.accumulated.push(ConstraintRule::elimination("MUST NOT use global mutable state"));
+        builder.accumulated.push(ConstraintRule::keyword("PREFER: global mutable state", 0.8, None));
+
+        let prompt = builder.build_bones_prompt();
+        assert!(prompt.contains("### Resolved Conflicts"));
+        assert!(prompt.contains("PREFER: global mutable state"));
+    }
+
+    fn two_domain_index() -> ThoughtIndex {
+        let mut t1 = Thought::new("build tool");
+        t1.shape.domain = 1;
+        t1.shape.keywords = vec!["build".to_string()];
+        t1.tags = vec!["tagA".to_string()];
+
+        let mut t2 = Thought::new("security scanner");
+        t2.shape.domain = 11;
+        t2.shape.keywords = vec!["security".to_string()];
+        t2.tags = vec!["tagB".to_string()];
+
+        let mut index = ThoughtIndex::new();
+        index.add_thought(t1);
+        index.add_thought(t2);
+        index
+    }
+
+    #[test]
+    fn test_from_context_decomposed_covers_multiple_domains() {
+        let index = two_domain_index();
+        let mut builder = ConstraintBuilder::new();
+        builder.from_context_decomposed("build security", &index);
+
+        let rules: Vec<&str> = builder.constraints().iter().map(|c| c.rule.as_str()).collect();
+        assert!(rules.contains(&"PREFER: build"));
+        assert!(rules.contains(&"PREFER: security"));
+    }
+
+    #[test]
+    fn test_from_context_decomposed_respects_max_constraints() {
+        let index = two_domain_index();
+        let mut builder = ConstraintBuilder::new().with_max_constraints(1);
+        builder.from_context_decomposed("build security", &index);
+
+        assert!(builder.count() <= 1);
+    }
+
+    #[test]
+    fn test_from_context_caches_repeated_query() {
+        let index = two_domain_index();
+        let mut builder = ConstraintBuilder::new();
+
+        builder.from_context("build", &index);
+        let stats = builder.stats();
+        assert_eq!(stats.cache_misses, 1);
+        assert_eq!(stats.cache_hits, 0);
+
+        builder.from_context("BUILD  ", &index);
+        let stats = builder.stats();
+        assert_eq!(stats.cache_misses, 1);
+        assert_eq!(stats.cache_hits, 1);
+    }
+
+    #[test]
+    fn test_from_context_cache_invalidated_when_index_changes() {
+        let mut index = two_domain_index();
+        let mut builder = ConstraintBuilder::new();
+
+        builder.from_context("build", &index);
+        assert_eq!(builder.stats().cache_misses, 1);
+
+        index.add_thought(Thought::new("another thought"));
+        builder.from_context("build", &index);
+
+        let stats = builder.stats();
+        assert_eq!(stats.cache_misses, 2);
+        assert_eq!(stats.cache_hits, 0);
+    }
+
+    #[test]
+    fn test_from_domain_routing_caches_repeated_query() {
+        let mut builder = ConstraintBuilder::new();
+
+        builder.from_domain_routing("security tooling");
+        assert_eq!(builder.stats().cache_misses, 1);
+
+        builder.from_domain_routing("security tooling");
+        let stats = builder.stats();
+        assert_eq!(stats.cache_misses, 1);
+        assert_eq!(stats.cache_hits, 1);
+    }
+
+    #[test]
+    fn test_invalidate_cache_forces_fresh_lookup() {
+        let index = two_domain_index();
+        let mut builder = ConstraintBuilder::new();
+
+        builder.from_context("build", &index);
+        builder.invalidate_cache();
+        builder.from_context("build", &index);
+
+        // Both calls miss since the cache was cleared in between
+        let stats = builder.stats();
+        assert_eq!(stats.cache_misses, 2);
+        assert_eq!(stats.cache_hits, 0);
+    }
+
+    #[test]
+    fn test_filtered_narrows_by_domain_and_confidence() {
+        let mut builder = ConstraintBuilder::new();
+        builder.accumulated.push(ConstraintRule::elimination("bad approach"));
+        builder.accumulated.push(ConstraintRule {
+            domain: Some(35),
+            ..ConstraintRule::elimination("leak secrets")
+        });
+        builder.accumulated.push(ConstraintRule {
+            domain: Some(35),
+            confidence: 0.4,
+            ..ConstraintRule::keyword("weak keyword", 0.4, Some(35))
+        });
+
+        let filter = ConstraintFilter::new()
+            .with_domains(vec![35])
+            .with_sources(vec![ConstraintSource::Elimination])
+            .with_min_confidence(0.7);
+        let matches = builder.filtered(&filter);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].rule, "leak secrets");
+    }
+
+    #[test]
+    fn test_filtered_by_text_substring_is_case_insensitive() {
+        let mut builder = ConstraintBuilder::new();
+        builder.add_user_constraint("Prefer Rust");
+        builder.add_user_constraint("Avoid Python");
+
+        let filter = ConstraintFilter::new().with_text("rust");
+        let matches = builder.filtered(&filter);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].rule, "Prefer Rust");
+    }
+
+    #[test]
+    fn test_build_bones_prompt_filtered_only_includes_matching_rules() {
+        let mut builder = ConstraintBuilder::new();
+        builder.accumulated.push(ConstraintRule {
+            domain: Some(35),
+            ..ConstraintRule::elimination("leak secrets")
+        });
+        builder.accumulated.push(ConstraintRule {
+            domain: Some(1),
+            ..ConstraintRule::elimination("skip tests")
+        });
+
+        let filter = ConstraintFilter::new().with_domains(vec![35]);
+        let prompt = builder.build_bones_prompt_filtered(&filter);
+
+        assert!(prompt.contains("leak secrets"));
+        assert!(!prompt.contains("skip tests"));
+    }
+
+    #[test]
+    fn test_cache_capacity_evicts_oldest_entry() {
+        let mut builder = ConstraintBuilder::new().with_cache_capacity(1);
+
+        builder.from_domain_routing("first query");
+        builder.from_domain_routing("second query");
+        // "first query" should have been evicted to make room for "second query"
+        builder.from_domain_routing("first query");
+
+        let stats = builder.stats();
+        assert_eq!(stats.cache_misses, 3);
+        assert_eq!(stats.cache_hits, 0);
+    }
 }
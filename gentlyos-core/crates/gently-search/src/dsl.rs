@@ -0,0 +1,375 @@
+//! A line-oriented text DSL for building a [`HyperspaceQuery`] without going
+//! through [`HyperspaceQueryBuilder`] in Rust. Meant for config files, a CLI,
+//! or an LLM tool call that only has a string to hand over.
+//!
+//! Each line is one statement; blank lines and lines starting with `#` are
+//! ignored. Dimension names (`WHO`, `WHAT`, `WHERE`, `WHEN`, `WHY`, `HOW`)
+//! are case-insensitive.
+//!
+//! ```text
+//! PIN WHERE = "security"
+//! FILTER WHEN >= 2025-12-01
+//! COLLAPSE [WHERE, WHY]
+//! ENUMERATE [WHO, WHAT, WHEN]
+//! LIMIT 50
+//! ```
+//!
+//! - `PIN <dim> = <value>` pins a dimension to a fixed value.
+//! - `FILTER <dim> <op> <value>` adds a filter, where `<op>` is one of
+//!   `=`, `>=`, `<=`, `contains`.
+//! - `COLLAPSE [<dim>, ...]` marks dimensions to collapse out of the output.
+//! - `ENUMERATE [<dim>, ...]` marks dimensions to keep as output columns.
+//! - `LIMIT <n>` sets the result limit.
+//!
+//! Values may be quoted (`"security"`) or bare (`2025-12-01`); bare values
+//! are accepted so dates and numbers don't need escaping. `parse_query`
+//! stores the original source text in `HyperspaceQuery::natural_source`.
+
+use crate::hyperspace::{Dimension, DimensionFilter, DimensionValue, FilterOp, FilterValue, HyperspaceQuery};
+use thiserror::Error;
+
+/// A DSL parse error with the byte offset into the source where it occurred.
+#[derive(Debug, Error)]
+#[error("{message} (at byte {offset})")]
+pub struct DslError {
+    pub message: String,
+    pub offset: usize,
+}
+
+impl DslError {
+    fn new(message: impl Into<String>, offset: usize) -> Self {
+        Self {
+            message: message.into(),
+            offset,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Keyword(String),
+    Dimension(Dimension),
+    Op(FilterOp),
+    Str(String),
+    Word(String),
+    LBracket,
+    RBracket,
+    Comma,
+}
+
+#[derive(Debug, Clone)]
+struct Spanned {
+    token: Token,
+    offset: usize,
+}
+
+/// Parse DSL source text into a `HyperspaceQuery`, recording `input` as the
+/// query's `natural_source`.
+pub fn parse_query(input: &str) -> Result<HyperspaceQuery, DslError> {
+    let mut query = HyperspaceQuery::new();
+    let mut line_offset = 0usize;
+
+    for raw_line in input.split_inclusive('\n') {
+        let line = raw_line.trim_end_matches('\n').trim_end_matches('\r');
+        let trimmed = line.trim();
+        if !trimmed.is_empty() && !trimmed.starts_with('#') {
+            let leading_ws = line.len() - line.trim_start().len();
+            let tokens = lex_line(trimmed, line_offset + leading_ws)?;
+            apply_statement(&mut query, &tokens)?;
+        }
+        line_offset += raw_line.len();
+    }
+
+    query.natural_source = Some(input.to_string());
+    Ok(query)
+}
+
+fn lex_line(line: &str, base_offset: usize) -> Result<Vec<Spanned>, DslError> {
+    let chars: Vec<(usize, char)> = line
+        .char_indices()
+        .map(|(i, c)| (base_offset + i, c))
+        .collect();
+    let mut idx = 0usize;
+    let mut tokens = Vec::new();
+
+    let peek = |idx: usize| chars.get(idx).copied();
+
+    while let Some((offset, c)) = peek(idx) {
+        if c.is_whitespace() {
+            idx += 1;
+            continue;
+        }
+
+        let token = match c {
+            '[' => {
+                idx += 1;
+                Token::LBracket
+            }
+            ']' => {
+                idx += 1;
+                Token::RBracket
+            }
+            ',' => {
+                idx += 1;
+                Token::Comma
+            }
+            '=' => {
+                idx += 1;
+                Token::Op(FilterOp::Eq)
+            }
+            '>' => {
+                idx += 1;
+                if matches!(peek(idx), Some((_, '='))) {
+                    idx += 1;
+                    Token::Op(FilterOp::Gte)
+                } else {
+                    return Err(DslError::new("bare '>' is not supported, use '>='", offset));
+                }
+            }
+            '<' => {
+                idx += 1;
+                if matches!(peek(idx), Some((_, '='))) {
+                    idx += 1;
+                    Token::Op(FilterOp::Lte)
+                } else {
+                    return Err(DslError::new("bare '<' is not supported, use '<='", offset));
+                }
+            }
+            '"' => {
+                idx += 1;
+                let mut s = String::new();
+                loop {
+                    match peek(idx) {
+                        Some((_, '"')) => {
+                            idx += 1;
+                            break;
+                        }
+                        Some((_, ch)) => {
+                            s.push(ch);
+                            idx += 1;
+                        }
+                        None => return Err(DslError::new("unterminated string literal", offset)),
+                    }
+                }
+                Token::Str(s)
+            }
+            _ if c.is_alphanumeric() || c == '_' || c == '-' || c == ':' || c == '.' => {
+                let mut word = String::new();
+                while let Some((_, ch)) = peek(idx) {
+                    if ch.is_alphanumeric() || ch == '_' || ch == '-' || ch == ':' || ch == '.' {
+                        word.push(ch);
+                        idx += 1;
+                    } else {
+                        break;
+                    }
+                }
+                word_token(&word)
+            }
+            _ => return Err(DslError::new(format!("unexpected character '{c}'"), offset)),
+        };
+
+        tokens.push(Spanned { token, offset });
+    }
+
+    Ok(tokens)
+}
+
+fn word_token(word: &str) -> Token {
+    let upper = word.to_uppercase();
+    match upper.as_str() {
+        "PIN" | "FILTER" | "COLLAPSE" | "ENUMERATE" | "LIMIT" => Token::Keyword(upper),
+        "CONTAINS" => Token::Op(FilterOp::Contains),
+        _ => match dimension_from_word(word) {
+            Some(dim) => Token::Dimension(dim),
+            None => Token::Word(word.to_string()),
+        },
+    }
+}
+
+fn dimension_from_word(s: &str) -> Option<Dimension> {
+    match s.to_lowercase().as_str() {
+        "who" => Some(Dimension::Who),
+        "what" => Some(Dimension::What),
+        "where" => Some(Dimension::Where),
+        "when" => Some(Dimension::When),
+        "why" => Some(Dimension::Why),
+        "how" => Some(Dimension::How),
+        _ => None,
+    }
+}
+
+fn apply_statement(query: &mut HyperspaceQuery, tokens: &[Spanned]) -> Result<(), DslError> {
+    let Some(first) = tokens.first() else {
+        return Ok(());
+    };
+    let Token::Keyword(keyword) = &first.token else {
+        return Err(DslError::new(
+            "expected a statement keyword (PIN, FILTER, COLLAPSE, ENUMERATE, LIMIT)",
+            first.offset,
+        ));
+    };
+
+    match keyword.as_str() {
+        "PIN" => apply_pin(query, tokens),
+        "FILTER" => apply_filter(query, tokens),
+        "COLLAPSE" => apply_dim_list(tokens, |dims| query.collapse.extend(dims)),
+        "ENUMERATE" => apply_dim_list(tokens, |dims| query.enumerate.extend(dims)),
+        "LIMIT" => apply_limit(query, tokens),
+        _ => unreachable!("word_token only produces known keywords"),
+    }
+}
+
+fn apply_pin(query: &mut HyperspaceQuery, tokens: &[Spanned]) -> Result<(), DslError> {
+    let dim = expect_dimension(tokens, 1)?;
+    expect_eq(tokens, 2)?;
+    let value = expect_value(tokens, 3)?;
+    query.pin.insert(dim, DimensionValue::new(&value));
+    Ok(())
+}
+
+fn apply_filter(query: &mut HyperspaceQuery, tokens: &[Spanned]) -> Result<(), DslError> {
+    let dim = expect_dimension(tokens, 1)?;
+    let operator = expect_any_op(tokens, 2)?;
+    let value = expect_value(tokens, 3)?;
+    query.filter.push(DimensionFilter {
+        dimension: dim,
+        operator,
+        value: FilterValue::String(value),
+    });
+    Ok(())
+}
+
+fn apply_dim_list(
+    tokens: &[Spanned],
+    mut sink: impl FnMut(Vec<Dimension>),
+) -> Result<(), DslError> {
+    let Some(open) = tokens.get(1) else {
+        return Err(DslError::new(
+            "expected '[' after statement keyword",
+            tokens[0].offset,
+        ));
+    };
+    if !matches!(open.token, Token::LBracket) {
+        return Err(DslError::new("expected '['", open.offset));
+    }
+
+    let Some(close_idx) = tokens.iter().position(|t| matches!(t.token, Token::RBracket)) else {
+        return Err(DslError::new("missing closing ']'", open.offset));
+    };
+
+    let mut dims = Vec::new();
+    for item in &tokens[2..close_idx] {
+        match &item.token {
+            Token::Dimension(d) => dims.push(*d),
+            Token::Comma => {}
+            _ => return Err(DslError::new("expected a dimension name or ','", item.offset)),
+        }
+    }
+
+    sink(dims);
+    Ok(())
+}
+
+fn apply_limit(query: &mut HyperspaceQuery, tokens: &[Spanned]) -> Result<(), DslError> {
+    let Some(spanned) = tokens.get(1) else {
+        return Err(DslError::new("expected a number after LIMIT", tokens[0].offset));
+    };
+    let Token::Word(w) = &spanned.token else {
+        return Err(DslError::new("expected a number after LIMIT", spanned.offset));
+    };
+    let limit = w
+        .parse::<usize>()
+        .map_err(|_| DslError::new(format!("'{w}' is not a valid limit"), spanned.offset))?;
+    query.limit = limit;
+    Ok(())
+}
+
+fn expect_dimension(tokens: &[Spanned], idx: usize) -> Result<Dimension, DslError> {
+    match tokens.get(idx) {
+        Some(Spanned { token: Token::Dimension(d), .. }) => Ok(*d),
+        Some(spanned) => Err(DslError::new("expected a dimension name (WHO/WHAT/WHERE/WHEN/WHY/HOW)", spanned.offset)),
+        None => Err(DslError::new("expected a dimension name", tokens[0].offset)),
+    }
+}
+
+fn expect_eq(tokens: &[Spanned], idx: usize) -> Result<(), DslError> {
+    match tokens.get(idx) {
+        Some(Spanned { token: Token::Op(FilterOp::Eq), .. }) => Ok(()),
+        Some(spanned) => Err(DslError::new("expected '='", spanned.offset)),
+        None => Err(DslError::new("expected '='", tokens[0].offset)),
+    }
+}
+
+fn expect_any_op(tokens: &[Spanned], idx: usize) -> Result<FilterOp, DslError> {
+    match tokens.get(idx) {
+        Some(Spanned { token: Token::Op(op), .. }) => Ok(op.clone()),
+        Some(spanned) => Err(DslError::new("expected an operator (=, >=, <=, contains)", spanned.offset)),
+        None => Err(DslError::new("expected an operator", tokens[0].offset)),
+    }
+}
+
+fn expect_value(tokens: &[Spanned], idx: usize) -> Result<String, DslError> {
+    match tokens.get(idx) {
+        Some(Spanned { token: Token::Str(s), .. }) => Ok(s.clone()),
+        Some(Spanned { token: Token::Word(w), .. }) => Ok(w.clone()),
+        Some(Spanned { token: Token::Dimension(d), .. }) => Ok(d.name().to_string()),
+        Some(spanned) => Err(DslError::new("expected a value", spanned.offset)),
+        None => Err(DslError::new("expected a value", tokens[0].offset)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pin_and_filter() {
+        let src = "PIN WHERE = \"security\"\nFILTER WHEN >= 2025-12-01";
+        let query = parse_query(src).unwrap();
+
+        assert_eq!(query.pin.get(&Dimension::Where).unwrap().value, "security");
+        assert_eq!(query.filter.len(), 1);
+        assert_eq!(query.filter[0].dimension, Dimension::When);
+        assert!(matches!(query.filter[0].operator, FilterOp::Gte));
+        assert_eq!(query.natural_source.as_deref(), Some(src));
+    }
+
+    #[test]
+    fn test_parse_collapse_enumerate_and_limit() {
+        let src = "COLLAPSE [WHERE, WHY]\nENUMERATE [WHO, WHAT, WHEN]\nLIMIT 50";
+        let query = parse_query(src).unwrap();
+
+        assert_eq!(query.collapse, vec![Dimension::Where, Dimension::Why]);
+        assert_eq!(query.enumerate, vec![Dimension::Who, Dimension::What, Dimension::When]);
+        assert_eq!(query.limit, 50);
+    }
+
+    #[test]
+    fn test_blank_lines_and_comments_are_ignored() {
+        let src = "# a comment\n\nPIN WHO = \"alice\"\n";
+        let query = parse_query(src).unwrap();
+        assert_eq!(query.pin.get(&Dimension::Who).unwrap().value, "alice");
+    }
+
+    #[test]
+    fn test_unknown_keyword_reports_offset() {
+        let src = "BOGUS WHO = \"alice\"";
+        let err = parse_query(src).unwrap_err();
+        assert_eq!(err.offset, 0);
+    }
+
+    #[test]
+    fn test_unterminated_string_reports_offset() {
+        let src = "PIN WHO = \"alice";
+        let err = parse_query(src).unwrap_err();
+        assert_eq!(err.offset, 10);
+    }
+
+    #[test]
+    fn test_contains_operator() {
+        let src = "FILTER WHAT contains security";
+        let query = parse_query(src).unwrap();
+        assert!(matches!(query.filter[0].operator, FilterOp::Contains));
+        assert!(matches!(&query.filter[0].value, FilterValue::String(v) if v == "security"));
+    }
+}
@@ -27,7 +27,7 @@ use regex::Regex;
 use gently_alexandria::ConceptId;
 
 /// The 5W dimensions of knowledge
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Dimension {
     /// WHO - Agent/Entity dimension (Tesseract Observer face)
@@ -40,6 +40,8 @@ pub enum Dimension {
     When,
     /// WHY - Causal/Reason dimension (Tesseract Purpose face)
     Why,
+    /// HOW - Method/Process dimension (Tesseract Method face)
+    How,
 }
 
 impl Dimension {
@@ -51,6 +53,7 @@ impl Dimension {
             Dimension::Where,
             Dimension::When,
             Dimension::Why,
+            Dimension::How,
         ]
     }
 
@@ -62,6 +65,7 @@ impl Dimension {
             Dimension::Where => "WHERE",
             Dimension::When => "WHEN",
             Dimension::Why => "WHY",
+            Dimension::How => "HOW",
         }
     }
 
@@ -73,6 +77,7 @@ impl Dimension {
             Dimension::Where => "Where?",
             Dimension::When => "When?",
             Dimension::Why => "Why?",
+            Dimension::How => "How?",
         }
     }
 
@@ -84,16 +89,111 @@ impl Dimension {
             Dimension::Where => 5,  // Context face
             Dimension::When => 3,   // Temporal face
             Dimension::Why => 7,    // Purpose face
+            Dimension::How => 6,    // Method face
+        }
+    }
+
+    /// The comparison semantics a raw string value for this dimension
+    /// should be parsed with. WHEN maps to `Instant` and every other
+    /// dimension to `String`; no dimension maps to `Long` or `Double`, so
+    /// the numeric paths of `evaluate` and `DimensionValue::parse` are not
+    /// reached through this mapping.
+    pub fn value_type(&self) -> ValueType {
+        match self {
+            Dimension::When => ValueType::Instant,
+            Dimension::Who | Dimension::What | Dimension::Where | Dimension::Why | Dimension::How => {
+                ValueType::String
+            }
         }
     }
 }
 
+/// The comparison semantics associated with a `Dimension`'s values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValueType {
+    String,
+    Long,
+    Double,
+    Instant,
+}
+
+/// A dimension value parsed according to its `Dimension::value_type()`,
+/// falling back to `Str` when parsing fails so callers comparing raw
+/// strings keep working.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TypedValue {
+    Str(String),
+    Long(i64),
+    Double(f64),
+    Instant(DateTime<Utc>),
+}
+
+/// Parse a raw WHEN value, accepting both full RFC3339 timestamps and a
+/// bare `YYYY-MM-DD` date (midnight UTC).
+fn parse_instant(raw: &str) -> Option<DateTime<Utc>> {
+    if let Ok(dt) = raw.parse::<DateTime<Utc>>() {
+        return Some(dt);
+    }
+    chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|naive| DateTime::<Utc>::from_naive_utc_and_offset(naive, Utc))
+}
+
 impl std::fmt::Display for Dimension {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.name())
     }
 }
 
+/// An interval of plausible confidence, for extraction paths that can only
+/// bound a value rather than pin a single point estimate.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceRange {
+    pub lo: f32,
+    pub hi: f32,
+}
+
+impl ConfidenceRange {
+    /// Build a range, clamping both ends to `[0.0, 1.0]` and correcting
+    /// `lo > hi` by widening `hi` to meet `lo` rather than rejecting it.
+    pub fn new(lo: f32, hi: f32) -> Self {
+        let lo = lo.clamp(0.0, 1.0);
+        let hi = hi.clamp(0.0, 1.0).max(lo);
+        Self { lo, hi }
+    }
+
+    /// A degenerate range representing a single point estimate.
+    pub fn point(confidence: f32) -> Self {
+        Self::new(confidence, confidence)
+    }
+
+    pub fn contains(&self, p: f32) -> bool {
+        p >= self.lo && p <= self.hi
+    }
+
+    /// Widen the range to include `p`, clamped to `[0.0, 1.0]`. Returns
+    /// whether the bounds actually changed.
+    pub fn expand(&mut self, p: f32) -> bool {
+        let p = p.clamp(0.0, 1.0);
+        let widened = p < self.lo || p > self.hi;
+        self.lo = self.lo.min(p);
+        self.hi = self.hi.max(p);
+        widened
+    }
+
+    /// Union with another range.
+    pub fn union(&mut self, other: &ConfidenceRange) {
+        self.lo = self.lo.min(other.lo);
+        self.hi = self.hi.max(other.hi);
+    }
+
+    /// Midpoint of the range, used as the scalar point estimate.
+    pub fn point_estimate(&self) -> f32 {
+        (self.lo + self.hi) / 2.0
+    }
+}
+
 /// A value in a dimension
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DimensionValue {
@@ -101,8 +201,14 @@ pub struct DimensionValue {
     pub value: String,
     /// Associated concepts (if resolved)
     pub concepts: Vec<ConceptId>,
-    /// Confidence of extraction (0.0-1.0)
+    /// Confidence of extraction (0.0-1.0). Kept as a cached point estimate
+    /// (the midpoint of `confidence_range`) by the constructors and `merge`,
+    /// so existing code comparing `confidence` directly keeps working;
+    /// `merge_dimension_values` instead keeps the larger confidence.
     pub confidence: f32,
+    /// Confidence as an interval, for extractors that can only bound the
+    /// value rather than pin a single point estimate.
+    pub confidence_range: ConfidenceRange,
 }
 
 impl DimensionValue {
@@ -111,11 +217,21 @@ impl DimensionValue {
             value: value.to_string(),
             concepts: Vec::new(),
             confidence: 1.0,
+            confidence_range: ConfidenceRange::point(1.0),
         }
     }
 
     pub fn with_confidence(mut self, confidence: f32) -> Self {
         self.confidence = confidence;
+        self.confidence_range = ConfidenceRange::point(confidence);
+        self
+    }
+
+    /// Seed an interval confidence instead of a single point estimate.
+    /// `confidence` is kept in sync as the range's midpoint.
+    pub fn with_confidence_range(mut self, lo: f32, hi: f32) -> Self {
+        self.confidence_range = ConfidenceRange::new(lo, hi);
+        self.confidence = self.confidence_range.point_estimate();
         self
     }
 
@@ -123,6 +239,183 @@ impl DimensionValue {
         self.concepts = concepts;
         self
     }
+
+    /// Parse a raw string into the `TypedValue` appropriate for `dim`,
+    /// falling back to `TypedValue::Str` when the raw text doesn't parse as
+    /// that dimension's type.
+    pub fn parse(dim: Dimension, raw: &str) -> TypedValue {
+        match dim.value_type() {
+            ValueType::Instant => parse_instant(raw)
+                .map(TypedValue::Instant)
+                .unwrap_or_else(|| TypedValue::Str(raw.to_string())),
+            ValueType::Long => raw
+                .parse::<i64>()
+                .map(TypedValue::Long)
+                .unwrap_or_else(|_| TypedValue::Str(raw.to_string())),
+            ValueType::Double => raw
+                .parse::<f64>()
+                .map(TypedValue::Double)
+                .unwrap_or_else(|_| TypedValue::Str(raw.to_string())),
+            ValueType::String => TypedValue::Str(raw.to_string()),
+        }
+    }
+
+    /// Merge another `DimensionValue` for the same `value` into this one:
+    /// union the confidence ranges and the associated concepts. No-op if
+    /// `other.value` differs, since merging only makes sense for the same
+    /// pinned/extracted value.
+    pub fn merge(&mut self, other: &DimensionValue) {
+        if self.value != other.value {
+            return;
+        }
+        self.confidence_range.union(&other.confidence_range);
+        self.confidence = self.confidence_range.point_estimate();
+        for concept in &other.concepts {
+            if !self.concepts.contains(concept) {
+                self.concepts.push(*concept);
+            }
+        }
+    }
+}
+
+/// On-disk schema version for [`DimensionIndex`] snapshots. Bump this when a
+/// change to `Dimension`/`DimensionValue` would make an older snapshot
+/// unreadable, and teach `DimensionIndex::load_from` to migrate or reject it.
+const DIMENSION_INDEX_SCHEMA_VERSION: u32 = 1;
+
+/// Versioned, serializable form of a [`DimensionIndex`] written to disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DimensionIndexSnapshot {
+    schema_version: u32,
+    entries: HashMap<Dimension, DimensionValue>,
+}
+
+/// A persisted Who/What/.../Why dimensional analysis, so callers don't have
+/// to recompute it on every process restart.
+#[derive(Debug, Clone, Default)]
+pub struct DimensionIndex {
+    pub entries: HashMap<Dimension, DimensionValue>,
+}
+
+impl DimensionIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, dim: Dimension, value: DimensionValue) {
+        self.entries.insert(dim, value);
+    }
+
+    pub fn get(&self, dim: Dimension) -> Option<&DimensionValue> {
+        self.entries.get(&dim)
+    }
+
+    /// Write the index to `path` as pretty-printed JSON, tagged with the
+    /// current schema version.
+    pub fn save_to(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let snapshot = DimensionIndexSnapshot {
+            schema_version: DIMENSION_INDEX_SCHEMA_VERSION,
+            entries: self.entries.clone(),
+        };
+        let content = serde_json::to_string_pretty(&snapshot)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        // Atomic write, matching the rest of the crate's persistence layers.
+        let path = path.as_ref();
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, &content)?;
+        std::fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+
+    /// Load the index from `path`, rejecting snapshots written by a schema
+    /// version newer than this crate understands rather than silently
+    /// deserializing a shape it doesn't recognize.
+    pub fn load_from(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let snapshot: DimensionIndexSnapshot = serde_json::from_str(&content)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        if snapshot.schema_version > DIMENSION_INDEX_SCHEMA_VERSION {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "dimension index snapshot is schema v{}, but this build only understands up to v{}",
+                    snapshot.schema_version, DIMENSION_INDEX_SCHEMA_VERSION
+                ),
+            ));
+        }
+
+        // No older schema versions exist yet to migrate from; once one does,
+        // handle `snapshot.schema_version < DIMENSION_INDEX_SCHEMA_VERSION`
+        // here before accepting `snapshot.entries` as-is.
+        Ok(Self { entries: snapshot.entries })
+    }
+}
+
+/// How a collapsed (grouped-away) dimension's values should be rolled up
+/// into a single synthetic output column, e.g. `Where_count`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Aggregate {
+    /// Number of rows in the group
+    Count,
+    /// Number of distinct values in the group
+    CountDistinct,
+    /// Sum of numeric values (non-numeric values are skipped)
+    Sum,
+    /// Mean of numeric values (non-numeric values are skipped)
+    Avg,
+    /// Lexicographic minimum value
+    Min,
+    /// Lexicographic maximum value
+    Max,
+    /// All values joined with ", "
+    Concat,
+}
+
+impl Aggregate {
+    /// Suffix used to name the synthetic output column, e.g. "count" in
+    /// `Where_count`.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Aggregate::Count => "count",
+            Aggregate::CountDistinct => "count_distinct",
+            Aggregate::Sum => "sum",
+            Aggregate::Avg => "avg",
+            Aggregate::Min => "min",
+            Aggregate::Max => "max",
+            Aggregate::Concat => "concat",
+        }
+    }
+
+    /// Roll up the raw string values of a collapsed dimension within one
+    /// group into a single rendered string.
+    pub fn apply(&self, values: &[&str]) -> String {
+        match self {
+            Aggregate::Count => values.len().to_string(),
+            Aggregate::CountDistinct => values
+                .iter()
+                .collect::<std::collections::HashSet<_>>()
+                .len()
+                .to_string(),
+            Aggregate::Sum => values
+                .iter()
+                .filter_map(|v| v.parse::<f64>().ok())
+                .sum::<f64>()
+                .to_string(),
+            Aggregate::Avg => {
+                let nums: Vec<f64> = values.iter().filter_map(|v| v.parse::<f64>().ok()).collect();
+                if nums.is_empty() {
+                    "0".to_string()
+                } else {
+                    (nums.iter().sum::<f64>() / nums.len() as f64).to_string()
+                }
+            }
+            Aggregate::Min => values.iter().min().map(|s| s.to_string()).unwrap_or_default(),
+            Aggregate::Max => values.iter().max().map(|s| s.to_string()).unwrap_or_default(),
+            Aggregate::Concat => values.join(", "),
+        }
+    }
 }
 
 /// Filter operations for dimensions
@@ -213,8 +506,79 @@ impl DimensionFilter {
         }
     }
 
-    /// Evaluate this filter against a value
+    /// Evaluate this filter against a value. Dispatches on the filtered
+    /// dimension's `value_type()`: `Instant` compares via `DateTime<Utc>`
+    /// ordering, `Long`/`Double` via numeric ordering, and `String` keeps
+    /// the substring/equality semantics below. Falls back to string
+    /// comparison when the row value fails to parse as the declared type.
+    /// When the row value parses but a `FilterValue::String` operand does
+    /// not, `Eq`/`Gt`/`Lt`/`Gte`/`Lte` are false, and `Ne` is true for
+    /// `Instant` and false for `Long`/`Double`.
     pub fn evaluate(&self, value: &str) -> bool {
+        match self.dimension.value_type() {
+            ValueType::Instant => self.evaluate_instant(value),
+            ValueType::Long | ValueType::Double => self.evaluate_numeric(value),
+            ValueType::String => self.evaluate_string(value),
+        }
+    }
+
+    fn evaluate_instant(&self, value: &str) -> bool {
+        let Some(row_dt) = parse_instant(value) else {
+            return self.evaluate_string(value);
+        };
+
+        match (&self.operator, &self.value) {
+            (FilterOp::Eq, FilterValue::String(v)) => parse_instant(v).map_or(false, |d| row_dt == d),
+            (FilterOp::Ne, FilterValue::String(v)) => parse_instant(v).map_or(true, |d| row_dt != d),
+            (FilterOp::Gt, FilterValue::String(v)) => parse_instant(v).map_or(false, |d| row_dt > d),
+            (FilterOp::Lt, FilterValue::String(v)) => parse_instant(v).map_or(false, |d| row_dt < d),
+            (FilterOp::Gte, FilterValue::String(v)) => parse_instant(v).map_or(false, |d| row_dt >= d),
+            (FilterOp::Lte, FilterValue::String(v)) => parse_instant(v).map_or(false, |d| row_dt <= d),
+            (_, FilterValue::DateTime(d)) => match self.operator {
+                FilterOp::Eq => row_dt == *d,
+                FilterOp::Ne => row_dt != *d,
+                FilterOp::Gt => row_dt > *d,
+                FilterOp::Lt => row_dt < *d,
+                FilterOp::Gte => row_dt >= *d,
+                FilterOp::Lte => row_dt <= *d,
+                _ => self.evaluate_string(value),
+            },
+            (_, FilterValue::DateRange { from, to }) => {
+                from.map_or(true, |f| row_dt >= f) && to.map_or(true, |t| row_dt <= t)
+            }
+            _ => self.evaluate_string(value),
+        }
+    }
+
+    fn evaluate_numeric(&self, value: &str) -> bool {
+        let Some(row_n) = value.parse::<f64>().ok() else {
+            return self.evaluate_string(value);
+        };
+
+        match (&self.operator, &self.value) {
+            (FilterOp::Eq, FilterValue::String(v)) => v.parse::<f64>().is_ok_and(|n| row_n == n),
+            (FilterOp::Ne, FilterValue::String(v)) => v.parse::<f64>().is_ok_and(|n| row_n != n),
+            (FilterOp::Gt, FilterValue::String(v)) => v.parse::<f64>().is_ok_and(|n| row_n > n),
+            (FilterOp::Lt, FilterValue::String(v)) => v.parse::<f64>().is_ok_and(|n| row_n < n),
+            (FilterOp::Gte, FilterValue::String(v)) => v.parse::<f64>().is_ok_and(|n| row_n >= n),
+            (FilterOp::Lte, FilterValue::String(v)) => v.parse::<f64>().is_ok_and(|n| row_n <= n),
+            (_, FilterValue::Number(n)) => match self.operator {
+                FilterOp::Eq => row_n == *n,
+                FilterOp::Ne => row_n != *n,
+                FilterOp::Gt => row_n > *n,
+                FilterOp::Lt => row_n < *n,
+                FilterOp::Gte => row_n >= *n,
+                FilterOp::Lte => row_n <= *n,
+                _ => self.evaluate_string(value),
+            },
+            (_, FilterValue::NumberRange { from, to }) => {
+                from.map_or(true, |f| row_n >= f) && to.map_or(true, |t| row_n <= t)
+            }
+            _ => self.evaluate_string(value),
+        }
+    }
+
+    fn evaluate_string(&self, value: &str) -> bool {
         match (&self.operator, &self.value) {
             (FilterOp::Eq, FilterValue::String(v)) => value == v,
             (FilterOp::Ne, FilterValue::String(v)) => value != v,
@@ -243,6 +607,9 @@ pub struct HyperspaceQuery {
     pub filter: Vec<DimensionFilter>,
     /// Collapsed dimensions (removed from output, aggregated)
     pub collapse: Vec<Dimension>,
+    /// Aggregate to apply to each collapsed dimension. A dimension in
+    /// `collapse` with no entry here defaults to `Aggregate::Count`.
+    pub collapse_aggregates: HashMap<Dimension, Aggregate>,
     /// Enumerated dimensions (become columns in output)
     pub enumerate: Vec<Dimension>,
     /// Natural language source (if extracted from NL)
@@ -258,6 +625,7 @@ impl HyperspaceQuery {
             pin: HashMap::new(),
             filter: Vec::new(),
             collapse: Vec::new(),
+            collapse_aggregates: HashMap::new(),
             enumerate: Vec::new(),
             natural_source: None,
             limit: 100,
@@ -288,6 +656,12 @@ impl HyperspaceQuery {
         self
     }
 
+    /// Set the rollup aggregate for a collapsed dimension.
+    pub fn with_aggregate(mut self, dim: Dimension, aggregate: Aggregate) -> Self {
+        self.collapse_aggregates.insert(dim, aggregate);
+        self
+    }
+
     /// Mark dimensions to enumerate (become columns)
     pub fn enumerate_dimensions(mut self, dims: Vec<Dimension>) -> Self {
         self.enumerate = dims;
@@ -389,6 +763,14 @@ impl HyperspaceQueryBuilder {
         self
     }
 
+    /// Add a single dimension to collapse with an explicit rollup aggregate
+    /// (defaults to `Aggregate::Count` if never set).
+    pub fn collapse_dim_with_aggregate(mut self, dim: Dimension, aggregate: Aggregate) -> Self {
+        self.query.collapse.push(dim);
+        self.query.collapse_aggregates.insert(dim, aggregate);
+        self
+    }
+
     /// Pin a dimension with a string value
     pub fn pin(mut self, dim: Dimension, value: &str) -> Self {
         self.query.pin.insert(dim, DimensionValue::new(value));
@@ -627,6 +1009,97 @@ impl Default for NaturalLanguageExtractor {
     }
 }
 
+/// Extracts values across the full 5W1H dimension space from an arbitrary
+/// input span, tagging each with the concepts that contributed to it. Unlike
+/// `NaturalLanguageExtractor` (which builds a `HyperspaceQuery` from a
+/// search-box style question), this is meant for classifying a span of text
+/// already associated with a set of resolved `ConceptId`s.
+pub struct DimensionExtractor {
+    /// Keyword indicators per dimension, checked against the lowercased span.
+    patterns: HashMap<Dimension, Vec<&'static str>>,
+}
+
+impl DimensionExtractor {
+    pub fn new() -> Self {
+        let mut patterns = HashMap::new();
+        patterns.insert(Dimension::Who, vec!["who ", "user ", "developer ", "admin ", "team ", "author "]);
+        patterns.insert(Dimension::What, vec!["what ", "which ", "find ", "show ", "create ", "update ", "delete "]);
+        patterns.insert(Dimension::Where, vec!["in ", "at ", "within ", "security", "auth", "database", "network", "api"]);
+        patterns.insert(Dimension::When, vec!["when ", "since ", "after ", "before ", "today", "yesterday"]);
+        patterns.insert(Dimension::Why, vec!["why ", "because ", "due to ", "caused by ", "broke", "failed", "error"]);
+        patterns.insert(Dimension::How, vec!["how ", "via ", "using ", "by running ", "through ", "with "]);
+        Self { patterns }
+    }
+
+    /// Extract dimension values from `span`, tagging each with `concepts`.
+    /// Only the dimensions in `only` are considered; pass `Dimension::all()`
+    /// to extract across the full space.
+    pub fn extract(
+        &self,
+        span: &str,
+        concepts: &[ConceptId],
+        only: &[Dimension],
+    ) -> Vec<(Dimension, DimensionValue)> {
+        let lower = span.to_lowercase();
+        let mut results = Vec::new();
+
+        for dim in only {
+            let Some(keywords) = self.patterns.get(dim) else {
+                continue;
+            };
+            let hits = keywords.iter().filter(|kw| lower.contains(*kw)).count();
+            if hits == 0 {
+                continue;
+            }
+
+            // More distinct keyword hits raise confidence, capped below 1.0
+            // since keyword matching alone is never a certain signal.
+            let confidence = (0.5 + 0.15 * hits as f32).min(0.9);
+            let value = DimensionValue::new(span)
+                .with_confidence(confidence)
+                .with_concepts(concepts.to_vec());
+            results.push((*dim, value));
+        }
+
+        merge_dimension_values(results)
+    }
+}
+
+impl Default for DimensionExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Collapse a multi-valued extraction down to one authoritative
+/// `DimensionValue` per `Dimension`: values for the same dimension with the
+/// same `value` text have their concepts unioned and confidence maxed,
+/// rather than merged via `DimensionValue::merge`'s interval semantics
+/// (which exist for reconciling range-confidence extractions, not for
+/// picking a single winner among keyword-matched duplicates).
+fn merge_dimension_values(values: Vec<(Dimension, DimensionValue)>) -> Vec<(Dimension, DimensionValue)> {
+    let mut merged: Vec<(Dimension, DimensionValue)> = Vec::new();
+
+    for (dim, value) in values {
+        if let Some((_, existing)) = merged
+            .iter_mut()
+            .find(|(d, v)| *d == dim && v.value == value.value)
+        {
+            existing.confidence = existing.confidence.max(value.confidence);
+            existing.confidence_range.union(&value.confidence_range);
+            for concept in value.concepts {
+                if !existing.concepts.contains(&concept) {
+                    existing.concepts.push(concept);
+                }
+            }
+        } else {
+            merged.push((dim, value));
+        }
+    }
+
+    merged
+}
+
 /// Result of a hyperspace query
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HyperspaceResult {
@@ -701,6 +1174,44 @@ mod tests {
         assert_eq!(query.collapse.len(), 2);
     }
 
+    #[test]
+    fn test_dimension_extractor_restricts_to_requested_dimensions() {
+        let extractor = DimensionExtractor::new();
+        let concept = ConceptId::from_concept("deploy");
+
+        let results = extractor.extract(
+            "deployed via the CI pipeline because of a failed build",
+            &[concept],
+            &[Dimension::How],
+        );
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, Dimension::How);
+        assert!(results[0].1.concepts.contains(&concept));
+    }
+
+    #[test]
+    fn test_dimension_extractor_merges_duplicate_values() {
+        let merged = merge_dimension_values(vec![
+            (
+                Dimension::Why,
+                DimensionValue::new("broke")
+                    .with_confidence(0.5)
+                    .with_concepts(vec![ConceptId::from_concept("a")]),
+            ),
+            (
+                Dimension::Why,
+                DimensionValue::new("broke")
+                    .with_confidence(0.8)
+                    .with_concepts(vec![ConceptId::from_concept("b")]),
+            ),
+        ]);
+
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].1.confidence, 0.8);
+        assert_eq!(merged[0].1.concepts.len(), 2);
+    }
+
     #[test]
     fn test_natural_language_extraction() {
         let extractor = NaturalLanguageExtractor::new();
@@ -729,6 +1240,28 @@ mod tests {
         assert!(!filter_eq.evaluate("database"));
     }
 
+    #[test]
+    fn test_filter_evaluation_when_is_chronological_not_lexical() {
+        // Lexically "2025-9-5" > "2025-10-01" (the '9' beats the '1'), but
+        // September 5th is before October 1st. A WHEN filter must compare
+        // dates, not strings.
+        let gte_oct = DimensionFilter {
+            dimension: Dimension::When,
+            operator: FilterOp::Gte,
+            value: FilterValue::String("2025-10-01".to_string()),
+        };
+        assert!(!gte_oct.evaluate("2025-9-5"));
+        assert!(gte_oct.evaluate("2025-10-15"));
+
+        // A non-WHEN dimension keeps the old lexical semantics unchanged.
+        let gt_string = DimensionFilter {
+            dimension: Dimension::What,
+            operator: FilterOp::Gt,
+            value: FilterValue::String("10".to_string()),
+        };
+        assert!(gt_string.evaluate("9"));
+    }
+
     #[test]
     fn test_output_dimensions() {
         let query = HyperspaceQueryBuilder::new()
@@ -753,4 +1286,92 @@ mod tests {
         assert_eq!(val.confidence, 0.9);
         assert_eq!(val.concepts.len(), 1);
     }
+
+    #[test]
+    fn test_confidence_range_contains_and_expand() {
+        let mut range = ConfidenceRange::new(0.4, 0.6);
+        assert!(range.contains(0.5));
+        assert!(!range.contains(0.9));
+
+        assert!(range.expand(0.9));
+        assert!(range.contains(0.9));
+        assert_eq!(range.hi, 0.9);
+
+        // Expanding to a point already inside the range changes nothing.
+        assert!(!range.expand(0.5));
+    }
+
+    #[test]
+    fn test_confidence_range_widens_inverted_bounds() {
+        let range = ConfidenceRange::new(0.8, 0.2);
+        assert_eq!(range.lo, 0.8);
+        assert_eq!(range.hi, 0.8);
+    }
+
+    #[test]
+    fn test_dimension_value_with_confidence_range() {
+        let val = DimensionValue::new("security").with_confidence_range(0.3, 0.7);
+        assert_eq!(val.confidence_range.lo, 0.3);
+        assert_eq!(val.confidence_range.hi, 0.7);
+        assert_eq!(val.confidence, 0.5);
+    }
+
+    #[test]
+    fn test_dimension_index_save_load_roundtrip() {
+        let path = std::env::temp_dir().join(format!(
+            "gently_search_dimension_index_{:?}.json",
+            std::thread::current().id()
+        ));
+
+        let mut index = DimensionIndex::new();
+        index.insert(Dimension::Who, DimensionValue::new("alice").with_confidence(0.9));
+        index.insert(Dimension::Where, DimensionValue::new("security").with_confidence(0.7));
+
+        index.save_to(&path).unwrap();
+        let loaded = DimensionIndex::load_from(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(loaded.get(Dimension::Who).unwrap().value, "alice");
+        assert_eq!(loaded.get(Dimension::Where).unwrap().value, "security");
+    }
+
+    #[test]
+    fn test_dimension_index_rejects_newer_schema_version() {
+        let path = std::env::temp_dir().join(format!(
+            "gently_search_dimension_index_future_{:?}.json",
+            std::thread::current().id()
+        ));
+
+        let snapshot = DimensionIndexSnapshot {
+            schema_version: DIMENSION_INDEX_SCHEMA_VERSION + 1,
+            entries: HashMap::new(),
+        };
+        std::fs::write(&path, serde_json::to_string(&snapshot).unwrap()).unwrap();
+
+        let result = DimensionIndex::load_from(&path);
+        std::fs::remove_file(&path).ok();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_dimension_value_merge_unions_range_and_concepts() {
+        let concept_a = ConceptId::from_concept("a");
+        let concept_b = ConceptId::from_concept("b");
+
+        let mut a = DimensionValue::new("security")
+            .with_confidence_range(0.3, 0.5)
+            .with_concepts(vec![concept_a]);
+        let b = DimensionValue::new("security")
+            .with_confidence_range(0.6, 0.8)
+            .with_concepts(vec![concept_b]);
+
+        a.merge(&b);
+
+        assert_eq!(a.confidence_range, ConfidenceRange::new(0.3, 0.8));
+        assert_eq!(a.confidence, a.confidence_range.point_estimate());
+        assert_eq!(a.concepts.len(), 2);
+        assert!(a.concepts.contains(&concept_a));
+        assert!(a.concepts.contains(&concept_b));
+    }
 }
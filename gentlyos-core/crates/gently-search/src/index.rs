@@ -163,6 +163,14 @@ impl ThoughtIndex {
         &self.wormholes
     }
 
+    /// Monotonic counter that only ever increases as thoughts and wormholes
+    /// are added, cheap enough for a consumer (e.g. `ConstraintBuilder`'s
+    /// search cache) to use as a "has anything been added" signal without
+    /// diffing its contents. `remove_thought` does not change it.
+    pub fn generation(&self) -> u64 {
+        self.thought_count + self.wormhole_count
+    }
+
     /// Remove a thought
     pub fn remove_thought(&mut self, id: Uuid) -> Option<Thought> {
         if let Some(pos) = self.thoughts.iter().position(|t| t.id == id) {
@@ -38,6 +38,7 @@ pub mod alexandria;
 pub mod constraint;
 pub mod hyperspace;
 pub mod collapse;
+pub mod dsl;
 pub mod bbbcp;
 pub mod chain;
 
@@ -47,9 +48,10 @@ pub use router::{ContextRouter, SearchResult};
 pub use thought::{Shape, Thought, ThoughtKind};
 pub use wormhole::{Wormhole, WormholeDetector};
 pub use alexandria::{AlexandriaSearch, AlexandriaSearchStats, SearchResults};
-pub use constraint::{ConstraintBuilder, ConstraintRule, ConstraintSource, ConstraintStats};
+pub use constraint::{ConstraintBuilder, ConstraintConflict, ConstraintFilter, ConstraintRule, ConstraintSource, ConstraintStats, SectionBudgets};
 pub use hyperspace::{Dimension, HyperspaceQuery, HyperspaceQueryBuilder, HyperspaceResult, NaturalLanguageExtractor};
 pub use collapse::{CollapseEngine, CollapseResult, CollapsedRow, CollapseProof, RowBuilder, TableOutput};
+pub use dsl::{parse_query as parse_hyperspace_query, DslError};
 pub use bbbcp::{BbbcpQuery, BbbcpQueryBuilder, BbbcpEngine, BbbcpResult, BbbcpOutput, Bone, Circle, BlobSearch, PinStrategy, ChainForward};
 pub use chain::{Conclusion, ConclusionChain, ConclusionChainer, ConclusionType, QuestionStep, InverseTrail};
 
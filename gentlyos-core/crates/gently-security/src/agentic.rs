@@ -64,9 +64,15 @@ pub struct AgenticSecurityController {
     /// Current defense mode
     defense_mode: Arc<RwLock<DefenseMode>>,
 
-    /// Event channel
+    /// Inbound side of the event channel: every daemon below sends here.
     event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>,
-    event_rx: Arc<tokio::sync::Mutex<mpsc::UnboundedReceiver<SecurityDaemonEvent>>>,
+    /// Downstream side the event processor reads, fed by `event_resync_queue`
+    /// rather than directly by `event_tx` — see its doc comment.
+    event_rx: Arc<tokio::sync::Mutex<mpsc::Receiver<SecurityDaemonEvent>>>,
+    /// Durable retry queue between `event_tx` and `event_rx`: absorbs and
+    /// replays events the processor can't keep up with instead of losing
+    /// them to the unbounded channel's lack of backpressure.
+    event_resync_queue: Arc<EventResyncQueue>,
 
     /// Layer 1: Foundation Daemons
     hash_chain_validator: Arc<HashChainValidatorDaemon>,
@@ -89,6 +95,8 @@ pub struct AgenticSecurityController {
     tarpit_controller: Arc<TarpitControllerDaemon>,
     response_mutator: Arc<ResponseMutatorDaemon>,
     rate_limit_enforcer: Arc<RateLimitEnforcerDaemon>,
+    /// Metrics registry shared across the Layer 4 daemons above
+    defense_metrics: Arc<DefenseMetrics>,
 
     /// Layer 5: Threat Intelligence Daemons
     threat_intel_collector: Arc<ThreatIntelCollectorDaemon>,
@@ -152,7 +160,10 @@ impl Default for EscalationConfig {
 impl AgenticSecurityController {
     /// Create a new agentic security controller
     pub fn new() -> Self {
-        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        let (event_tx, inbound_rx) = mpsc::unbounded_channel();
+        let resync_config = ResyncQueueConfig::default();
+        let (downstream_tx, event_rx) = mpsc::channel(resync_config.capacity);
+        let event_resync_queue = Arc::new(EventResyncQueue::new(inbound_rx, downstream_tx, resync_config));
 
         // Layer 1: Foundation
         let hash_chain_validator = Arc::new(HashChainValidatorDaemon::new(
@@ -174,10 +185,11 @@ impl AgenticSecurityController {
         let anomaly_detector = Arc::new(AnomalyDetectorDaemon::new(event_tx.clone()));
 
         // Layer 4: Active Defense
-        let session_isolator = Arc::new(SessionIsolatorDaemon::new(event_tx.clone()));
-        let tarpit_controller = Arc::new(TarpitControllerDaemon::new(event_tx.clone()));
-        let response_mutator = Arc::new(ResponseMutatorDaemon::new(event_tx.clone()));
-        let rate_limit_enforcer = Arc::new(RateLimitEnforcerDaemon::new(event_tx.clone()));
+        let defense_metrics = DefenseMetrics::new();
+        let session_isolator = Arc::new(SessionIsolatorDaemon::with_metrics(event_tx.clone(), defense_metrics.clone()));
+        let tarpit_controller = Arc::new(TarpitControllerDaemon::with_metrics(event_tx.clone(), defense_metrics.clone()));
+        let response_mutator = Arc::new(ResponseMutatorDaemon::with_metrics(event_tx.clone(), defense_metrics.clone()));
+        let rate_limit_enforcer = Arc::new(RateLimitEnforcerDaemon::with_metrics(event_tx.clone(), defense_metrics.clone()));
 
         // Layer 5: Threat Intelligence
         let threat_intel_collector = Arc::new(ThreatIntelCollectorDaemon::new(event_tx.clone()));
@@ -188,6 +200,7 @@ impl AgenticSecurityController {
             defense_mode: Arc::new(RwLock::new(DefenseMode::Normal)),
             event_tx,
             event_rx: Arc::new(tokio::sync::Mutex::new(event_rx)),
+            event_resync_queue,
             hash_chain_validator,
             btc_anchor,
             forensic_logger,
@@ -202,6 +215,7 @@ impl AgenticSecurityController {
             tarpit_controller,
             response_mutator,
             rate_limit_enforcer,
+            defense_metrics,
             threat_intel_collector,
             swarm_defense,
             stats: Arc::new(RwLock::new(ControllerStats::default())),
@@ -233,7 +247,7 @@ impl AgenticSecurityController {
         self.forensic_logger.log(
             ForensicLevel::Info,
             "agentic_controller",
-            "All 16 security daemons started",
+            "All 16 security daemons (plus the event resync queue) started",
         );
     }
 
@@ -285,6 +299,9 @@ impl AgenticSecurityController {
 
         let rle = self.rate_limit_enforcer.clone();
         tokio::spawn(async move { rle.run().await });
+
+        let erq = self.event_resync_queue.clone();
+        tokio::spawn(async move { erq.run().await });
     }
 
     async fn spawn_layer5_daemons(&self) {
@@ -348,7 +365,7 @@ impl AgenticSecurityController {
                                         duration: Some(Duration::from_secs(3600)),
                                     });
 
-                                    response_mutator.add_to_mutate_list(entity);
+                                    response_mutator.add_to_mutate_list(entity).await;
                                     anomaly_detector.add_indicator(entity, "injection_attempt", 0.3);
                                 }
                             }
@@ -356,7 +373,7 @@ impl AgenticSecurityController {
                             SecurityDaemonEvent::AnomalyDetected { entity, score, indicators } => {
                                 if *score >= 0.8 {
                                     // High anomaly - engage tarpit
-                                    tarpit_controller.engage(entity, "anomaly_detection");
+                                    tarpit_controller.engage(entity, "anomaly_detection").await;
 
                                     // Add to threat intel
                                     threat_intel.add_indicator(RawIndicator {
@@ -510,6 +527,70 @@ impl AgenticSecurityController {
         self.rate_limit_enforcer.stop();
         self.threat_intel_collector.stop();
         self.swarm_defense.stop();
+        self.event_resync_queue.stop();
+    }
+
+    /// Gracefully stop the Layer 4 active-defense daemons, waiting up to
+    /// `timeout` for any `engage()`/`mutate_response()` call already in
+    /// flight to finish, then `stop()` every other daemon as before. Returns
+    /// each Layer 4 daemon's drain result so a supervisor can tell a clean
+    /// drain apart from one that timed out with calls still running.
+    pub async fn shutdown_gracefully(&self, timeout: Duration) -> HashMap<String, Result<DrainReport, DrainTimeout>> {
+        self.running.store(false, Ordering::SeqCst);
+
+        // Drained concurrently so the whole wait stays within one `timeout`.
+        let (session_isolator, tarpit_controller, response_mutator, rate_limit_enforcer) = tokio::join!(
+            self.session_isolator.shutdown(timeout),
+            self.tarpit_controller.shutdown(timeout),
+            self.response_mutator.shutdown(timeout),
+            self.rate_limit_enforcer.shutdown(timeout),
+        );
+
+        let mut results = HashMap::new();
+        results.insert("session_isolator".to_string(), session_isolator);
+        results.insert("tarpit_controller".to_string(), tarpit_controller);
+        results.insert("response_mutator".to_string(), response_mutator);
+        results.insert("rate_limit_enforcer".to_string(), rate_limit_enforcer);
+
+        self.hash_chain_validator.stop();
+        self.btc_anchor.stop();
+        self.forensic_logger.stop();
+        self.traffic_sentinel.stop();
+        self.token_watchdog.stop();
+        self.cost_guardian.stop();
+        self.prompt_analyzer.stop();
+        self.behavior_profiler.stop();
+        self.pattern_matcher.stop();
+        self.anomaly_detector.stop();
+        self.threat_intel_collector.stop();
+        self.swarm_defense.stop();
+        self.event_resync_queue.stop();
+
+        results
+    }
+
+    /// Hook SIGTERM/SIGINT so a supervisor (systemd, docker, k8s) can stop
+    /// the controller with a clean drain instead of killing it mid-request.
+    /// Spawns a task that waits for either signal once, then runs
+    /// `shutdown_gracefully` with `timeout`.
+    pub fn spawn_shutdown_signal_handler(self: &Arc<Self>, timeout: Duration) {
+        let controller = self.clone();
+        tokio::spawn(async move {
+            let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+                .expect("failed to install SIGTERM handler");
+
+            tokio::select! {
+                _ = sigterm.recv() => {}
+                _ = tokio::signal::ctrl_c() => {}
+            }
+
+            controller.log_forensic(
+                ForensicLevel::Info,
+                "agentic_controller",
+                "Shutdown signal received, draining Layer 4 daemons",
+            );
+            controller.shutdown_gracefully(timeout).await;
+        });
     }
 
     /// Get current defense mode
@@ -560,9 +641,19 @@ impl AgenticSecurityController {
         statuses.insert("threat_intel_collector".to_string(), self.threat_intel_collector.status());
         statuses.insert("swarm_defense".to_string(), self.swarm_defense.status());
 
+        // Event pipeline
+        statuses.insert("event_resync_queue".to_string(), self.event_resync_queue.status());
+
         statuses
     }
 
+    /// Render the Layer-4 defense daemons' Prometheus/OpenMetrics text
+    /// exposition, for an operator to serve over whatever HTTP endpoint
+    /// their deployment already exposes metrics on.
+    pub fn gather_defense_metrics(&self) -> String {
+        self.defense_metrics.gather()
+    }
+
     /// Get recent threat events
     pub fn recent_threats(&self, limit: usize) -> Vec<ThreatEvent> {
         let history = self.threat_history.read().unwrap();
@@ -623,9 +714,13 @@ impl AgenticSecurityController {
         self.tarpit_controller.get_delay(entity)
     }
 
-    /// Apply response mutation if needed
-    pub fn mutate_response(&self, entity: &str, response: &str) -> (String, Vec<String>) {
-        self.response_mutator.mutate_response(entity, response)
+    /// Apply response mutation if needed. Passes the response through
+    /// unmutated if the mutator has started shutting down.
+    pub async fn mutate_response(&self, entity: &str, response: &str) -> (String, Vec<String>) {
+        self.response_mutator
+            .mutate_response(entity, response)
+            .await
+            .unwrap_or_else(|| (response.to_string(), Vec::new()))
     }
 
     /// Log forensic entry
@@ -670,7 +765,7 @@ mod tests {
         let controller = AgenticSecurityController::new();
 
         let statuses = controller.daemon_statuses();
-        assert_eq!(statuses.len(), 16); // 16 daemons
+        assert_eq!(statuses.len(), 17); // 16 daemons + the event resync queue
 
         // All should be not running initially
         for status in statuses.values() {
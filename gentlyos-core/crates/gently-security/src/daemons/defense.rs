@@ -6,18 +6,177 @@
 //! - ResponseMutatorDaemon: Modifies responses to confuse attackers
 //! - RateLimitEnforcerDaemon: Enforces rate limits across layers
 
-use super::{SecurityDaemon, DaemonStatus, DaemonConfig, SecurityDaemonEvent, SessionAction, DefenseMode};
-use std::sync::{Arc, Mutex, RwLock, atomic::{AtomicBool, Ordering}};
+use super::{SecurityDaemon, DaemonStatus, DaemonConfig, SecurityDaemonEvent, SessionAction, DefenseMode, ShutdownHandle, DrainReport, DrainTimeout};
+use super::state_store::{StateStore, InMemoryStore};
+use parking_lot::{Mutex, RwLock};
+use std::fmt::Write as _;
+use std::sync::{Arc, atomic::{AtomicU64, Ordering}};
 use std::time::{Duration, Instant};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{HashMap, VecDeque};
 use tokio::sync::mpsc;
 use chrono::{DateTime, Utc};
 
+/// Upper bounds (ms) for `DefenseMetrics`' tarpit delay histogram buckets,
+/// spanning `TarpitControllerDaemon`'s default `base_delay_ms`..`max_delay_ms`
+/// range. An observation lands in the first bucket whose bound it doesn't
+/// exceed; `gather()` renders these as the usual Prometheus cumulative
+/// `le="..."` buckets plus an implicit `+Inf`.
+const TARPIT_DELAY_BUCKETS_MS: [u64; 7] = [1_000, 2_000, 5_000, 10_000, 20_000, 40_000, 60_000];
+
+#[derive(Default)]
+struct DelayHistogram {
+    /// Per-bucket hit counts, parallel to `TARPIT_DELAY_BUCKETS_MS`; the
+    /// final slot is the `+Inf` bucket for values past the largest bound.
+    bucket_hits: [u64; TARPIT_DELAY_BUCKETS_MS.len() + 1],
+    sum_ms: u64,
+    count: u64,
+}
+
+impl DelayHistogram {
+    fn observe(&mut self, value_ms: u64) {
+        self.sum_ms += value_ms;
+        self.count += 1;
+        let bucket = TARPIT_DELAY_BUCKETS_MS.iter().position(|&bound| value_ms <= bound)
+            .unwrap_or(TARPIT_DELAY_BUCKETS_MS.len());
+        self.bucket_hits[bucket] += 1;
+    }
+}
+
+/// Prometheus/OpenMetrics counters and gauges for the four Layer-4 defense
+/// daemons, updated directly from their `run` loops and `check_*`/`engage`
+/// methods rather than polled from `DaemonStatus` after the fact. Share one
+/// `Arc<DefenseMetrics>` across all four daemons via their `with_metrics`
+/// constructor so `gather()` reflects the whole layer; each daemon's plain
+/// `new()` still works standalone with its own private registry.
+#[derive(Default)]
+pub struct DefenseMetrics {
+    cycles: Mutex<HashMap<&'static str, u64>>,
+    events_emitted: Mutex<HashMap<&'static str, u64>>,
+    /// Current (not cumulative) breakdown of `SessionIsolatorDaemon::isolated`,
+    /// refreshed whenever that map changes.
+    isolated_by_severity: Mutex<HashMap<u8, u64>>,
+    isolated_by_restriction: Mutex<HashMap<&'static str, u64>>,
+    tarpit_delay_histogram: Mutex<DelayHistogram>,
+    tarpit_engagements_by_reason: Mutex<HashMap<String, u64>>,
+    mutate_list_size: AtomicU64,
+    rate_limit_rejections_by_layer: Mutex<HashMap<u8, u64>>,
+}
+
+impl DefenseMetrics {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    fn record_cycle(&self, daemon: &'static str) {
+        *self.cycles.lock().entry(daemon).or_insert(0) += 1;
+    }
+
+    fn record_event(&self, daemon: &'static str) {
+        *self.events_emitted.lock().entry(daemon).or_insert(0) += 1;
+    }
+
+    fn set_isolated_snapshot(&self, by_severity: HashMap<u8, u64>, by_restriction: HashMap<&'static str, u64>) {
+        *self.isolated_by_severity.lock() = by_severity;
+        *self.isolated_by_restriction.lock() = by_restriction;
+    }
+
+    fn observe_tarpit_delay(&self, delay_ms: u64) {
+        self.tarpit_delay_histogram.lock().observe(delay_ms);
+    }
+
+    fn record_tarpit_engagement(&self, reason: &str) {
+        *self.tarpit_engagements_by_reason.lock().entry(reason.to_string()).or_insert(0) += 1;
+    }
+
+    fn set_mutate_list_size(&self, size: usize) {
+        self.mutate_list_size.store(size as u64, Ordering::SeqCst);
+    }
+
+    fn record_rate_limit_rejection(&self, layer: u8) {
+        *self.rate_limit_rejections_by_layer.lock().entry(layer).or_insert(0) += 1;
+    }
+
+    /// Render everything recorded so far in Prometheus text exposition format.
+    pub fn gather(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(out, "# HELP gently_security_defense_daemon_cycles_total Completed run-loop cycles for a Layer-4 defense daemon.");
+        let _ = writeln!(out, "# TYPE gently_security_defense_daemon_cycles_total counter");
+        for (daemon, count) in self.cycles.lock().iter() {
+            let _ = writeln!(out, "gently_security_defense_daemon_cycles_total{{daemon=\"{}\"}} {}", daemon, count);
+        }
+
+        let _ = writeln!(out, "# HELP gently_security_defense_daemon_events_emitted_total Security events emitted by a Layer-4 defense daemon.");
+        let _ = writeln!(out, "# TYPE gently_security_defense_daemon_events_emitted_total counter");
+        for (daemon, count) in self.events_emitted.lock().iter() {
+            let _ = writeln!(out, "gently_security_defense_daemon_events_emitted_total{{daemon=\"{}\"}} {}", daemon, count);
+        }
+
+        let _ = writeln!(out, "# HELP gently_security_defense_isolated_sessions Currently isolated sessions by severity.");
+        let _ = writeln!(out, "# TYPE gently_security_defense_isolated_sessions gauge");
+        for (severity, count) in self.isolated_by_severity.lock().iter() {
+            let _ = writeln!(out, "gently_security_defense_isolated_sessions{{severity=\"{}\"}} {}", severity, count);
+        }
+
+        let _ = writeln!(out, "# HELP gently_security_defense_isolated_sessions_by_restriction Currently isolated sessions by active restriction.");
+        let _ = writeln!(out, "# TYPE gently_security_defense_isolated_sessions_by_restriction gauge");
+        for (restriction, count) in self.isolated_by_restriction.lock().iter() {
+            let _ = writeln!(out, "gently_security_defense_isolated_sessions_by_restriction{{restriction=\"{}\"}} {}", restriction, count);
+        }
+
+        {
+            let hist = self.tarpit_delay_histogram.lock();
+            let _ = writeln!(out, "# HELP gently_security_defense_tarpit_delay_ms Tarpit delay assigned on engagement.");
+            let _ = writeln!(out, "# TYPE gently_security_defense_tarpit_delay_ms histogram");
+            let mut cumulative = 0u64;
+            for (bound, hits) in TARPIT_DELAY_BUCKETS_MS.iter().zip(hist.bucket_hits.iter()) {
+                cumulative += hits;
+                let _ = writeln!(out, "gently_security_defense_tarpit_delay_ms_bucket{{le=\"{}\"}} {}", bound, cumulative);
+            }
+            cumulative += hist.bucket_hits[TARPIT_DELAY_BUCKETS_MS.len()];
+            let _ = writeln!(out, "gently_security_defense_tarpit_delay_ms_bucket{{le=\"+Inf\"}} {}", cumulative);
+            let _ = writeln!(out, "gently_security_defense_tarpit_delay_ms_sum {}", hist.sum_ms);
+            let _ = writeln!(out, "gently_security_defense_tarpit_delay_ms_count {}", hist.count);
+        }
+
+        let _ = writeln!(out, "# HELP gently_security_defense_tarpit_engagements_total Tarpit engagements by reason.");
+        let _ = writeln!(out, "# TYPE gently_security_defense_tarpit_engagements_total counter");
+        for (reason, count) in self.tarpit_engagements_by_reason.lock().iter() {
+            let _ = writeln!(out, "gently_security_defense_tarpit_engagements_total{{reason=\"{}\"}} {}", reason, count);
+        }
+
+        let _ = writeln!(out, "# HELP gently_security_defense_mutate_list_size Entities currently marked for response mutation.");
+        let _ = writeln!(out, "# TYPE gently_security_defense_mutate_list_size gauge");
+        let _ = writeln!(out, "gently_security_defense_mutate_list_size {}", self.mutate_list_size.load(Ordering::SeqCst));
+
+        let _ = writeln!(out, "# HELP gently_security_defense_rate_limit_rejections_total Rejected requests by the layer that shed them.");
+        let _ = writeln!(out, "# TYPE gently_security_defense_rate_limit_rejections_total counter");
+        for (layer, count) in self.rate_limit_rejections_by_layer.lock().iter() {
+            let _ = writeln!(out, "gently_security_defense_rate_limit_rejections_total{{layer=\"{}\"}} {}", rate_limit_layer_name(*layer), count);
+        }
+
+        out
+    }
+}
+
+/// Human-readable name for a `RateLimitResult.layer` value (the `as_u8` of
+/// the `RateLimitLayer` that rejected), used to label `gather()`'s output.
+fn rate_limit_layer_name(layer: u8) -> &'static str {
+    match layer {
+        1 => "global",
+        2 => "provider",
+        3 => "token",
+        4 => "session",
+        5 => "entity",
+        _ => "unknown",
+    }
+}
+
 /// Session Isolator Daemon
 /// Isolates suspicious sessions from main system
 pub struct SessionIsolatorDaemon {
     config: DaemonConfig,
-    stop_flag: Arc<AtomicBool>,
+    shutdown: ShutdownHandle,
     status: Arc<Mutex<DaemonStatus>>,
     event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>,
     /// Isolated sessions
@@ -26,6 +185,8 @@ pub struct SessionIsolatorDaemon {
     isolation_queue: Arc<Mutex<VecDeque<IsolationRequest>>>,
     /// Current defense mode
     defense_mode: Arc<RwLock<DefenseMode>>,
+    /// Shared Layer-4 metrics registry
+    metrics: Arc<DefenseMetrics>,
 }
 
 #[derive(Debug, Clone)]
@@ -48,6 +209,20 @@ pub enum Restriction {
     Terminated,
 }
 
+impl Restriction {
+    /// Metric label, ignoring any associated data (e.g. `RateLimited`'s `max_rpm`).
+    fn label(&self) -> &'static str {
+        match self {
+            Restriction::NoExternalProviders => "no_external_providers",
+            Restriction::RateLimited { .. } => "rate_limited",
+            Restriction::ResponseFiltered => "response_filtered",
+            Restriction::ReadOnly => "read_only",
+            Restriction::Sandboxed => "sandboxed",
+            Restriction::Terminated => "terminated",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IsolationRequest {
     pub session_id: String,
@@ -58,35 +233,41 @@ pub struct IsolationRequest {
 
 impl SessionIsolatorDaemon {
     pub fn new(event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>) -> Self {
+        Self::with_metrics(event_tx, DefenseMetrics::new())
+    }
+
+    /// Create sharing a `DefenseMetrics` registry with the other Layer-4 daemons.
+    pub fn with_metrics(event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>, metrics: Arc<DefenseMetrics>) -> Self {
         Self {
             config: DaemonConfig {
                 interval: Duration::from_millis(100),
                 ..Default::default()
             },
-            stop_flag: Arc::new(AtomicBool::new(false)),
+            shutdown: ShutdownHandle::new(),
             status: Arc::new(Mutex::new(DaemonStatus::default())),
             event_tx,
             isolated: Arc::new(RwLock::new(HashMap::new())),
             isolation_queue: Arc::new(Mutex::new(VecDeque::new())),
             defense_mode: Arc::new(RwLock::new(DefenseMode::Normal)),
+            metrics,
         }
     }
 
     /// Request session isolation
     pub fn request_isolation(&self, request: IsolationRequest) {
-        let mut queue = self.isolation_queue.lock().unwrap();
+        let mut queue = self.isolation_queue.lock();
         queue.push_back(request);
     }
 
     /// Check if session is isolated
     pub fn is_isolated(&self, session_id: &str) -> bool {
-        let isolated = self.isolated.read().unwrap();
+        let isolated = self.isolated.read();
         isolated.contains_key(session_id)
     }
 
     /// Get session restrictions
     pub fn get_restrictions(&self, session_id: &str) -> Vec<Restriction> {
-        let isolated = self.isolated.read().unwrap();
+        let isolated = self.isolated.read();
         isolated.get(session_id)
             .map(|s| s.restrictions.clone())
             .unwrap_or_default()
@@ -94,12 +275,12 @@ impl SessionIsolatorDaemon {
 
     /// Set defense mode
     pub fn set_defense_mode(&self, mode: DefenseMode) {
-        let mut dm = self.defense_mode.write().unwrap();
+        let mut dm = self.defense_mode.write();
         *dm = mode;
     }
 
     fn determine_restrictions(&self, severity: u8) -> Vec<Restriction> {
-        let mode = *self.defense_mode.read().unwrap();
+        let mode = *self.defense_mode.read();
 
         match (severity, mode) {
             (10, _) => vec![Restriction::Terminated],
@@ -136,13 +317,41 @@ impl SessionIsolatorDaemon {
     }
 
     fn cleanup_expired(&self) {
-        let mut isolated = self.isolated.write().unwrap();
+        let mut isolated = self.isolated.write();
         let now = Utc::now();
 
         isolated.retain(|_, session| {
             session.expires_at.map(|e| e > now).unwrap_or(true)
         });
     }
+
+    /// Recompute the `isolated` gauge breakdown by severity/restriction and
+    /// publish it to `metrics`. Called whenever `isolated` changes.
+    fn refresh_isolation_metrics(&self) {
+        let isolated = self.isolated.read();
+        let mut by_severity: HashMap<u8, u64> = HashMap::new();
+        let mut by_restriction: HashMap<&'static str, u64> = HashMap::new();
+
+        for session in isolated.values() {
+            *by_severity.entry(session.severity).or_insert(0) += 1;
+            for restriction in &session.restrictions {
+                *by_restriction.entry(restriction.label()).or_insert(0) += 1;
+            }
+        }
+
+        self.metrics.set_isolated_snapshot(by_severity, by_restriction);
+    }
+
+    /// Gracefully stop: signal `run` to exit and wait up to `timeout` for the
+    /// current cycle to drain its queued isolation requests before reporting
+    /// on `DaemonStatus::draining`. Prefer this over the bare `stop()` from
+    /// `SecurityDaemon` when the caller can await the result.
+    pub async fn shutdown(&self, timeout: Duration) -> Result<DrainReport, DrainTimeout> {
+        self.status.lock().draining = true;
+        let result = self.shutdown.shutdown(timeout).await;
+        self.status.lock().draining = false;
+        result
+    }
 }
 
 #[async_trait::async_trait]
@@ -157,15 +366,21 @@ impl SecurityDaemon for SessionIsolatorDaemon {
 
     async fn run(&self) {
         {
-            let mut status = self.status.lock().unwrap();
+            let mut status = self.status.lock();
             status.running = true;
             status.started_at = Some(Instant::now());
         }
 
-        while !self.stop_flag.load(Ordering::SeqCst) {
+        while !self.shutdown.is_stopping() {
+            // Held for the whole cycle so `shutdown()` waits for it to finish.
+            let cycle_guard = match self.shutdown.enter() {
+                Some(guard) => guard,
+                None => break,
+            };
+
             // Process isolation requests
             let requests: Vec<IsolationRequest> = {
-                let mut queue = self.isolation_queue.lock().unwrap();
+                let mut queue = self.isolation_queue.lock();
                 queue.drain(..).collect()
             };
 
@@ -174,9 +389,10 @@ impl SecurityDaemon for SessionIsolatorDaemon {
 
                 // Store isolation
                 {
-                    let mut isolated = self.isolated.write().unwrap();
+                    let mut isolated = self.isolated.write();
                     isolated.insert(session.session_id.clone(), session.clone());
                 }
+                self.refresh_isolation_metrics();
 
                 // Emit event
                 let action = if session.restrictions.iter().any(|r| matches!(r, Restriction::Terminated)) {
@@ -192,36 +408,69 @@ impl SecurityDaemon for SessionIsolatorDaemon {
 
                 // Update status
                 {
-                    let mut status = self.status.lock().unwrap();
+                    let mut status = self.status.lock();
                     status.events_emitted += 1;
                 }
+                self.metrics.record_event("session_isolator");
             }
 
             // Cleanup expired isolations
             self.cleanup_expired();
+            self.refresh_isolation_metrics();
 
             // Update status
             {
-                let mut status = self.status.lock().unwrap();
+                let mut status = self.status.lock();
                 status.cycles += 1;
                 status.last_cycle = Some(Instant::now());
             }
+            self.metrics.record_cycle("session_isolator");
+            drop(cycle_guard);
 
             tokio::time::sleep(self.config.interval).await;
         }
 
         {
-            let mut status = self.status.lock().unwrap();
+            let mut status = self.status.lock();
             status.running = false;
         }
     }
 
     fn stop(&self) {
-        self.stop_flag.store(true, Ordering::SeqCst);
+        self.shutdown.signal();
     }
 
     fn status(&self) -> DaemonStatus {
-        self.status.lock().unwrap().clone()
+        self.status.lock().clone()
+    }
+}
+
+/// Tunable knobs for `TarpitControllerDaemon`: how fast `delay_ms` escalates
+/// on each `engage()`, how long an entity must sit idle before `run`'s decay
+/// pass starts unwinding that escalation, and how much randomized jitter
+/// `engage` applies to the delay it returns so an attacker can't fingerprint
+/// the exact escalation curve and time around it.
+#[derive(Debug, Clone)]
+pub struct TarpitConfig {
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+    pub escalation_factor: f64,
+    /// Idle time (ms) since an entity's last `engage()` before it starts
+    /// decaying back toward `base_delay_ms`.
+    pub decay_window_ms: u64,
+    /// Bounded jitter applied to `engage`'s returned delay, e.g. `0.2` for ±20%.
+    pub jitter_fraction: f64,
+}
+
+impl Default for TarpitConfig {
+    fn default() -> Self {
+        Self {
+            base_delay_ms: 1000,     // 1 second base
+            max_delay_ms: 60000,     // 60 seconds max
+            escalation_factor: 1.5,
+            decay_window_ms: 60000,  // 1 minute idle before decay starts
+            jitter_fraction: 0.2,
+        }
     }
 }
 
@@ -229,15 +478,17 @@ impl SecurityDaemon for SessionIsolatorDaemon {
 /// Introduces delays to waste attacker resources
 pub struct TarpitControllerDaemon {
     config: DaemonConfig,
-    stop_flag: Arc<AtomicBool>,
+    shutdown: ShutdownHandle,
     status: Arc<Mutex<DaemonStatus>>,
     event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>,
     /// Tarpitted entities
     tarpits: Arc<RwLock<HashMap<String, TarpitEntry>>>,
-    /// Base delay (ms)
-    base_delay_ms: u64,
-    /// Max delay (ms)
-    max_delay_ms: u64,
+    tarpit_config: TarpitConfig,
+    /// Persisted per-entity escalation level, so a crash/restart mid-attack
+    /// doesn't reset an entity back to `base_delay_ms` for free.
+    store: Arc<dyn StateStore>,
+    /// Shared Layer-4 metrics registry
+    metrics: Arc<DefenseMetrics>,
 }
 
 #[derive(Debug, Clone)]
@@ -246,67 +497,186 @@ pub struct TarpitEntry {
     pub reason: String,
     pub delay_ms: u64,
     pub engaged_at: DateTime<Utc>,
+    pub last_request_at: DateTime<Utc>,
     pub request_count: u64,
-    pub escalation_factor: f64,
 }
 
 impl TarpitControllerDaemon {
     pub fn new(event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>) -> Self {
+        Self::with_config(event_tx, TarpitConfig::default())
+    }
+
+    /// Create with non-default escalation/decay/jitter settings
+    pub fn with_config(event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>, tarpit_config: TarpitConfig) -> Self {
+        Self::with_config_and_metrics(event_tx, tarpit_config, DefenseMetrics::new())
+    }
+
+    /// Create sharing a `DefenseMetrics` registry with the other Layer-4 daemons.
+    pub fn with_metrics(event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>, metrics: Arc<DefenseMetrics>) -> Self {
+        Self::with_config_and_metrics(event_tx, TarpitConfig::default(), metrics)
+    }
+
+    /// Create with both non-default settings and a shared metrics registry
+    pub fn with_config_and_metrics(
+        event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>,
+        tarpit_config: TarpitConfig,
+        metrics: Arc<DefenseMetrics>,
+    ) -> Self {
+        Self::with_config_store_and_metrics(event_tx, tarpit_config, Arc::new(InMemoryStore::new()), metrics)
+    }
+
+    /// Create backed by a non-default persistence store (e.g. `FileBackedStore`,
+    /// to survive restarts or share escalation levels across cage instances).
+    pub fn with_store(event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>, store: Arc<dyn StateStore>) -> Self {
+        Self::with_config_store_and_metrics(event_tx, TarpitConfig::default(), store, DefenseMetrics::new())
+    }
+
+    /// Create with both a non-default persistence store and a shared metrics registry
+    pub fn with_store_and_metrics(
+        event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>,
+        store: Arc<dyn StateStore>,
+        metrics: Arc<DefenseMetrics>,
+    ) -> Self {
+        Self::with_config_store_and_metrics(event_tx, TarpitConfig::default(), store, metrics)
+    }
+
+    /// Create with non-default settings, persistence store, and metrics registry all at once
+    pub fn with_config_store_and_metrics(
+        event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>,
+        tarpit_config: TarpitConfig,
+        store: Arc<dyn StateStore>,
+        metrics: Arc<DefenseMetrics>,
+    ) -> Self {
         Self {
             config: DaemonConfig {
                 interval: Duration::from_secs(1),
                 ..Default::default()
             },
-            stop_flag: Arc::new(AtomicBool::new(false)),
+            shutdown: ShutdownHandle::new(),
             status: Arc::new(Mutex::new(DaemonStatus::default())),
             event_tx,
             tarpits: Arc::new(RwLock::new(HashMap::new())),
-            base_delay_ms: 1000,   // 1 second base
-            max_delay_ms: 60000,   // 60 seconds max
+            tarpit_config,
+            store,
+            metrics,
         }
     }
 
-    /// Engage tarpit for entity
-    pub fn engage(&self, entity: &str, reason: &str) -> u64 {
-        let mut tarpits = self.tarpits.write().unwrap();
+    /// Engage tarpit for entity. Returns `None` (engaging nothing) once a
+    /// `shutdown()` has been requested, instead of starting new work after
+    /// the daemon has been asked to stop.
+    pub async fn engage(&self, entity: &str, reason: &str) -> Option<u64> {
+        // Counted as in-flight for the duration of this call so a concurrent
+        // `shutdown()` waits for it instead of the caller racing a stop.
+        let _in_flight = self.shutdown.enter()?;
+
+        // Read the escalation level persisted from *before this call* so a
+        // freshly (re)created in-memory entry after a restart seeds its
+        // delay from where the entity left off instead of back at
+        // base_delay_ms, then bump it so the next restart sees this engage.
+        let prior_level = self.store.load_escalation(entity).await;
+        self.store.bump_escalation(entity).await;
+
+        let mut tarpits = self.tarpits.write();
+        let now = Utc::now();
 
-        let entry = tarpits.entry(entity.to_string()).or_insert_with(|| TarpitEntry {
-            entity: entity.to_string(),
-            reason: reason.to_string(),
-            delay_ms: self.base_delay_ms,
-            engaged_at: Utc::now(),
-            request_count: 0,
-            escalation_factor: 1.5,
+        let entry = tarpits.entry(entity.to_string()).or_insert_with(|| {
+            let seeded_delay = ((self.tarpit_config.base_delay_ms as f64)
+                * self.tarpit_config.escalation_factor.powi(prior_level as i32))
+                .min(self.tarpit_config.max_delay_ms as f64) as u64;
+            TarpitEntry {
+                entity: entity.to_string(),
+                reason: reason.to_string(),
+                delay_ms: seeded_delay.max(self.tarpit_config.base_delay_ms),
+                engaged_at: now,
+                last_request_at: now,
+                request_count: prior_level as u64,
+            }
         });
 
         entry.request_count += 1;
+        entry.last_request_at = now;
 
         // Escalate delay
-        entry.delay_ms = ((entry.delay_ms as f64) * entry.escalation_factor) as u64;
-        entry.delay_ms = entry.delay_ms.min(self.max_delay_ms);
+        entry.delay_ms = ((entry.delay_ms as f64) * self.tarpit_config.escalation_factor) as u64;
+        entry.delay_ms = entry.delay_ms.min(self.tarpit_config.max_delay_ms);
+        let escalated_delay = entry.delay_ms;
+
+        self.metrics.observe_tarpit_delay(escalated_delay);
+        self.metrics.record_tarpit_engagement(reason);
 
-        entry.delay_ms
+        // Jitter only the delay handed back to the caller; the stored
+        // escalation curve stays exact so repeated engagements still
+        // escalate predictably from the daemon's point of view.
+        Some(apply_jitter(escalated_delay, self.tarpit_config.jitter_fraction))
     }
 
     /// Get current delay for entity
     pub fn get_delay(&self, entity: &str) -> Option<u64> {
-        let tarpits = self.tarpits.read().unwrap();
+        let tarpits = self.tarpits.read();
         tarpits.get(entity).map(|e| e.delay_ms)
     }
 
     /// Release entity from tarpit
     pub fn release(&self, entity: &str) {
-        let mut tarpits = self.tarpits.write().unwrap();
+        let mut tarpits = self.tarpits.write();
         tarpits.remove(entity);
     }
 
     fn check_and_report(&self) -> Vec<TarpitEntry> {
-        let tarpits = self.tarpits.read().unwrap();
+        let tarpits = self.tarpits.read();
         tarpits.values()
             .filter(|e| e.request_count > 0)
             .cloned()
             .collect()
     }
+
+    /// Halve `delay_ms` for entities idle longer than `decay_window_ms`,
+    /// evicting the entry entirely once it decays back down to
+    /// `base_delay_ms` — at that point it's indistinguishable from an entity
+    /// that was never tarpitted, so there's nothing left worth keeping.
+    fn decay_idle_entries(&self) {
+        let mut tarpits = self.tarpits.write();
+        let now = Utc::now();
+        let window = chrono::Duration::milliseconds(self.tarpit_config.decay_window_ms as i64);
+        let base = self.tarpit_config.base_delay_ms;
+
+        tarpits.retain(|_, entry| {
+            if now - entry.last_request_at < window {
+                return true;
+            }
+
+            let decayed = (entry.delay_ms / 2).max(base);
+            if decayed <= base {
+                false
+            } else {
+                entry.delay_ms = decayed;
+                true
+            }
+        });
+    }
+
+    /// Gracefully stop: signal `run` to exit, then wait up to `timeout` for
+    /// any `engage()` call already in flight to finish escalating before
+    /// reporting on
+    /// `DaemonStatus::draining`. Prefer this over the bare `stop()` from
+    /// `SecurityDaemon` when the caller can await the result.
+    pub async fn shutdown(&self, timeout: Duration) -> Result<DrainReport, DrainTimeout> {
+        self.status.lock().draining = true;
+        let result = self.shutdown.shutdown(timeout).await;
+        self.status.lock().draining = false;
+        result
+    }
+}
+
+/// Apply bounded random jitter (±`jitter_fraction`) to `delay_ms`, e.g.
+/// `jitter_fraction = 0.2` returns a value within ±20% of `delay_ms`.
+fn apply_jitter(delay_ms: u64, jitter_fraction: f64) -> u64 {
+    if jitter_fraction <= 0.0 {
+        return delay_ms;
+    }
+    let offset = (rand::random::<f64>() * 2.0 - 1.0) * jitter_fraction;
+    ((delay_ms as f64) * (1.0 + offset)).max(0.0) as u64
 }
 
 #[async_trait::async_trait]
@@ -321,12 +691,12 @@ impl SecurityDaemon for TarpitControllerDaemon {
 
     async fn run(&self) {
         {
-            let mut status = self.status.lock().unwrap();
+            let mut status = self.status.lock();
             status.running = true;
             status.started_at = Some(Instant::now());
         }
 
-        while !self.stop_flag.load(Ordering::SeqCst) {
+        while !self.shutdown.is_stopping() {
             // Report active tarpits
             let active = self.check_and_report();
 
@@ -338,28 +708,33 @@ impl SecurityDaemon for TarpitControllerDaemon {
                 });
             }
 
+            // Let reformed clients wind back down instead of staying
+            // maximally penalized forever
+            self.decay_idle_entries();
+
             // Update status
             {
-                let mut status = self.status.lock().unwrap();
+                let mut status = self.status.lock();
                 status.cycles += 1;
                 status.last_cycle = Some(Instant::now());
             }
+            self.metrics.record_cycle("tarpit_controller");
 
             tokio::time::sleep(self.config.interval).await;
         }
 
         {
-            let mut status = self.status.lock().unwrap();
+            let mut status = self.status.lock();
             status.running = false;
         }
     }
 
     fn stop(&self) {
-        self.stop_flag.store(true, Ordering::SeqCst);
+        self.shutdown.signal();
     }
 
     fn status(&self) -> DaemonStatus {
-        self.status.lock().unwrap().clone()
+        self.status.lock().clone()
     }
 }
 
@@ -367,13 +742,15 @@ impl SecurityDaemon for TarpitControllerDaemon {
 /// Modifies responses to confuse/mislead attackers
 pub struct ResponseMutatorDaemon {
     config: DaemonConfig,
-    stop_flag: Arc<AtomicBool>,
+    shutdown: ShutdownHandle,
     status: Arc<Mutex<DaemonStatus>>,
     event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>,
     /// Mutation rules
     rules: Arc<RwLock<Vec<MutationRule>>>,
-    /// Entities requiring mutation
-    mutate_list: Arc<RwLock<HashSet<String>>>,
+    /// Entities requiring mutation, persisted through `store`
+    store: Arc<dyn StateStore>,
+    /// Shared Layer-4 metrics registry
+    metrics: Arc<DefenseMetrics>,
 }
 
 #[derive(Debug, Clone)]
@@ -400,16 +777,37 @@ pub enum MutationType {
 
 impl ResponseMutatorDaemon {
     pub fn new(event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>) -> Self {
+        Self::with_metrics(event_tx, DefenseMetrics::new())
+    }
+
+    /// Create sharing a `DefenseMetrics` registry with the other Layer-4 daemons.
+    pub fn with_metrics(event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>, metrics: Arc<DefenseMetrics>) -> Self {
+        Self::with_store_and_metrics(event_tx, Arc::new(InMemoryStore::new()), metrics)
+    }
+
+    /// Create backed by a non-default persistence store (e.g. `FileBackedStore`,
+    /// so the mutate-list survives a restart).
+    pub fn with_store(event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>, store: Arc<dyn StateStore>) -> Self {
+        Self::with_store_and_metrics(event_tx, store, DefenseMetrics::new())
+    }
+
+    /// Create with both a non-default persistence store and a shared metrics registry
+    pub fn with_store_and_metrics(
+        event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>,
+        store: Arc<dyn StateStore>,
+        metrics: Arc<DefenseMetrics>,
+    ) -> Self {
         Self {
             config: DaemonConfig {
                 interval: Duration::from_secs(1),
                 ..Default::default()
             },
-            stop_flag: Arc::new(AtomicBool::new(false)),
+            shutdown: ShutdownHandle::new(),
             status: Arc::new(Mutex::new(DaemonStatus::default())),
             event_tx,
             rules: Arc::new(RwLock::new(Self::default_rules())),
-            mutate_list: Arc::new(RwLock::new(HashSet::new())),
+            store,
+            metrics,
         }
     }
 
@@ -439,30 +837,33 @@ impl ResponseMutatorDaemon {
     }
 
     /// Add entity to mutation list
-    pub fn add_to_mutate_list(&self, entity: &str) {
-        let mut list = self.mutate_list.write().unwrap();
-        list.insert(entity.to_string());
+    pub async fn add_to_mutate_list(&self, entity: &str) {
+        self.store.add_mutate_target(entity).await;
     }
 
     /// Remove entity from mutation list
-    pub fn remove_from_mutate_list(&self, entity: &str) {
-        let mut list = self.mutate_list.write().unwrap();
-        list.remove(entity);
+    pub async fn remove_from_mutate_list(&self, entity: &str) {
+        self.store.remove_mutate_target(entity).await;
     }
 
     /// Check if entity requires mutation
-    pub fn requires_mutation(&self, entity: &str) -> bool {
-        let list = self.mutate_list.read().unwrap();
-        list.contains(entity)
+    pub async fn requires_mutation(&self, entity: &str) -> bool {
+        self.store.list_mutate_targets().await.iter().any(|e| e == entity)
     }
 
-    /// Apply mutations to response
-    pub fn mutate_response(&self, entity: &str, response: &str) -> (String, Vec<String>) {
-        if !self.requires_mutation(entity) {
-            return (response.to_string(), Vec::new());
+    /// Apply mutations to response. Returns `None` once a `shutdown()` has
+    /// been requested, instead of starting new work after the daemon has
+    /// been asked to stop.
+    pub async fn mutate_response(&self, entity: &str, response: &str) -> Option<(String, Vec<String>)> {
+        // Counted as in-flight for the duration of this call so a concurrent
+        // `shutdown()` waits for it instead of the caller racing a stop.
+        let _in_flight = self.shutdown.enter()?;
+
+        if !self.requires_mutation(entity).await {
+            return Some((response.to_string(), Vec::new()));
         }
 
-        let rules = self.rules.read().unwrap();
+        let rules = self.rules.read();
         let mut result = response.to_string();
         let mut applied = Vec::new();
 
@@ -500,7 +901,18 @@ impl ResponseMutatorDaemon {
             }
         }
 
-        (result, applied)
+        Some((result, applied))
+    }
+
+    /// Gracefully stop: signal `run` to exit, then wait up to `timeout` for
+    /// any `mutate_response()` call already in flight to finish before
+    /// reporting on `DaemonStatus::draining`. Prefer this over the bare
+    /// `stop()` from `SecurityDaemon` when the caller can await the result.
+    pub async fn shutdown(&self, timeout: Duration) -> Result<DrainReport, DrainTimeout> {
+        self.status.lock().draining = true;
+        let result = self.shutdown.shutdown(timeout).await;
+        self.status.lock().draining = false;
+        result
     }
 }
 
@@ -516,17 +928,15 @@ impl SecurityDaemon for ResponseMutatorDaemon {
 
     async fn run(&self) {
         {
-            let mut status = self.status.lock().unwrap();
+            let mut status = self.status.lock();
             status.running = true;
             status.started_at = Some(Instant::now());
         }
 
-        while !self.stop_flag.load(Ordering::SeqCst) {
+        while !self.shutdown.is_stopping() {
             // Report mutation list size
-            let list_size = {
-                let list = self.mutate_list.read().unwrap();
-                list.len()
-            };
+            let list_size = self.store.list_mutate_targets().await.len();
+            self.metrics.set_mutate_list_size(list_size);
 
             if list_size > 0 {
                 // Could emit stats here
@@ -534,62 +944,281 @@ impl SecurityDaemon for ResponseMutatorDaemon {
 
             // Update status
             {
-                let mut status = self.status.lock().unwrap();
+                let mut status = self.status.lock();
                 status.cycles += 1;
                 status.last_cycle = Some(Instant::now());
             }
+            self.metrics.record_cycle("response_mutator");
 
             tokio::time::sleep(self.config.interval).await;
         }
 
         {
-            let mut status = self.status.lock().unwrap();
+            let mut status = self.status.lock();
             status.running = false;
         }
     }
 
     fn stop(&self) {
-        self.stop_flag.store(true, Ordering::SeqCst);
+        self.shutdown.signal();
     }
 
     fn status(&self) -> DaemonStatus {
-        self.status.lock().unwrap().clone()
+        self.status.lock().clone()
     }
 }
 
+/// How many `run` cycles (each `config.interval`, 100ms by default) between
+/// GC sweeps of fully-recovered buckets. At the default interval this is
+/// every ~5 seconds, coarse enough that a bucket isn't evicted and
+/// immediately recreated across a single burst of requests.
+const BUCKET_GC_INTERVAL_CYCLES: u64 = 50;
+
+/// How many times `check_rate_limit`'s hot path spins attempting a
+/// non-blocking `try_write` on `buckets` before falling back to a blocking
+/// `write`. Keeps the common, uncontended case lock-free-ish without risking
+/// starvation if another request is genuinely holding the lock.
+const BUCKET_WRITE_SPIN_ATTEMPTS: u32 = 4;
+
 /// Rate Limit Enforcer Daemon
 /// Enforces rate limits across 5 layers
+///
+/// Lock ordering: `check_rate_limit` and the refill/GC passes in `run` always
+/// touch the five layers in the same order — global, provider, token,
+/// session, entity — matching `RateLimitLayer`'s declaration order. They're
+/// all reached through the single `buckets` lock, but `KeyedLevel::check`
+/// also reads `limits` while `buckets` is held; nothing ever takes `limits`
+/// before `buckets`, so that pair can't deadlock. Keep any future code that
+/// locks these layers individually in the same global-first order.
 pub struct RateLimitEnforcerDaemon {
     config: DaemonConfig,
-    stop_flag: Arc<AtomicBool>,
+    shutdown: ShutdownHandle,
     status: Arc<Mutex<DaemonStatus>>,
     event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>,
+    /// Per-layer capacity/refill-rate, shared with every `KeyedLevel` so
+    /// `set_level_config` takes effect on the next bucket created for that layer
+    limits: Arc<RwLock<RateLimitConfig>>,
     /// Rate limit buckets by layer
     buckets: Arc<RwLock<RateLimitBuckets>>,
+    /// Shared Layer-4 metrics registry
+    metrics: Arc<DefenseMetrics>,
 }
 
+/// One of the five ordered layers `RateLimitEnforcerDaemon::check_rate_limit`
+/// walks through. Used both as the map key for `RateLimitConfig` and to tag
+/// which layer rejected in `RateLimitResult`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitLayer {
+    Global,
+    Provider,
+    Token,
+    Session,
+    Entity,
+}
+
+impl RateLimitLayer {
+    fn as_u8(self) -> u8 {
+        match self {
+            RateLimitLayer::Global => 1,
+            RateLimitLayer::Provider => 2,
+            RateLimitLayer::Token => 3,
+            RateLimitLayer::Session => 4,
+            RateLimitLayer::Entity => 5,
+        }
+    }
+
+    fn label(self, key: &str) -> String {
+        match self {
+            RateLimitLayer::Global => "global".to_string(),
+            RateLimitLayer::Provider => format!("provider:{}", key),
+            RateLimitLayer::Token => format!("token:{}", &key[..8.min(key.len())]),
+            RateLimitLayer::Session => format!("session:{}", key),
+            RateLimitLayer::Entity => format!("entity:{}", key),
+        }
+    }
+}
+
+/// Per-layer `(capacity, refill_rate)`, defaulted to the original hardcoded
+/// values but overridable at construction (`RateLimitEnforcerDaemon::with_config`)
+/// and mutable at runtime (`RateLimitEnforcerDaemon::set_level_config`).
 #[derive(Debug, Clone)]
-pub struct RateLimitBuckets {
-    /// Layer 1: Global rate limit
-    pub global: TokenBucket,
-    /// Layer 2: Per-provider limits
-    pub providers: HashMap<String, TokenBucket>,
-    /// Layer 3: Per-token limits (API key)
-    pub tokens: HashMap<String, TokenBucket>,
-    /// Layer 4: Per-session limits
-    pub sessions: HashMap<String, TokenBucket>,
-    /// Layer 5: Per-entity limits
-    pub entities: HashMap<String, TokenBucket>,
-}
-
-impl Default for RateLimitBuckets {
+pub struct RateLimitConfig {
+    levels: HashMap<RateLimitLayer, (u32, u32)>,
+}
+
+impl Default for RateLimitConfig {
     fn default() -> Self {
+        let mut levels = HashMap::new();
+        levels.insert(RateLimitLayer::Global, (1000, 100));
+        levels.insert(RateLimitLayer::Provider, (100, 20));
+        levels.insert(RateLimitLayer::Token, (50, 10));
+        levels.insert(RateLimitLayer::Session, (30, 5));
+        levels.insert(RateLimitLayer::Entity, (20, 2));
+        Self { levels }
+    }
+}
+
+impl RateLimitConfig {
+    pub fn get(&self, layer: RateLimitLayer) -> (u32, u32) {
+        self.levels.get(&layer).copied().unwrap_or((100, 10))
+    }
+
+    pub fn set(&mut self, layer: RateLimitLayer, capacity: u32, refill_rate: u32) {
+        self.levels.insert(layer, (capacity, refill_rate));
+    }
+}
+
+/// A rate-limit layer's bucket storage: either the single global bucket or a
+/// keyed collection (one bucket per provider/token/session/entity).
+/// `check_rate_limit` walks an ordered list of these instead of hand-rolling
+/// the same consume-or-reject block per layer.
+pub trait MapLevel {
+    fn check(&mut self, key: &str, cost: u32) -> Result<(), RateLimitResult>;
+    fn refill(&mut self);
+    fn remove_full_buckets(&mut self);
+    fn bucket_count(&self) -> usize;
+}
+
+/// Layer 1: the single global bucket
+pub struct GlobalLevel {
+    bucket: TokenBucket,
+}
+
+impl GlobalLevel {
+    fn new(config: &RateLimitConfig) -> Self {
+        let (capacity, refill_rate) = config.get(RateLimitLayer::Global);
+        Self { bucket: TokenBucket::new(capacity, refill_rate) }
+    }
+}
+
+impl MapLevel for GlobalLevel {
+    fn check(&mut self, _key: &str, cost: u32) -> Result<(), RateLimitResult> {
+        if self.bucket.try_consume(cost) {
+            Ok(())
+        } else {
+            Err(RateLimitResult {
+                layer: RateLimitLayer::Global.as_u8(),
+                limit: RateLimitLayer::Global.label(""),
+                retry_after: self.bucket.time_until_available(cost),
+            })
+        }
+    }
+
+    fn refill(&mut self) {
+        self.bucket.refill();
+    }
+
+    fn remove_full_buckets(&mut self) {
+        // The global bucket is never recycled; it always exists.
+    }
+
+    fn bucket_count(&self) -> usize {
+        1
+    }
+}
+
+/// Layers 2-5: one bucket per key (provider, token, session, or entity),
+/// created lazily on first use from the layer's current config.
+pub struct KeyedLevel {
+    layer: RateLimitLayer,
+    config: Arc<RwLock<RateLimitConfig>>,
+    buckets: HashMap<String, TokenBucket>,
+}
+
+impl KeyedLevel {
+    fn new(layer: RateLimitLayer, config: Arc<RwLock<RateLimitConfig>>) -> Self {
+        Self { layer, config, buckets: HashMap::new() }
+    }
+}
+
+impl MapLevel for KeyedLevel {
+    fn check(&mut self, key: &str, cost: u32) -> Result<(), RateLimitResult> {
+        let (capacity, refill_rate) = self.config.read().get(self.layer);
+        let bucket = self.buckets.entry(key.to_string())
+            .or_insert_with(|| TokenBucket::new(capacity, refill_rate));
+
+        if bucket.try_consume(cost) {
+            Ok(())
+        } else {
+            Err(RateLimitResult {
+                layer: self.layer.as_u8(),
+                limit: self.layer.label(key),
+                retry_after: bucket.time_until_available(cost),
+            })
+        }
+    }
+
+    fn refill(&mut self) {
+        for bucket in self.buckets.values_mut() {
+            bucket.refill();
+        }
+    }
+
+    fn remove_full_buckets(&mut self) {
+        self.buckets.retain(|_, b| { b.refill(); !b.is_fully_recovered() });
+    }
+
+    fn bucket_count(&self) -> usize {
+        self.buckets.len()
+    }
+}
+
+/// Rate limit buckets for all 5 layers
+pub struct RateLimitBuckets {
+    pub global: GlobalLevel,
+    pub providers: KeyedLevel,
+    pub tokens: KeyedLevel,
+    pub sessions: KeyedLevel,
+    pub entities: KeyedLevel,
+}
+
+impl RateLimitBuckets {
+    fn new(config: Arc<RwLock<RateLimitConfig>>) -> Self {
+        let global = GlobalLevel::new(&config.read());
         Self {
-            global: TokenBucket::new(1000, 100), // 1000 tokens, 100/sec refill
-            providers: HashMap::new(),
-            tokens: HashMap::new(),
-            sessions: HashMap::new(),
-            entities: HashMap::new(),
+            global,
+            providers: KeyedLevel::new(RateLimitLayer::Provider, config.clone()),
+            tokens: KeyedLevel::new(RateLimitLayer::Token, config.clone()),
+            sessions: KeyedLevel::new(RateLimitLayer::Session, config.clone()),
+            entities: KeyedLevel::new(RateLimitLayer::Entity, config),
+        }
+    }
+}
+
+/// Burst-vs-throughput tuning for a single `TokenBucket`, applied via
+/// `TokenBucket::preconfig_burst`/`preconfig_throughput`. Distinct from
+/// `RateLimitConfig`, which holds the per-*layer* `(capacity, refill_rate)`
+/// pairs `RateLimitEnforcerDaemon` picks a bucket's nominal size from — this
+/// is about how aggressively a single bucket spends and refills that budget.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenBucketConfig {
+    /// Fraction of the nominal capacity this bucket will aggressively
+    /// consume before throttling, e.g. `0.99` for burst, `0.47` for throughput.
+    pub burst_pct: f32,
+    /// Added to each refill window as a safety buffer against clock skew /
+    /// round-trip latency before a token is credited back.
+    pub duration_overhead: Duration,
+    /// How many times `try_consume_with_retry` will sleep out the
+    /// time-to-next-token before giving up.
+    pub retries: u8,
+}
+
+impl TokenBucketConfig {
+    /// Spend nearly the whole budget quickly, then idle — latency-optimized.
+    pub fn burst() -> Self {
+        Self {
+            burst_pct: 0.99,
+            duration_overhead: Duration::from_millis(989),
+            retries: 3,
+        }
+    }
+
+    /// Spread consumption evenly to stay well under the ceiling — safety-optimized.
+    pub fn throughput() -> Self {
+        Self {
+            burst_pct: 0.47,
+            duration_overhead: Duration::from_millis(10),
+            retries: 3,
         }
     }
 }
@@ -600,6 +1229,10 @@ pub struct TokenBucket {
     pub tokens: f64,
     pub refill_rate: f64, // tokens per second
     pub last_refill: Instant,
+    /// Set by `preconfig_burst`/`preconfig_throughput`; `None` for a plain
+    /// `TokenBucket::new`, which refills continuously against the full
+    /// `capacity` with no windowing.
+    preset: Option<TokenBucketConfig>,
 }
 
 impl TokenBucket {
@@ -609,6 +1242,38 @@ impl TokenBucket {
             tokens: capacity as f64,
             refill_rate: refill_rate as f64,
             last_refill: Instant::now(),
+            preset: None,
+        }
+    }
+
+    /// Burst preset: lets you spend nearly the whole budget quickly then idle.
+    pub fn preconfig_burst(rate: u32) -> Self {
+        Self::with_preset(rate, TokenBucketConfig::burst())
+    }
+
+    /// Throughput preset: spreads consumption evenly to stay well under the ceiling.
+    pub fn preconfig_throughput(rate: u32) -> Self {
+        Self::with_preset(rate, TokenBucketConfig::throughput())
+    }
+
+    fn with_preset(rate: u32, preset: TokenBucketConfig) -> Self {
+        let mut bucket = Self {
+            capacity: rate,
+            tokens: 0.0,
+            refill_rate: rate as f64,
+            last_refill: Instant::now(),
+            preset: Some(preset),
+        };
+        bucket.tokens = bucket.effective_capacity();
+        bucket
+    }
+
+    /// `floor(capacity * burst_pct)` for a preset bucket, or the full
+    /// `capacity` for a plain one.
+    fn effective_capacity(&self) -> f64 {
+        match self.preset {
+            Some(cfg) => (self.capacity as f32 * cfg.burst_pct).floor() as f64,
+            None => self.capacity as f64,
         }
     }
 
@@ -616,7 +1281,9 @@ impl TokenBucket {
         self.refill();
 
         if self.tokens >= tokens as f64 {
-            self.tokens -= tokens as f64;
+            // Clamp to 0.0 so float rounding on a near-exact consume can
+            // never leave a tiny negative balance behind.
+            self.tokens = (self.tokens - tokens as f64).max(0.0);
             true
         } else {
             false
@@ -624,10 +1291,43 @@ impl TokenBucket {
     }
 
     pub fn refill(&mut self) {
-        let now = Instant::now();
-        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
-        self.tokens = (self.tokens + elapsed * self.refill_rate).min(self.capacity as f64);
-        self.last_refill = now;
+        match self.preset {
+            None => {
+                let now = Instant::now();
+                let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+                self.tokens = (self.tokens + elapsed * self.refill_rate).clamp(0.0, self.capacity as f64);
+                self.last_refill = now;
+            }
+            Some(_) => {
+                // Windowed refill: credit whole tokens only once a full
+                // `refill_window()` has elapsed, carrying over any leftover
+                // sub-window time so slow polling doesn't lose progress.
+                let window = self.refill_window();
+                let elapsed = Instant::now().duration_since(self.last_refill);
+                let whole_windows = (elapsed.as_secs_f64() / window.as_secs_f64()).floor();
+                if whole_windows >= 1.0 {
+                    self.tokens = (self.tokens + whole_windows).clamp(0.0, self.effective_capacity());
+                    self.last_refill += window.mul_f64(whole_windows);
+                }
+            }
+        }
+    }
+
+    /// Time to credit a single token: `1 / refill_rate` seconds, plus the
+    /// preset's `duration_overhead` buffer if one is configured.
+    fn refill_window(&self) -> Duration {
+        let base = Duration::from_secs_f64(1.0 / self.refill_rate);
+        match self.preset {
+            Some(cfg) => base + cfg.duration_overhead,
+            None => base,
+        }
+    }
+
+    /// True once this bucket has fully recovered to its (effective) capacity
+    /// after a `refill()`, i.e. it's behaviorally identical to a freshly
+    /// constructed one and can be dropped with no loss of enforcement state.
+    pub fn is_fully_recovered(&self) -> bool {
+        self.tokens >= self.effective_capacity()
     }
 
     pub fn time_until_available(&self, tokens: u32) -> Duration {
@@ -635,26 +1335,79 @@ impl TokenBucket {
             Duration::ZERO
         } else {
             let needed = tokens as f64 - self.tokens;
-            Duration::from_secs_f64(needed / self.refill_rate)
+            match self.preset {
+                None => Duration::from_secs_f64(needed / self.refill_rate),
+                Some(_) => self.refill_window().mul_f64(needed),
+            }
+        }
+    }
+
+    /// On exhaustion, sleeps out the computed time-to-next-token and retries
+    /// up to the preset's `retries` count before giving up. A plain (preset-less)
+    /// bucket never retries — only the burst/throughput presets have a configured
+    /// `retries` count to draw from.
+    pub async fn try_consume_with_retry(&mut self, tokens: u32) -> bool {
+        let retries = self.preset.map(|cfg| cfg.retries).unwrap_or(0);
+
+        for attempt in 0..=retries {
+            if self.try_consume(tokens) {
+                return true;
+            }
+            if attempt == retries {
+                return false;
+            }
+            tokio::time::sleep(self.time_until_available(tokens)).await;
         }
+
+        false
     }
 }
 
 impl RateLimitEnforcerDaemon {
     pub fn new(event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>) -> Self {
+        Self::with_config(event_tx, RateLimitConfig::default())
+    }
+
+    /// Create with non-default per-layer capacity/refill-rate
+    pub fn with_config(event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>, limits: RateLimitConfig) -> Self {
+        Self::with_config_and_metrics(event_tx, limits, DefenseMetrics::new())
+    }
+
+    /// Create sharing a `DefenseMetrics` registry with the other Layer-4 daemons.
+    pub fn with_metrics(event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>, metrics: Arc<DefenseMetrics>) -> Self {
+        Self::with_config_and_metrics(event_tx, RateLimitConfig::default(), metrics)
+    }
+
+    /// Create with both a non-default per-layer config and a shared metrics registry
+    pub fn with_config_and_metrics(
+        event_tx: mpsc::UnboundedSender<SecurityDaemonEvent>,
+        limits: RateLimitConfig,
+        metrics: Arc<DefenseMetrics>,
+    ) -> Self {
+        let limits = Arc::new(RwLock::new(limits));
         Self {
             config: DaemonConfig {
                 interval: Duration::from_millis(100),
                 ..Default::default()
             },
-            stop_flag: Arc::new(AtomicBool::new(false)),
+            shutdown: ShutdownHandle::new(),
             status: Arc::new(Mutex::new(DaemonStatus::default())),
             event_tx,
-            buckets: Arc::new(RwLock::new(RateLimitBuckets::default())),
+            buckets: Arc::new(RwLock::new(RateLimitBuckets::new(limits.clone()))),
+            limits,
+            metrics,
         }
     }
 
-    /// Check if request is allowed
+    /// Change a layer's capacity/refill-rate at runtime. Takes effect for
+    /// buckets created after this call; existing per-key buckets keep
+    /// whatever capacity they were created with until GC'd and recreated.
+    pub fn set_level_config(&self, layer: RateLimitLayer, capacity: u32, refill_rate: u32) {
+        self.limits.write().set(layer, capacity, refill_rate);
+    }
+
+    /// Check if request is allowed. Walks the five layers in order and
+    /// returns the first that rejects.
     pub fn check_rate_limit(
         &self,
         entity: &str,
@@ -663,67 +1416,77 @@ impl RateLimitEnforcerDaemon {
         token: &str,
         cost: u32,
     ) -> Result<(), RateLimitResult> {
-        let mut buckets = self.buckets.write().unwrap();
-
-        // Layer 1: Global
-        if !buckets.global.try_consume(cost) {
-            return Err(RateLimitResult {
-                layer: 1,
-                limit: "global".to_string(),
-                retry_after: buckets.global.time_until_available(cost),
-            });
-        }
-
-        // Layer 2: Provider
-        let provider_bucket = buckets.providers
-            .entry(provider.to_string())
-            .or_insert_with(|| TokenBucket::new(100, 20));
-        if !provider_bucket.try_consume(cost) {
-            return Err(RateLimitResult {
-                layer: 2,
-                limit: format!("provider:{}", provider),
-                retry_after: provider_bucket.time_until_available(cost),
-            });
-        }
-
-        // Layer 3: Token
-        let token_bucket = buckets.tokens
-            .entry(token.to_string())
-            .or_insert_with(|| TokenBucket::new(50, 10));
-        if !token_bucket.try_consume(cost) {
-            return Err(RateLimitResult {
-                layer: 3,
-                limit: format!("token:{}", &token[..8.min(token.len())]),
-                retry_after: token_bucket.time_until_available(cost),
-            });
-        }
-
-        // Layer 4: Session
-        let session_bucket = buckets.sessions
-            .entry(session.to_string())
-            .or_insert_with(|| TokenBucket::new(30, 5));
-        if !session_bucket.try_consume(cost) {
-            return Err(RateLimitResult {
-                layer: 4,
-                limit: format!("session:{}", session),
-                retry_after: session_bucket.time_until_available(cost),
-            });
-        }
-
-        // Layer 5: Entity
-        let entity_bucket = buckets.entities
-            .entry(entity.to_string())
-            .or_insert_with(|| TokenBucket::new(20, 2));
-        if !entity_bucket.try_consume(cost) {
-            return Err(RateLimitResult {
-                layer: 5,
-                limit: format!("entity:{}", entity),
-                retry_after: entity_bucket.time_until_available(cost),
-            });
+        // Try a few non-blocking acquisitions first since this runs on every
+        // request; only fall back to a blocking wait if another request (or
+        // the refill/GC pass in `run`) is genuinely holding the lock.
+        let mut buckets = {
+            let mut guard = None;
+            for _ in 0..BUCKET_WRITE_SPIN_ATTEMPTS {
+                if let Some(g) = self.buckets.try_write() {
+                    guard = Some(g);
+                    break;
+                }
+                std::hint::spin_loop();
+            }
+            guard.unwrap_or_else(|| self.buckets.write())
+        };
+
+        let levels: [(&mut dyn MapLevel, &str); 5] = [
+            (&mut buckets.global, ""),
+            (&mut buckets.providers, provider),
+            (&mut buckets.tokens, token),
+            (&mut buckets.sessions, session),
+            (&mut buckets.entities, entity),
+        ];
+
+        for (level, key) in levels {
+            if let Err(e) = level.check(key, cost) {
+                self.metrics.record_rate_limit_rejection(e.layer);
+                return Err(e);
+            }
         }
 
         Ok(())
     }
+
+    /// Current number of tracked buckets for the given layer (layer 1 is the
+    /// single global bucket and always reports 1). Lets callers monitor map
+    /// growth between GC sweeps.
+    pub fn bucket_count(&self, layer: u8) -> usize {
+        let buckets = self.buckets.read();
+        match layer {
+            1 => buckets.global.bucket_count(),
+            2 => buckets.providers.bucket_count(),
+            3 => buckets.tokens.bucket_count(),
+            4 => buckets.sessions.bucket_count(),
+            5 => buckets.entities.bucket_count(),
+            _ => 0,
+        }
+    }
+
+    /// Drop buckets that have fully recovered to capacity, across every
+    /// keyed layer. A recovered bucket behaves identically to one that was
+    /// never created, so this is pure memory reclamation: nothing about
+    /// enforcement changes, it just keeps the maps from growing without
+    /// bound as new entities/sessions/tokens/providers appear.
+    fn gc_recovered_buckets(&self) {
+        let mut buckets = self.buckets.write();
+        buckets.providers.remove_full_buckets();
+        buckets.tokens.remove_full_buckets();
+        buckets.sessions.remove_full_buckets();
+        buckets.entities.remove_full_buckets();
+    }
+
+    /// Gracefully stop: signal `run` to exit and wait up to `timeout` for the
+    /// current refill cycle to finish before reporting on
+    /// `DaemonStatus::draining`. Prefer this over the bare `stop()` from
+    /// `SecurityDaemon` when the caller can await the result.
+    pub async fn shutdown(&self, timeout: Duration) -> Result<DrainReport, DrainTimeout> {
+        self.status.lock().draining = true;
+        let result = self.shutdown.shutdown(timeout).await;
+        self.status.lock().draining = false;
+        result
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -745,54 +1508,61 @@ impl SecurityDaemon for RateLimitEnforcerDaemon {
 
     async fn run(&self) {
         {
-            let mut status = self.status.lock().unwrap();
+            let mut status = self.status.lock();
             status.running = true;
             status.started_at = Some(Instant::now());
         }
 
-        while !self.stop_flag.load(Ordering::SeqCst) {
-            // Periodic cleanup of stale buckets
-            {
-                let mut buckets = self.buckets.write().unwrap();
+        let mut cycle: u64 = 0;
+        while !self.shutdown.is_stopping() {
+            // Held for the whole cycle so `shutdown()` waits for it to finish.
+            let cycle_guard = match self.shutdown.enter() {
+                Some(guard) => guard,
+                None => break,
+            };
 
-                // Refill all buckets
+            // Refill all buckets every cycle
+            {
+                let mut buckets = self.buckets.write();
                 buckets.global.refill();
-                for bucket in buckets.providers.values_mut() {
-                    bucket.refill();
-                }
-                for bucket in buckets.tokens.values_mut() {
-                    bucket.refill();
-                }
-                for bucket in buckets.sessions.values_mut() {
-                    bucket.refill();
-                }
-                for bucket in buckets.entities.values_mut() {
-                    bucket.refill();
-                }
+                buckets.providers.refill();
+                buckets.tokens.refill();
+                buckets.sessions.refill();
+                buckets.entities.refill();
+            }
+
+            // GC fully-recovered buckets on a coarser cadence than the hot
+            // refill loop above, so a burst of one-off entities/sessions
+            // doesn't thrash the maps every 100ms.
+            if cycle % BUCKET_GC_INTERVAL_CYCLES == 0 {
+                self.gc_recovered_buckets();
             }
+            cycle += 1;
 
             // Update status
             {
-                let mut status = self.status.lock().unwrap();
+                let mut status = self.status.lock();
                 status.cycles += 1;
                 status.last_cycle = Some(Instant::now());
             }
+            self.metrics.record_cycle("rate_limit_enforcer");
+            drop(cycle_guard);
 
             tokio::time::sleep(self.config.interval).await;
         }
 
         {
-            let mut status = self.status.lock().unwrap();
+            let mut status = self.status.lock();
             status.running = false;
         }
     }
 
     fn stop(&self) {
-        self.stop_flag.store(true, Ordering::SeqCst);
+        self.shutdown.signal();
     }
 
     fn status(&self) -> DaemonStatus {
-        self.status.lock().unwrap().clone()
+        self.status.lock().clone()
     }
 }
 
@@ -820,30 +1590,311 @@ mod tests {
     }
 
     #[test]
-    fn test_tarpit_escalation() {
+    fn test_preconfig_burst_caps_below_nominal_rate() {
+        let bucket = TokenBucket::preconfig_burst(100);
+
+        // burst_pct = 0.99, so 99 of the nominal 100 tokens are spendable
+        assert_eq!(bucket.effective_capacity(), 99.0);
+        assert_eq!(bucket.tokens, 99.0);
+    }
+
+    #[test]
+    fn test_preconfig_throughput_caps_well_below_nominal_rate() {
+        let bucket = TokenBucket::preconfig_throughput(100);
+
+        // burst_pct = 0.47, so only 47 of the nominal 100 tokens are spendable
+        assert_eq!(bucket.effective_capacity(), 47.0);
+        assert_eq!(bucket.tokens, 47.0);
+    }
+
+    #[tokio::test]
+    async fn test_try_consume_with_retry_succeeds_after_refill() {
+        let mut bucket = TokenBucket::preconfig_throughput(1000);
+        assert!(bucket.try_consume(bucket.effective_capacity() as u32));
+
+        // Exhausted; a plain try_consume would fail outright, but the retry
+        // variant should sleep out the short throughput refill window and
+        // pick up the next credited token.
+        assert!(bucket.try_consume_with_retry(1).await);
+    }
+
+    #[tokio::test]
+    async fn test_try_consume_with_retry_gives_up_after_retries_exhausted() {
+        let mut bucket = TokenBucket::with_preset(100, TokenBucketConfig {
+            burst_pct: 0.5,
+            duration_overhead: Duration::from_millis(10),
+            retries: 0,
+        });
+        assert!(bucket.try_consume(bucket.effective_capacity() as u32));
+
+        // retries: 0 means give up immediately on the first failed attempt
+        // rather than sleeping at all.
+        assert!(!bucket.try_consume_with_retry(1).await);
+    }
+
+    #[tokio::test]
+    async fn test_tarpit_escalation() {
         let (tx, _rx) = mpsc::unbounded_channel();
         let tarpit = TarpitControllerDaemon::new(tx);
 
-        let d1 = tarpit.engage("attacker1", "suspicious");
-        let d2 = tarpit.engage("attacker1", "suspicious");
-        let d3 = tarpit.engage("attacker1", "suspicious");
+        let d1 = tarpit.engage("attacker1", "suspicious").await.unwrap();
+        let d2 = tarpit.engage("attacker1", "suspicious").await.unwrap();
+        let d3 = tarpit.engage("attacker1", "suspicious").await.unwrap();
 
         // Delay should escalate
         assert!(d2 > d1);
         assert!(d3 > d2);
     }
 
+    #[tokio::test]
+    async fn test_tarpit_decay_halves_then_evicts_idle_entries() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let tarpit = TarpitControllerDaemon::with_config(
+            tx,
+            TarpitConfig {
+                base_delay_ms: 1000,
+                max_delay_ms: 60000,
+                escalation_factor: 1.5,
+                decay_window_ms: 1000,
+                jitter_fraction: 0.0,
+            },
+        );
+
+        tarpit.engage("attacker1", "suspicious").await;
+        tarpit.engage("attacker1", "suspicious").await;
+        let escalated = tarpit.get_delay("attacker1").unwrap();
+        assert!(escalated > 1000);
+
+        // Back-date the entry past the decay window instead of sleeping
+        {
+            let mut tarpits = tarpit.tarpits.write();
+            let entry = tarpits.get_mut("attacker1").unwrap();
+            entry.last_request_at = Utc::now() - chrono::Duration::milliseconds(2000);
+        }
+
+        tarpit.decay_idle_entries();
+        let decayed = tarpit.get_delay("attacker1").unwrap();
+        assert!(decayed < escalated);
+        assert!(decayed >= 1000);
+
+        // Keep decaying until it fully recovers and gets evicted
+        for _ in 0..10 {
+            if tarpit.get_delay("attacker1").is_none() {
+                break;
+            }
+            let mut tarpits = tarpit.tarpits.write();
+            if let Some(entry) = tarpits.get_mut("attacker1") {
+                entry.last_request_at = Utc::now() - chrono::Duration::milliseconds(2000);
+            }
+            drop(tarpits);
+            tarpit.decay_idle_entries();
+        }
+        assert!(tarpit.get_delay("attacker1").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tarpit_jitter_stays_within_bound() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let tarpit = TarpitControllerDaemon::with_config(
+            tx,
+            TarpitConfig {
+                base_delay_ms: 1000,
+                max_delay_ms: 60000,
+                escalation_factor: 1.5,
+                decay_window_ms: 60000,
+                jitter_fraction: 0.2,
+            },
+        );
+
+        for _ in 0..20 {
+            let returned = tarpit.engage("attacker2", "suspicious").await.unwrap();
+            let stored = tarpit.get_delay("attacker2").unwrap();
+            let lower = (stored as f64 * 0.8) as u64;
+            let upper = (stored as f64 * 1.2) as u64;
+            assert!(returned >= lower && returned <= upper);
+        }
+    }
+
+    #[test]
+    fn test_gc_recovered_buckets_drops_full_entries() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let enforcer = RateLimitEnforcerDaemon::new(tx);
+
+        // A zero-cost request still creates the buckets but never depletes
+        // them, so they read as fully recovered immediately
+        enforcer.check_rate_limit("entityA", "sessionA", "providerA", "tokenA", 0).unwrap();
+        enforcer.check_rate_limit("entityB", "sessionB", "providerB", "tokenB", 0).unwrap();
+        assert_eq!(enforcer.bucket_count(5), 2);
+
+        enforcer.gc_recovered_buckets();
+        assert_eq!(enforcer.bucket_count(5), 0);
+        assert_eq!(enforcer.bucket_count(2), 0);
+        assert_eq!(enforcer.bucket_count(3), 0);
+        assert_eq!(enforcer.bucket_count(4), 0);
+    }
+
     #[test]
-    fn test_response_mutation() {
+    fn test_gc_recovered_buckets_keeps_depleted_entries() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let enforcer = RateLimitEnforcerDaemon::new(tx);
+
+        // Entity bucket capacity is 20; drain it most of the way down
+        for _ in 0..15 {
+            let _ = enforcer.check_rate_limit("heavy", "session", "provider", "token", 1);
+        }
+        assert_eq!(enforcer.bucket_count(5), 1);
+
+        enforcer.gc_recovered_buckets();
+        assert_eq!(enforcer.bucket_count(5), 1);
+    }
+
+    #[test]
+    fn test_try_consume_never_leaves_negative_tokens() {
+        let mut bucket = TokenBucket::new(5, 1);
+        assert!(bucket.try_consume(5));
+        assert!(bucket.tokens >= 0.0);
+        assert!(!bucket.try_consume(1));
+        assert!(bucket.tokens >= 0.0);
+    }
+
+    #[test]
+    fn test_check_rate_limit_reports_rejecting_layer() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut config = RateLimitConfig::default();
+        config.set(RateLimitLayer::Entity, 1, 1);
+        let enforcer = RateLimitEnforcerDaemon::with_config(tx, config);
+
+        enforcer.check_rate_limit("e", "s", "p", "t", 1).unwrap();
+        let err = enforcer.check_rate_limit("e", "s", "p", "t", 1).unwrap_err();
+        assert_eq!(err.layer, RateLimitLayer::Entity.as_u8());
+        assert_eq!(err.limit, "entity:e");
+    }
+
+    #[test]
+    fn test_set_level_config_applies_to_new_buckets() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let enforcer = RateLimitEnforcerDaemon::new(tx);
+        enforcer.set_level_config(RateLimitLayer::Session, 1, 1);
+
+        // First request for a never-seen session creates its bucket from
+        // the now-reduced capacity, so a second request of the same cost
+        // should immediately be rejected.
+        enforcer.check_rate_limit("e", "new_session", "p", "t", 1).unwrap();
+        let err = enforcer.check_rate_limit("e", "new_session", "p", "t", 1).unwrap_err();
+        assert_eq!(err.layer, RateLimitLayer::Session.as_u8());
+    }
+
+    #[tokio::test]
+    async fn test_response_mutation() {
         let (tx, _rx) = mpsc::unbounded_channel();
         let mutator = ResponseMutatorDaemon::new(tx);
 
-        mutator.add_to_mutate_list("suspicious_entity");
+        mutator.add_to_mutate_list("suspicious_entity").await;
 
         let response = "Here is the API key: sk-ant-abc123";
-        let (mutated, applied) = mutator.mutate_response("suspicious_entity", response);
+        let (mutated, applied) = mutator.mutate_response("suspicious_entity", response).await.unwrap();
 
         assert!(mutated.contains("[REDACTED]"));
         assert!(!applied.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_tarpit_engage_records_delay_histogram_and_reason_counter() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let metrics = DefenseMetrics::new();
+        let tarpit = TarpitControllerDaemon::with_metrics(tx, metrics.clone());
+
+        tarpit.engage("attacker1", "suspicious").await;
+        tarpit.engage("attacker1", "suspicious").await;
+
+        let text = metrics.gather();
+        assert!(text.contains("gently_security_defense_tarpit_delay_ms_count 2"));
+        assert!(text.contains("gently_security_defense_tarpit_engagements_total{reason=\"suspicious\"} 2"));
+    }
+
+    #[test]
+    fn test_check_rate_limit_records_rejection_by_layer() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let metrics = DefenseMetrics::new();
+        let mut config = RateLimitConfig::default();
+        config.set(RateLimitLayer::Entity, 1, 1);
+        let enforcer = RateLimitEnforcerDaemon::with_config_and_metrics(tx, config, metrics.clone());
+
+        enforcer.check_rate_limit("e", "s", "p", "t", 1).unwrap();
+        let _ = enforcer.check_rate_limit("e", "s", "p", "t", 1);
+
+        let text = metrics.gather();
+        assert!(text.contains("gently_security_defense_rate_limit_rejections_total{layer=\"entity\"} 1"));
+    }
+
+    #[test]
+    fn test_gather_renders_mutate_list_gauge() {
+        let metrics = DefenseMetrics::new();
+        metrics.set_mutate_list_size(3);
+
+        let text = metrics.gather();
+        assert!(text.contains("gently_security_defense_mutate_list_size 3"));
+    }
+
+    #[test]
+    fn test_session_isolator_refreshes_isolation_gauges() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let isolator = SessionIsolatorDaemon::new(tx);
+
+        isolator.request_isolation(IsolationRequest {
+            session_id: "s1".to_string(),
+            reason: "test".to_string(),
+            severity: 7,
+            duration: None,
+        });
+        let session = isolator.process_isolation(IsolationRequest {
+            session_id: "s1".to_string(),
+            reason: "test".to_string(),
+            severity: 7,
+            duration: None,
+        });
+        isolator.isolated.write().insert(session.session_id.clone(), session);
+        isolator.refresh_isolation_metrics();
+
+        let text = isolator.metrics.gather();
+        assert!(text.contains("gently_security_defense_isolated_sessions{severity=\"7\"} 1"));
+        assert!(text.contains("gently_security_defense_isolated_sessions_by_restriction{restriction=\"no_external_providers\"} 1"));
+    }
+
+    #[tokio::test]
+    async fn test_tarpit_shutdown_waits_for_in_flight_engage() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let tarpit = Arc::new(TarpitControllerDaemon::new(tx));
+
+        let engaging = tarpit.clone();
+        let handle = tokio::spawn(async move {
+            engaging.engage("attacker3", "suspicious").await;
+        });
+
+        let report = tarpit.shutdown(Duration::from_secs(1)).await.unwrap();
+        handle.await.unwrap();
+
+        assert_eq!(report.force_dropped, 0);
+        assert!(!tarpit.status().draining);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_times_out_with_stuck_engage() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let tarpit = TarpitControllerDaemon::new(tx);
+        let _stuck_guard = tarpit.shutdown.enter().unwrap();
+
+        let err = tarpit.shutdown(Duration::from_millis(50)).await.unwrap_err();
+        assert_eq!(err.force_dropped, 1);
+    }
+
+    #[tokio::test]
+    async fn test_engage_refuses_new_work_once_shutdown_requested() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let tarpit = TarpitControllerDaemon::new(tx);
+
+        tarpit.shutdown.signal();
+
+        assert!(tarpit.engage("attacker1", "suspicious").await.is_none());
+    }
 }
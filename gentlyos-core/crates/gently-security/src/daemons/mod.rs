@@ -12,14 +12,18 @@ pub mod traffic;
 pub mod detection;
 pub mod defense;
 pub mod intel;
+pub mod state_store;
+pub mod resync_queue;
 
 pub use foundation::*;
 pub use traffic::*;
 pub use detection::*;
 pub use defense::*;
 pub use intel::*;
+pub use state_store::{StateStore, StateSnapshot, InMemoryStore, FileBackedStore};
+pub use resync_queue::{EventResyncQueue, ResyncQueueConfig};
 
-use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+use std::sync::{Arc, atomic::{AtomicBool, AtomicU64, Ordering}};
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
@@ -51,28 +55,173 @@ pub trait SecurityDaemon: Send + Sync {
 #[derive(Debug, Clone)]
 pub struct DaemonStatus {
     pub running: bool,
+    /// Set between a `shutdown()` call being accepted and its drain
+    /// finishing (or timing out); distinct from `running` so a caller
+    /// polling `status()` can tell "still doing in-flight work" apart from
+    /// "stopped and idle".
+    pub draining: bool,
     pub started_at: Option<Instant>,
     pub cycles: u64,
     pub last_cycle: Option<Instant>,
     pub errors: u32,
     pub events_emitted: u64,
+    /// Backlog depth of `EventResyncQueue`'s durable retry queue. Zero for
+    /// every other daemon; only meaningful on the status reported under the
+    /// `"event_resync_queue"` key.
+    pub queue_depth: u64,
+    /// When `EventResyncQueue` last attempted a replay batch, whether or not
+    /// it had anything to deliver. `None` for every other daemon.
+    pub last_drain: Option<Instant>,
 }
 
 impl Default for DaemonStatus {
     fn default() -> Self {
         Self {
             running: false,
+            draining: false,
             started_at: None,
             cycles: 0,
             last_cycle: None,
             errors: 0,
             events_emitted: 0,
+            queue_depth: 0,
+            last_drain: None,
         }
     }
 }
 
+/// Coordinates a daemon's shutdown: signals its `run` loop to stop, tracks
+/// calls still in flight via `enter()`'s RAII guard, and lets a caller
+/// `shutdown()`-and-wait for them to finish instead of flipping `stop()`'s
+/// raw flag and walking away mid-request (e.g. mid-tarpit-delay or
+/// mid-response-mutation).
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    stop_flag: Arc<AtomicBool>,
+    in_flight: Arc<AtomicU64>,
+    drained: Arc<tokio::sync::Notify>,
+}
+
+impl Default for ShutdownHandle {
+    fn default() -> Self {
+        Self {
+            stop_flag: Arc::new(AtomicBool::new(false)),
+            in_flight: Arc::new(AtomicU64::new(0)),
+            drained: Arc::new(tokio::sync::Notify::new()),
+        }
+    }
+}
+
+impl ShutdownHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checked by `run` loops each cycle in place of the old raw `stop_flag`.
+    pub fn is_stopping(&self) -> bool {
+        self.stop_flag.load(Ordering::SeqCst)
+    }
+
+    /// Flip the stop flag without waiting for in-flight work to drain. Used
+    /// by `SecurityDaemon::stop`'s synchronous signature; prefer `shutdown`
+    /// when the caller can await the drain instead.
+    pub fn signal(&self) {
+        self.stop_flag.store(true, Ordering::SeqCst);
+    }
+
+    /// Mark the start of one in-flight call; the count is decremented
+    /// automatically when the returned guard drops. Returns `None` once
+    /// `shutdown`/`signal` has been called, so new work can't start after a
+    /// shutdown has been requested - callers must treat `None` as "refused,
+    /// don't proceed" rather than always going ahead.
+    ///
+    /// Increments `in_flight` *before* checking `is_stopping()`, backing the
+    /// increment out if shutdown turns out to already be in progress. A
+    /// check-then-increment would leave a window where `shutdown()` reads
+    /// `in_flight_count() == 0` and reports a clean drain, while an `enter()`
+    /// that read `is_stopping() == false` a moment earlier is still about to
+    /// increment and return `Some` - starting new work after shutdown already
+    /// reported success. Incrementing first closes that window: either this
+    /// call's increment lands before `signal()`'s store (so `shutdown()` sees
+    /// it and waits for it to drain), or it lands after (so this call
+    /// observes `is_stopping() == true` and backs out).
+    pub fn enter(&self) -> Option<InFlightGuard> {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        if self.is_stopping() {
+            if self.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+                self.drained.notify_waiters();
+            }
+            return None;
+        }
+        Some(InFlightGuard { in_flight: self.in_flight.clone(), drained: self.drained.clone() })
+    }
+
+    fn in_flight_count(&self) -> u64 {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Signal shutdown and wait up to `timeout` for every `enter()`ed call to
+    /// finish. `Ok` reports how many were in flight and drained on their own;
+    /// `Err` reports how many were still running (and thus force-abandoned)
+    /// when the grace period expired.
+    pub async fn shutdown(&self, timeout: Duration) -> Result<DrainReport, DrainTimeout> {
+        self.signal();
+        let started_in_flight = self.in_flight_count();
+
+        let wait_drained = async {
+            loop {
+                if self.in_flight_count() == 0 {
+                    break;
+                }
+                let notified = self.drained.notified();
+                if self.in_flight_count() == 0 {
+                    break;
+                }
+                notified.await;
+            }
+        };
+
+        match tokio::time::timeout(timeout, wait_drained).await {
+            Ok(()) => Ok(DrainReport { drained: started_in_flight, force_dropped: 0 }),
+            Err(_) => Err(DrainTimeout { force_dropped: self.in_flight_count() }),
+        }
+    }
+}
+
+/// RAII guard returned by `ShutdownHandle::enter`; dropping it decrements the
+/// in-flight count and wakes any `shutdown()` call waiting on a drain.
+pub struct InFlightGuard {
+    in_flight: Arc<AtomicU64>,
+    drained: Arc<tokio::sync::Notify>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.drained.notify_waiters();
+        }
+    }
+}
+
+/// Result of a `ShutdownHandle::shutdown` that finished within its timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrainReport {
+    pub drained: u64,
+    pub force_dropped: u64,
+}
+
+/// Returned when `ShutdownHandle::shutdown`'s grace period expired with
+/// in-flight calls still running; `force_dropped` is how many.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrainTimeout {
+    pub force_dropped: u64,
+}
+
 /// Security event for daemon communication
-#[derive(Debug, Clone)]
+///
+/// `Serialize`/`Deserialize` let `EventResyncQueue` persist undelivered
+/// events to disk across a restart.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum SecurityDaemonEvent {
     // Foundation layer
     ChainValidated { entries: usize, valid: bool, errors: Vec<String> },
@@ -103,7 +252,7 @@ pub enum SecurityDaemonEvent {
 }
 
 /// Forensic log levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum ForensicLevel {
     Trace,
     Debug,
@@ -114,7 +263,7 @@ pub enum ForensicLevel {
 }
 
 /// Session actions
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum SessionAction {
     Isolated { reason: String },
     Terminated { reason: String },
@@ -123,7 +272,7 @@ pub enum SessionAction {
 }
 
 /// Defense modes (matches controller)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum DefenseMode {
     Normal,
     Elevated,
@@ -151,3 +300,54 @@ impl Default for DaemonConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_enter_refuses_once_stopping_and_leaves_in_flight_at_zero() {
+        let handle = ShutdownHandle::new();
+
+        let guard = handle.enter().expect("not stopping yet");
+        handle.signal();
+        assert!(handle.enter().is_none());
+
+        drop(guard);
+        assert_eq!(handle.in_flight_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_never_reports_a_clean_drain_while_enter_is_racing_in() {
+        // Regression test: enter() used to check is_stopping() before
+        // incrementing in_flight, leaving a window where shutdown() could
+        // observe in_flight_count() == 0 and report success while a racing
+        // enter() was still about to land and return Some. Incrementing
+        // first closes that window - this drives many concurrent enter()s
+        // against a concurrent shutdown() and asserts the drain finishes
+        // without timing out, leaves in_flight at zero, and reports no more
+        // drained calls than enter()s attempted.
+        let handle = ShutdownHandle::new();
+        let mut guards = Vec::new();
+        let mut tasks = Vec::new();
+
+        for _ in 0..64 {
+            let handle = handle.clone();
+            tasks.push(tokio::spawn(async move { handle.enter() }));
+        }
+
+        let shutdown_handle = handle.clone();
+        let shutdown_task = tokio::spawn(async move { shutdown_handle.shutdown(Duration::from_secs(5)).await });
+
+        for task in tasks {
+            if let Some(guard) = task.await.unwrap() {
+                guards.push(guard);
+            }
+        }
+        drop(guards);
+
+        let report = shutdown_task.await.unwrap().expect("drain should not time out");
+        assert_eq!(handle.in_flight_count(), 0);
+        assert!(report.drained <= 64);
+    }
+}
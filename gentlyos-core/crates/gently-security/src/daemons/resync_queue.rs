@@ -0,0 +1,375 @@
+//! Durable retry queue sitting in front of the shared security-event sender.
+//!
+//! Daemons still emit through a plain `mpsc::UnboundedSender<SecurityDaemonEvent>`,
+//! same as before; what changed is where that sender feeds. `EventResyncQueue`
+//! consumes it, forwards each event into a bounded downstream channel when
+//! there's room, and falls back to a disk-backed FIFO backlog when the
+//! downstream consumer is slow or absent. A background replay pass drains
+//! that backlog at a throttle governed by `tranquility`, so flushing never
+//! starves the daemons' request-handling hot paths upstream of it.
+
+use super::{DaemonStatus, SecurityDaemon, SecurityDaemonEvent, ShutdownHandle};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Governs the replay task's sleep between backlog-draining batches:
+/// `sleep = base_interval * tranquility / (1 + depth)`, floored at
+/// `min_interval`. Higher `tranquility` makes a shallow backlog sleep longer
+/// (lazier at rest); a growing `depth` always shrinks the
+/// interval back down, so backlog pressure wins regardless of `tranquility`.
+#[derive(Debug, Clone, Copy)]
+pub struct ResyncQueueConfig {
+    /// Max events the durable backlog holds before it starts dropping the
+    /// oldest pending event to make room for the newest.
+    pub capacity: usize,
+    /// Events forwarded per replay batch.
+    pub batch_size: usize,
+    /// Sleep at backlog depth 0 before `tranquility` scaling; the actual
+    /// depth-0 sleep is `base_interval * tranquility`.
+    pub base_interval: Duration,
+    /// Floor on the sleep between batches, however deep the backlog gets.
+    pub min_interval: Duration,
+    /// Non-negative factor controlling how lazily the replay task drains a
+    /// shallow backlog; see the type-level docs for the curve it drives.
+    pub tranquility: f64,
+}
+
+impl Default for ResyncQueueConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            batch_size: 50,
+            base_interval: Duration::from_secs(5),
+            min_interval: Duration::from_millis(50),
+            tranquility: 1.0,
+        }
+    }
+}
+
+impl ResyncQueueConfig {
+    fn next_interval(&self, depth: usize) -> Duration {
+        if self.tranquility <= 0.0 {
+            return self.min_interval;
+        }
+        let scaled = self.base_interval.mul_f64(self.tranquility) / (1 + depth as u32);
+        scaled.max(self.min_interval)
+    }
+}
+
+/// On-disk round-trip format for the backlog, mirroring `state_store`'s
+/// `FileBackedStore`: the whole backlog is re-serialized on each replay
+/// tick rather than append-only, which is fine at this queue's expected
+/// depth and keeps replay-after-restart a single read.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct BacklogSnapshot {
+    events: VecDeque<SecurityDaemonEvent>,
+}
+
+/// Consumes the daemons' shared inbound event channel and re-emits onto
+/// `downstream`, falling back to a persisted backlog (and replaying it on a
+/// `tranquility`-throttled timer) whenever `downstream` can't take an event
+/// immediately.
+pub struct EventResyncQueue {
+    shutdown: ShutdownHandle,
+    status: Arc<Mutex<DaemonStatus>>,
+    inbound: Arc<tokio::sync::Mutex<mpsc::UnboundedReceiver<SecurityDaemonEvent>>>,
+    downstream: mpsc::Sender<SecurityDaemonEvent>,
+    backlog: Arc<Mutex<VecDeque<SecurityDaemonEvent>>>,
+    throttle: ResyncQueueConfig,
+    persist_path: Option<PathBuf>,
+}
+
+impl EventResyncQueue {
+    /// In-memory only: the backlog is lost on restart.
+    pub fn new(
+        inbound: mpsc::UnboundedReceiver<SecurityDaemonEvent>,
+        downstream: mpsc::Sender<SecurityDaemonEvent>,
+        throttle: ResyncQueueConfig,
+    ) -> Self {
+        Self::with_path_inner(inbound, downstream, throttle, None)
+    }
+
+    /// Persist the backlog to `path` as JSON on each replay tick, reloading
+    /// whatever was pending there on construction so a restart replays
+    /// at-least-once instead of silently dropping it.
+    pub fn with_path(
+        inbound: mpsc::UnboundedReceiver<SecurityDaemonEvent>,
+        downstream: mpsc::Sender<SecurityDaemonEvent>,
+        throttle: ResyncQueueConfig,
+        path: impl Into<PathBuf>,
+    ) -> Self {
+        Self::with_path_inner(inbound, downstream, throttle, Some(path.into()))
+    }
+
+    fn with_path_inner(
+        inbound: mpsc::UnboundedReceiver<SecurityDaemonEvent>,
+        downstream: mpsc::Sender<SecurityDaemonEvent>,
+        throttle: ResyncQueueConfig,
+        persist_path: Option<PathBuf>,
+    ) -> Self {
+        let backlog = persist_path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<BacklogSnapshot>(&content).ok())
+            .map(|snapshot| snapshot.events)
+            .unwrap_or_default();
+
+        Self {
+            shutdown: ShutdownHandle::new(),
+            status: Arc::new(Mutex::new(DaemonStatus::default())),
+            inbound: Arc::new(tokio::sync::Mutex::new(inbound)),
+            downstream,
+            backlog: Arc::new(Mutex::new(backlog)),
+            throttle,
+            persist_path,
+        }
+    }
+
+    /// Write the current backlog to `persist_path` off the async runtime
+    /// (blocking disk I/O) and atomically, via a temp file + rename, so a
+    /// crash mid-write can't corrupt or lose the persisted backlog. Only
+    /// called from `replay_batch`'s `tranquility`-throttled timer tick, not
+    /// from the ingest path - see `push_backlog`.
+    async fn persist(&self) {
+        let Some(path) = self.persist_path.clone() else { return };
+        let snapshot = BacklogSnapshot { events: self.backlog.lock().clone() };
+        let _ = tokio::task::spawn_blocking(move || {
+            let content = match serde_json::to_string_pretty(&snapshot) {
+                Ok(content) => content,
+                Err(_) => return,
+            };
+            let tmp_path = path.with_extension("json.tmp");
+            if std::fs::write(&tmp_path, content).is_ok() {
+                let _ = std::fs::rename(&tmp_path, &path);
+            }
+        })
+        .await;
+    }
+
+    /// Append to the durable backlog, dropping the oldest pending event if
+    /// already at `capacity` so the newest activity is never the thing
+    /// silently discarded.
+    ///
+    /// Deliberately does *not* persist here: this runs on the synchronous
+    /// ingest path (`accept`, called for every inbound event), and a
+    /// blocking full-backlog write per overflow would defeat the whole
+    /// point of the `tranquility` throttle. The in-memory backlog is picked
+    /// up and flushed to disk by `replay_batch` on its throttled timer tick
+    /// instead, same as any backlog growth from a failed replay attempt.
+    fn push_backlog(&self, event: SecurityDaemonEvent) {
+        let mut backlog = self.backlog.lock();
+        if backlog.len() >= self.throttle.capacity {
+            backlog.pop_front();
+        }
+        backlog.push_back(event);
+    }
+
+    /// Forward straight to `downstream` if there's room, otherwise fall back
+    /// to the durable backlog for the replay task to retry later.
+    fn accept(&self, event: SecurityDaemonEvent) {
+        if let Err(err) = self.downstream.try_send(event) {
+            let event = match err {
+                mpsc::error::TrySendError::Full(event) => event,
+                mpsc::error::TrySendError::Closed(event) => event,
+            };
+            self.push_backlog(event);
+        }
+    }
+
+    /// Forward up to `batch_size` backlog events in FIFO order. The first
+    /// one that still can't be delivered, and everything behind it, goes
+    /// back onto the backlog rather than being dropped, so delivery stays
+    /// at-least-once. Returns the backlog depth after the attempt.
+    async fn replay_batch(&self) -> usize {
+        let batch: Vec<SecurityDaemonEvent> = {
+            let mut backlog = self.backlog.lock();
+            let n = self.throttle.batch_size.min(backlog.len());
+            backlog.drain(..n).collect()
+        };
+
+        let mut undelivered = Vec::new();
+        for event in batch {
+            if !undelivered.is_empty() {
+                undelivered.push(event);
+                continue;
+            }
+            if let Err(err) = self.downstream.try_send(event) {
+                let event = match err {
+                    mpsc::error::TrySendError::Full(event) => event,
+                    mpsc::error::TrySendError::Closed(event) => event,
+                };
+                undelivered.push(event);
+            }
+        }
+        // Back at the front, in their original order, ahead of anything
+        // that was behind them in the backlog.
+        {
+            let mut backlog = self.backlog.lock();
+            for event in undelivered.into_iter().rev() {
+                backlog.push_front(event);
+            }
+        }
+
+        self.persist().await;
+        self.backlog.lock().len()
+    }
+
+    /// Current backlog depth.
+    pub fn queue_depth(&self) -> usize {
+        self.backlog.lock().len()
+    }
+}
+
+#[async_trait::async_trait]
+impl SecurityDaemon for EventResyncQueue {
+    fn name(&self) -> &str {
+        "event_resync_queue"
+    }
+
+    fn layer(&self) -> u8 {
+        4
+    }
+
+    async fn run(&self) {
+        {
+            let mut status = self.status.lock();
+            status.running = true;
+            status.started_at = Some(Instant::now());
+        }
+
+        let mut inbound = self.inbound.lock().await;
+        let sleep = tokio::time::sleep(self.throttle.next_interval(self.queue_depth()));
+        tokio::pin!(sleep);
+
+        while !self.shutdown.is_stopping() {
+            tokio::select! {
+                maybe_event = inbound.recv() => {
+                    match maybe_event {
+                        Some(event) => self.accept(event),
+                        None => break,
+                    }
+                }
+                () = &mut sleep => {
+                    let depth = self.replay_batch().await;
+                    {
+                        let mut status = self.status.lock();
+                        status.cycles += 1;
+                        status.last_cycle = Some(Instant::now());
+                        status.last_drain = Some(Instant::now());
+                        status.queue_depth = depth as u64;
+                    }
+                    sleep.as_mut().reset(tokio::time::Instant::now() + self.throttle.next_interval(depth));
+                }
+            }
+        }
+
+        {
+            let mut status = self.status.lock();
+            status.running = false;
+        }
+    }
+
+    fn stop(&self) {
+        self.shutdown.signal();
+    }
+
+    fn status(&self) -> DaemonStatus {
+        let mut status = self.status.lock().clone();
+        status.queue_depth = self.queue_depth() as u64;
+        status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_backlog_used_when_downstream_full() {
+        let (inbound_tx, inbound_rx) = mpsc::unbounded_channel();
+        let (downstream_tx, mut downstream_rx) = mpsc::channel(1);
+        let throttle = ResyncQueueConfig {
+            base_interval: Duration::from_millis(20),
+            min_interval: Duration::from_millis(10),
+            ..Default::default()
+        };
+        let queue = EventResyncQueue::new(inbound_rx, downstream_tx, throttle);
+
+        // Fill the bounded downstream channel's one slot so the next send overflows.
+        inbound_tx.send(SecurityDaemonEvent::TarpitEngaged {
+            entity: "a".to_string(),
+            delay_ms: 1,
+            reason: "r".to_string(),
+        }).unwrap();
+        inbound_tx.send(SecurityDaemonEvent::TarpitEngaged {
+            entity: "b".to_string(),
+            delay_ms: 1,
+            reason: "r".to_string(),
+        }).unwrap();
+
+        let queue = Arc::new(queue);
+        let runner = queue.clone();
+        let handle = tokio::spawn(async move { runner.run().await });
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(queue.queue_depth(), 1);
+
+        // Draining the downstream channel frees room for the replay pass to deliver the backlog.
+        downstream_rx.recv().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(queue.queue_depth(), 0);
+
+        queue.stop();
+        handle.abort();
+    }
+
+    #[test]
+    fn test_tranquility_scales_sleep_and_floors_at_min() {
+        let config = ResyncQueueConfig {
+            base_interval: Duration::from_secs(10),
+            min_interval: Duration::from_millis(100),
+            tranquility: 2.0,
+            ..Default::default()
+        };
+
+        assert_eq!(config.next_interval(0), Duration::from_secs(20));
+        assert!(config.next_interval(100) < config.next_interval(0));
+        assert!(config.next_interval(100_000) >= config.min_interval);
+    }
+
+    #[test]
+    fn test_zero_tranquility_drains_immediately() {
+        let config = ResyncQueueConfig { tranquility: 0.0, ..Default::default() };
+        assert_eq!(config.next_interval(0), config.min_interval);
+        assert_eq!(config.next_interval(500), config.min_interval);
+    }
+
+    #[tokio::test]
+    async fn test_push_backlog_does_not_persist_until_replay_tick() {
+        let mut path = std::env::temp_dir();
+        path.push(format!("gently-security-resync-queue-test-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let (_inbound_tx, inbound_rx) = mpsc::unbounded_channel();
+        let (downstream_tx, _downstream_rx) = mpsc::channel(1);
+        let queue = EventResyncQueue::with_path(inbound_rx, downstream_tx, ResyncQueueConfig::default(), &path);
+
+        // Pushing to the backlog directly must not touch disk - only the
+        // throttled replay tick does.
+        queue.push_backlog(SecurityDaemonEvent::TarpitEngaged {
+            entity: "b".to_string(),
+            delay_ms: 1,
+            reason: "r".to_string(),
+        });
+        assert!(!path.exists());
+
+        queue.replay_batch().await;
+        assert!(path.exists());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
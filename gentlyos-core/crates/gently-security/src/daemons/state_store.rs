@@ -0,0 +1,258 @@
+//! Pluggable persistence for Layer-4 daemon state.
+//!
+//! `TarpitControllerDaemon`'s per-entity escalation level and
+//! `ResponseMutatorDaemon`'s mutate-list live behind an `Arc<dyn StateStore>`
+//! instead of an owned map, so an attacker who triggers a crash/restart
+//! doesn't get their escalation reset for free, and a durable backend can
+//! share that state across multiple cage instances.
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Point-in-time copy of everything a `StateStore` tracks, used for
+/// migrating between backends and for `FileBackedStore`'s on-disk format.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    pub escalations: HashMap<String, u32>,
+    pub mutate_targets: Vec<String>,
+}
+
+/// Backing store for daemon state that needs to survive a restart. Swapping
+/// `InMemoryStore` for a durable backend is a constructor-time config
+/// choice, not a code change in the daemons that use it.
+#[async_trait::async_trait]
+pub trait StateStore: Send + Sync {
+    /// Current escalation level tracked for `entity`, or 0 if none has ever
+    /// been recorded.
+    async fn load_escalation(&self, entity: &str) -> u32;
+
+    /// Increment and persist `entity`'s escalation level, returning the new value.
+    async fn bump_escalation(&self, entity: &str) -> u32;
+
+    /// Entities currently on the response-mutator's target list.
+    async fn list_mutate_targets(&self) -> Vec<String>;
+
+    /// Add `entity` to the response-mutator's target list, if not already present.
+    async fn add_mutate_target(&self, entity: &str);
+
+    /// Remove `entity` from the response-mutator's target list.
+    async fn remove_mutate_target(&self, entity: &str);
+
+    /// Full point-in-time copy of this store's contents.
+    async fn snapshot(&self) -> StateSnapshot;
+
+    /// Replace this store's contents with a previously captured snapshot,
+    /// e.g. on daemon startup after a restart.
+    async fn restore(&self, snapshot: StateSnapshot);
+}
+
+/// Current (default) behavior: everything lives in memory and is lost on restart.
+#[derive(Default)]
+pub struct InMemoryStore {
+    escalations: RwLock<HashMap<String, u32>>,
+    mutate_targets: RwLock<Vec<String>>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl StateStore for InMemoryStore {
+    async fn load_escalation(&self, entity: &str) -> u32 {
+        self.escalations.read().get(entity).copied().unwrap_or(0)
+    }
+
+    async fn bump_escalation(&self, entity: &str) -> u32 {
+        let mut escalations = self.escalations.write();
+        let level = escalations.entry(entity.to_string()).or_insert(0);
+        *level += 1;
+        *level
+    }
+
+    async fn list_mutate_targets(&self) -> Vec<String> {
+        self.mutate_targets.read().clone()
+    }
+
+    async fn add_mutate_target(&self, entity: &str) {
+        let mut targets = self.mutate_targets.write();
+        if !targets.iter().any(|t| t == entity) {
+            targets.push(entity.to_string());
+        }
+    }
+
+    async fn remove_mutate_target(&self, entity: &str) {
+        self.mutate_targets.write().retain(|t| t != entity);
+    }
+
+    async fn snapshot(&self) -> StateSnapshot {
+        StateSnapshot {
+            escalations: self.escalations.read().clone(),
+            mutate_targets: self.mutate_targets.read().clone(),
+        }
+    }
+
+    async fn restore(&self, snapshot: StateSnapshot) {
+        *self.escalations.write() = snapshot.escalations;
+        *self.mutate_targets.write() = snapshot.mutate_targets;
+    }
+}
+
+/// Durable backend: an `InMemoryStore` mirrored to a JSON file on disk after
+/// every mutation, so state survives a process restart and (if `path` lives
+/// on shared/network storage) can be picked up by another cage instance. A
+/// heavier KV/object-store-backed implementation can slot in later behind
+/// the same trait without touching any daemon code.
+pub struct FileBackedStore {
+    inner: InMemoryStore,
+    path: PathBuf,
+}
+
+impl FileBackedStore {
+    /// Load existing state from `path` if present, otherwise start empty.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let inner = InMemoryStore::new();
+
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            if let Ok(snapshot) = serde_json::from_str::<StateSnapshot>(&content) {
+                *inner.escalations.write() = snapshot.escalations;
+                *inner.mutate_targets.write() = snapshot.mutate_targets;
+            }
+        }
+
+        Self { inner, path }
+    }
+
+    /// Write `snapshot` to `self.path` off the async runtime (this is
+    /// blocking disk I/O, and `bump_escalation` runs on
+    /// `TarpitControllerDaemon::engage`'s hot path) and atomically, via a
+    /// temp file + rename, so a crash mid-write can't corrupt or lose the
+    /// previously-persisted state.
+    async fn persist(&self, snapshot: StateSnapshot) {
+        let path = self.path.clone();
+        let _ = tokio::task::spawn_blocking(move || {
+            let content = match serde_json::to_string_pretty(&snapshot) {
+                Ok(content) => content,
+                Err(_) => return,
+            };
+            let tmp_path = path.with_extension("json.tmp");
+            if std::fs::write(&tmp_path, content).is_ok() {
+                let _ = std::fs::rename(&tmp_path, &path);
+            }
+        })
+        .await;
+    }
+}
+
+#[async_trait::async_trait]
+impl StateStore for FileBackedStore {
+    async fn load_escalation(&self, entity: &str) -> u32 {
+        self.inner.load_escalation(entity).await
+    }
+
+    async fn bump_escalation(&self, entity: &str) -> u32 {
+        let level = self.inner.bump_escalation(entity).await;
+        self.persist(self.inner.snapshot().await).await;
+        level
+    }
+
+    async fn list_mutate_targets(&self) -> Vec<String> {
+        self.inner.list_mutate_targets().await
+    }
+
+    async fn add_mutate_target(&self, entity: &str) {
+        self.inner.add_mutate_target(entity).await;
+        self.persist(self.inner.snapshot().await).await;
+    }
+
+    async fn remove_mutate_target(&self, entity: &str) {
+        self.inner.remove_mutate_target(entity).await;
+        self.persist(self.inner.snapshot().await).await;
+    }
+
+    async fn snapshot(&self) -> StateSnapshot {
+        self.inner.snapshot().await
+    }
+
+    async fn restore(&self, snapshot: StateSnapshot) {
+        self.inner.restore(snapshot.clone()).await;
+        self.persist(snapshot).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_store_bumps_and_loads_escalation() {
+        let store = InMemoryStore::new();
+
+        assert_eq!(store.load_escalation("attacker1").await, 0);
+        assert_eq!(store.bump_escalation("attacker1").await, 1);
+        assert_eq!(store.bump_escalation("attacker1").await, 2);
+        assert_eq!(store.load_escalation("attacker1").await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_dedupes_mutate_targets() {
+        let store = InMemoryStore::new();
+
+        store.add_mutate_target("attacker1").await;
+        store.add_mutate_target("attacker1").await;
+        store.add_mutate_target("attacker2").await;
+
+        let mut targets = store.list_mutate_targets().await;
+        targets.sort();
+        assert_eq!(targets, vec!["attacker1".to_string(), "attacker2".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_removes_mutate_target() {
+        let store = InMemoryStore::new();
+        store.add_mutate_target("attacker1").await;
+        store.add_mutate_target("attacker2").await;
+
+        store.remove_mutate_target("attacker1").await;
+
+        assert_eq!(store.list_mutate_targets().await, vec!["attacker2".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_snapshot_restore_round_trips() {
+        let store = InMemoryStore::new();
+        store.bump_escalation("attacker1").await;
+        store.add_mutate_target("attacker1").await;
+
+        let snapshot = store.snapshot().await;
+
+        let restored = InMemoryStore::new();
+        restored.restore(snapshot).await;
+
+        assert_eq!(restored.load_escalation("attacker1").await, 1);
+        assert_eq!(restored.list_mutate_targets().await, vec!["attacker1".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_file_backed_store_persists_across_reload() {
+        let mut path = std::env::temp_dir();
+        path.push(format!("gently-security-state-store-test-{}.json", std::process::id()));
+
+        {
+            let store = FileBackedStore::new(&path);
+            store.bump_escalation("attacker1").await;
+            store.add_mutate_target("attacker1").await;
+        }
+
+        let reloaded = FileBackedStore::new(&path);
+        assert_eq!(reloaded.load_escalation("attacker1").await, 1);
+        assert_eq!(reloaded.list_mutate_targets().await, vec!["attacker1".to_string()]);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -747,6 +747,11 @@ enum BrainCommands {
         /// Show daemon events
         #[arg(long, default_value = "false")]
         verbose: bool,
+
+        /// Persist the Alexandria graph and Tesseract positions to a sled
+        /// database at this path, hydrating from it on startup
+        #[arg(long)]
+        db_path: Option<String>,
     },
 
     /// List available skills
@@ -795,6 +800,24 @@ enum BrainCommands {
 
     /// Get current awareness state
     Awareness,
+
+    /// Replay a workload file against a fresh orchestrator and report latency
+    Bench {
+        /// Path to a workload JSON file
+        workload: String,
+
+        /// Synthetic concepts to seed before timing starts
+        #[arg(short, long, default_value = "0")]
+        seed_concepts: usize,
+
+        /// Run with background daemons enabled
+        #[arg(long)]
+        daemons: bool,
+
+        /// Write the full JSON report to this file (summary always prints)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -2826,7 +2849,7 @@ fn cmd_brain(command: BrainCommands) -> Result<()> {
             Ok(())
         }
 
-        BrainCommands::Orchestrate { ipfs, verbose } => {
+        BrainCommands::Orchestrate { ipfs, verbose, db_path } => {
             use gently_brain::{BrainOrchestrator, BrainConfig};
 
             println!("\n  BRAIN ORCHESTRATOR");
@@ -2834,6 +2857,7 @@ fn cmd_brain(command: BrainCommands) -> Result<()> {
 
             let config = BrainConfig {
                 enable_ipfs: ipfs,
+                alexandria_db_path: db_path.map(std::path::PathBuf::from),
                 ..Default::default()
             };
 
@@ -2968,9 +2992,9 @@ fn cmd_brain(command: BrainCommands) -> Result<()> {
                         println!("  No daemons running.");
                         println!("  Use: gently brain daemon spawn <type>");
                     } else {
-                        for (name, dtype, running) in daemons {
+                        for (name, dtype, running, state) in daemons {
                             let status = if running { "running" } else { "stopped" };
-                            println!("  {:30} [{:?}] {}", name, dtype, status);
+                            println!("  {:30} [{:?}] {} ({:?})", name, dtype, status, state);
                         }
                     }
                 }
@@ -3018,6 +3042,7 @@ fn cmd_brain(command: BrainCommands) -> Result<()> {
                         Some(status) => {
                             println!("  Daemon: {}", name);
                             println!("  Running: {}", status.running);
+                            println!("  State: {:?}", status.state);
                             println!("  Cycles: {}", status.cycles);
                             println!("  Errors: {}", status.errors);
                             println!();
@@ -3209,6 +3234,27 @@ fn cmd_brain(command: BrainCommands) -> Result<()> {
             }
             Ok(())
         }
+
+        BrainCommands::Bench { workload, seed_concepts, daemons, output } => {
+            use gently_brain::{bench, BenchConfig};
+
+            println!("\n  AWARENESS PIPELINE BENCHMARK");
+            println!("  ============================\n");
+
+            let workload = bench::Workload::load(&workload)?;
+            let config = BenchConfig { seed_concepts, enable_daemons: daemons };
+
+            let rt = tokio::runtime::Runtime::new()?;
+            let report = rt.block_on(bench::run(&workload, &config));
+
+            println!("{}", report.summary_text());
+
+            if let Some(path) = output {
+                std::fs::write(&path, report.to_json()?)?;
+                println!("\n  Full report written to {}", path);
+            }
+            Ok(())
+        }
     }
 }
 
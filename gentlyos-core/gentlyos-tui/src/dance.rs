@@ -0,0 +1,239 @@
+//! Dance Protocol - Visual-audio authentication using XOR key splits
+//!
+//! A freshly generated secp256k1 keypair's secret is split into N shares
+//! via XOR masking: all N shares XOR back to the secret, but any N-1 of
+//! them reveal nothing about it (each share alone, and any strict subset,
+//! is indistinguishable from random noise). The shares are held apart and
+//! must be reassembled every time a signature is produced - the signing
+//! key itself is never persisted, only its shares.
+
+use k256::ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier};
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+const SHARE_LEN: usize = 32;
+/// Default number of XOR shares a fresh `/dance gen` splits the secret into.
+pub const DEFAULT_SHARES: usize = 3;
+
+/// The XOR key-split shares for a single generated secret.
+#[derive(Clone)]
+pub struct KeySplit {
+    shares: Vec<[u8; SHARE_LEN]>,
+}
+
+impl KeySplit {
+    /// Generate a random secret and split it into `n` XOR shares.
+    pub fn generate(n: usize) -> Self {
+        assert!(n >= 2, "a key split requires at least 2 shares");
+        let mut rng = rand::thread_rng();
+
+        let mut secret = [0u8; SHARE_LEN];
+        rng.fill_bytes(&mut secret);
+
+        let mut shares = Vec::with_capacity(n);
+        let mut accumulator = [0u8; SHARE_LEN];
+        for _ in 0..n - 1 {
+            let mut share = [0u8; SHARE_LEN];
+            rng.fill_bytes(&mut share);
+            for i in 0..SHARE_LEN {
+                accumulator[i] ^= share[i];
+            }
+            shares.push(share);
+        }
+        // Final share makes the whole set XOR back to `secret`.
+        let mut last = [0u8; SHARE_LEN];
+        for i in 0..SHARE_LEN {
+            last[i] = secret[i] ^ accumulator[i];
+        }
+        shares.push(last);
+
+        secret.zeroize();
+        Self { shares }
+    }
+
+    pub fn share_count(&self) -> usize {
+        self.shares.len()
+    }
+
+    /// A single share as hex, by index. Callers must surface shares one at
+    /// a time (e.g. via separate `/dance share <i>` retrievals) - never
+    /// concatenate more than one into the same response, or an observer of
+    /// that single message gets enough shares to reassemble the secret.
+    pub fn share_hex(&self, index: usize) -> Option<String> {
+        self.shares.get(index).map(hex::encode)
+    }
+
+    /// Reassemble the shares into the raw secret. Callers must zeroize the result.
+    fn reassemble(&self) -> [u8; SHARE_LEN] {
+        let mut secret = [0u8; SHARE_LEN];
+        for share in &self.shares {
+            for i in 0..SHARE_LEN {
+                secret[i] ^= share[i];
+            }
+        }
+        secret
+    }
+
+    /// Reassemble the shares and derive the signing key. The reassembled
+    /// secret is zeroized as soon as the key is constructed from it.
+    fn signing_key(&self) -> Result<SigningKey, String> {
+        let mut secret = self.reassemble();
+        let key = SigningKey::from_bytes((&secret).into())
+            .map_err(|e| format!("reassembled secret is not a valid key: {}", e));
+        secret.zeroize();
+        key
+    }
+
+    pub fn public_key_hex(&self) -> Result<String, String> {
+        let key = self.signing_key()?;
+        Ok(hex::encode(key.verifying_key().to_sec1_bytes()))
+    }
+
+    pub fn address(&self) -> Result<String, String> {
+        let key = self.signing_key()?;
+        Ok(address_from_public_key(key.verifying_key()))
+    }
+
+    /// Reassemble the shares and sign `message`, returning a hex-encoded
+    /// recoverable signature (64 bytes signature + 1 byte recovery id).
+    pub fn sign(&self, message: &[u8]) -> Result<String, String> {
+        let key = self.signing_key()?;
+        let digest = Sha256::digest(message);
+        let (signature, recovery_id): (Signature, RecoveryId) = key
+            .sign_prehash_recoverable(&digest)
+            .map_err(|e| format!("signing failed: {}", e))?;
+        Ok(encode_recoverable(&signature, recovery_id))
+    }
+}
+
+/// A public key's 20-byte address, the same convention used for wallet
+/// addresses elsewhere: `SHA-256(pubkey)` truncated to its last 20 bytes.
+pub fn address_from_public_key(key: &VerifyingKey) -> String {
+    let digest = Sha256::digest(key.to_sec1_bytes());
+    hex::encode(&digest[digest.len() - 20..])
+}
+
+fn encode_recoverable(signature: &Signature, recovery_id: RecoveryId) -> String {
+    let mut bytes = signature.to_bytes().to_vec();
+    bytes.push(recovery_id.to_byte());
+    hex::encode(bytes)
+}
+
+fn decode_recoverable(sig_hex: &str) -> Result<(Signature, RecoveryId), String> {
+    let bytes = hex::decode(sig_hex).map_err(|e| format!("invalid signature hex: {}", e))?;
+    if bytes.len() != 65 {
+        return Err(format!(
+            "expected a 65-byte recoverable signature, got {} bytes",
+            bytes.len()
+        ));
+    }
+    let signature = Signature::try_from(&bytes[..64])
+        .map_err(|e| format!("invalid signature bytes: {}", e))?;
+    let recovery_id = RecoveryId::from_byte(bytes[64])
+        .ok_or_else(|| "invalid recovery id byte".to_string())?;
+    Ok((signature, recovery_id))
+}
+
+/// Verify that `sig_hex` (a recoverable signature) over `message` was
+/// produced by the holder of `pubkey_hex`.
+pub fn verify(pubkey_hex: &str, sig_hex: &str, message: &[u8]) -> Result<bool, String> {
+    let pubkey_bytes = hex::decode(pubkey_hex).map_err(|e| format!("invalid public key hex: {}", e))?;
+    let verifying_key = VerifyingKey::from_sec1_bytes(&pubkey_bytes)
+        .map_err(|e| format!("invalid public key: {}", e))?;
+    let (signature, _) = decode_recoverable(sig_hex)?;
+    let digest = Sha256::digest(message);
+    Ok(verifying_key.verify_prehash(&digest, &signature).is_ok())
+}
+
+/// Recover the public key (and its address) that produced `sig_hex` over
+/// `message`, without needing the public key up front.
+pub fn recover(sig_hex: &str, message: &[u8]) -> Result<(String, String), String> {
+    let (signature, recovery_id) = decode_recoverable(sig_hex)?;
+    let digest = Sha256::digest(message);
+    let verifying_key = VerifyingKey::recover_from_prehash(&digest, &signature, recovery_id)
+        .map_err(|e| format!("recovery failed: {}", e))?;
+    let pubkey_hex = hex::encode(verifying_key.to_sec1_bytes());
+    let address = address_from_public_key(&verifying_key);
+    Ok((pubkey_hex, address))
+}
+
+/// Dance Protocol authentication state held by the TUI.
+#[derive(Default)]
+pub struct DanceState {
+    split: Option<KeySplit>,
+    authenticated: bool,
+}
+
+impl DanceState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_authenticated(&self) -> bool {
+        self.authenticated
+    }
+
+    /// Generate a new key split and mark the dance as authenticated: the
+    /// shares were just reassembled once to derive the public key/address,
+    /// proving they're consistent.
+    ///
+    /// Deliberately does not return the shares themselves: printing all of
+    /// them into one response would put the whole secret in a single
+    /// loggable message, defeating the "any N-1 reveal nothing" property.
+    /// Retrieve each share separately with `/dance share <i>`.
+    pub fn generate(&mut self) -> Result<String, String> {
+        let split = KeySplit::generate(DEFAULT_SHARES);
+        let address = split.address()?;
+        let pubkey = split.public_key_hex()?;
+        let share_count = split.share_count();
+        self.split = Some(split);
+        self.authenticated = true;
+
+        Ok(format!(
+            "Dance keypair generated ({share_count} XOR shares).\n\
+             Address: {address}\n\
+             Public key: {pubkey}\n\
+             Retrieve shares one at a time with `/dance share <i>` (i = 0..{share_count}) \
+             and store each separately - never together in one place."
+        ))
+    }
+
+    /// Retrieve a single share as hex, for out-of-band storage. Deliberately
+    /// one share per call - see `generate`.
+    pub fn share(&self, index: usize) -> Result<String, String> {
+        let split = self.split.as_ref().ok_or("No dance keypair yet. Run /dance gen first.")?;
+        split
+            .share_hex(index)
+            .ok_or_else(|| format!("share index {} out of range (0..{})", index, split.share_count()))
+    }
+
+    pub fn sign(&self, message: &str) -> Result<String, String> {
+        let split = self.split.as_ref().ok_or("No dance keypair yet. Run /dance gen first.")?;
+        split.sign(message.as_bytes())
+    }
+
+    /// Flip the authentication state (e.g. to simulate logging out of the
+    /// dance and back in) without discarding the generated key split, and
+    /// return the new state.
+    pub fn toggle(&mut self) -> bool {
+        self.authenticated = !self.authenticated;
+        self.authenticated
+    }
+
+    pub fn status(&self) -> String {
+        match &self.split {
+            Some(split) => format!(
+                "Dance: {} | {} shares held | address {}",
+                if self.authenticated { "AUTHENTICATED" } else { "locked" },
+                split.share_count(),
+                split.address().unwrap_or_else(|e| format!("<error: {}>", e)),
+            ),
+            None => format!(
+                "Dance: {} | no keypair yet - run /dance gen",
+                if self.authenticated { "AUTHENTICATED" } else { "locked" },
+            ),
+        }
+    }
+}
@@ -3,7 +3,9 @@
 //! Supports: Anthropic, OpenAI, DeepSeek, Grok, Ollama, LM Studio, HuggingFace
 
 use crate::boneblob::{BoneBlobPipeline, default_system_bones};
+use crate::dance::{self, DanceState};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
 use tokio::sync::mpsc;
 
@@ -16,7 +18,7 @@ pub enum LlmResponse {
 }
 
 /// Supported LLM providers
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Provider {
     #[default]
     Anthropic,
@@ -208,6 +210,53 @@ impl ModelConfig {
     }
 }
 
+/// Validate a user-supplied base URL the same way wallet server endpoints are
+/// validated: it must parse into `scheme://host[:port]`, the scheme must be
+/// `http` or `https`, the host must be non-empty, and if a port is present it
+/// must be a non-empty number. Returns the normalized (trimmed) URL on success.
+fn validate_base_url(url: &str) -> Result<String, String> {
+    let url = url.trim();
+    let (scheme, rest) = url.split_once("://").ok_or_else(|| {
+        format!("Invalid URL '{}': missing scheme (expected http:// or https://)", url)
+    })?;
+    if scheme != "http" && scheme != "https" {
+        return Err(format!("Invalid URL '{}': unsupported scheme '{}'", url, scheme));
+    }
+
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    if authority.is_empty() {
+        return Err(format!("Invalid URL '{}': missing host", url));
+    }
+
+    // Bracketed IPv6 host, e.g. [::1]:8080
+    let (host, port) = if let Some(stripped) = authority.strip_prefix('[') {
+        match stripped.split_once(']') {
+            Some((host, tail)) => {
+                let port = (!tail.is_empty()).then(|| tail.strip_prefix(':').unwrap_or(tail));
+                (host, port)
+            }
+            None => return Err(format!("Invalid URL '{}': unterminated IPv6 host", url)),
+        }
+    } else {
+        match authority.split_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+
+    if host.is_empty() {
+        return Err(format!("Invalid URL '{}': missing host", url));
+    }
+
+    if let Some(port) = port {
+        if port.is_empty() || !port.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("Invalid URL '{}': port '{}' is not a valid number", url, port));
+        }
+    }
+
+    Ok(url.to_string())
+}
+
 /// Provider-specific configuration
 #[derive(Debug, Clone)]
 pub struct ProviderConfig {
@@ -276,6 +325,10 @@ pub struct LlmClient {
     conversation: Vec<Message>,
     system_prompt: String,
     http_client: reqwest::Client,
+    /// Base URLs the user has explicitly set via `/url`, keyed by provider, so
+    /// switching providers with `/provider` restores each provider's own
+    /// endpoint instead of leaking the last-set URL onto the next provider.
+    custom_base_urls: HashMap<Provider, String>,
 }
 
 impl LlmClient {
@@ -285,6 +338,7 @@ impl LlmClient {
             conversation: Vec::new(),
             system_prompt: GENTLY_SYSTEM_PROMPT.to_string(),
             http_client: reqwest::Client::new(),
+            custom_base_urls: HashMap::new(),
         }
     }
 
@@ -298,6 +352,9 @@ impl LlmClient {
 
     pub fn set_provider(&mut self, provider: Provider) {
         self.config = ProviderConfig::new(provider);
+        if let Some(url) = self.custom_base_urls.get(&provider) {
+            self.config.base_url = url.clone();
+        }
         self.conversation.clear();
     }
 
@@ -313,8 +370,20 @@ impl LlmClient {
         self.conversation.clear();
     }
 
-    pub fn set_base_url(&mut self, url: &str) {
-        self.config.base_url = url.to_string();
+    /// Validate and set the base URL for the current provider, remembering it
+    /// so a later `/provider` switch back restores it instead of leaking it
+    /// onto other providers. Rejects malformed input before committing it.
+    pub fn set_base_url(&mut self, url: &str) -> Result<(), String> {
+        let url = validate_base_url(url)?;
+        self.custom_base_urls.insert(self.provider(), url.clone());
+        self.config.base_url = url;
+        Ok(())
+    }
+
+    /// The remembered custom base URL for `provider`, if one was set via
+    /// `/url` for it, regardless of which provider is currently active.
+    pub fn custom_base_url_for(&self, provider: Provider) -> Option<&str> {
+        self.custom_base_urls.get(&provider).map(|s| s.as_str())
     }
 
     /// Send message to the configured provider
@@ -625,12 +694,14 @@ impl LlmWorker {
                 boneblob.add_system_bone(&bone.constraint);
             }
 
+            let mut dance = DanceState::new();
+
             while let Some(msg) = request_rx.recv().await {
                 match msg {
                     WorkerMessage::Chat(message) => {
-                        // Handle slash commands locally
+                        // Handle slash commands locally, off the render thread
                         if message.starts_with('/') {
-                            let response = handle_command(&message, &mut client, &boneblob);
+                            let response = handle_command(&message, &mut client, &boneblob, &mut dance);
                             let _ = response_tx.send(response).await;
                             continue;
                         }
@@ -662,10 +733,11 @@ impl LlmWorker {
                         )).await;
                     }
                     WorkerMessage::SetBaseUrl(url) => {
-                        client.set_base_url(&url);
-                        let _ = response_tx.send(LlmResponse::Text(
-                            format!("Base URL set to: {}", url)
-                        )).await;
+                        let response = match client.set_base_url(&url) {
+                            Ok(()) => LlmResponse::Text(format!("Base URL set to: {}", url)),
+                            Err(e) => LlmResponse::Error(e),
+                        };
+                        let _ = response_tx.send(response).await;
                     }
                     WorkerMessage::ClearHistory => {
                         client.clear_history();
@@ -724,7 +796,12 @@ impl LlmWorker {
 }
 
 /// Handle slash commands
-fn handle_command(cmd: &str, client: &mut LlmClient, boneblob: &BoneBlobPipeline) -> LlmResponse {
+fn handle_command(
+    cmd: &str,
+    client: &mut LlmClient,
+    boneblob: &BoneBlobPipeline,
+    dance: &mut DanceState,
+) -> LlmResponse {
     let parts: Vec<&str> = cmd.trim().split_whitespace().collect();
     let command = parts.first().map(|s| s.to_lowercase()).unwrap_or_default();
 
@@ -766,11 +843,15 @@ fn handle_command(cmd: &str, client: &mut LlmClient, boneblob: &BoneBlobPipeline
                         "not set"
                     };
                     let marker = if p == current { ">" } else { " " };
-                    info.push_str(&format!("{} {} - {} ({})\n",
+                    let url_suffix = client.custom_base_url_for(p)
+                        .map(|url| format!(" [{}]", url))
+                        .unwrap_or_default();
+                    info.push_str(&format!("{} {} - {} ({}){}\n",
                         marker,
                         p.short_name(),
                         p.display_name(),
-                        key_status
+                        key_status,
+                        url_suffix
                     ));
                 }
                 LlmResponse::Text(info)
@@ -791,8 +872,10 @@ fn handle_command(cmd: &str, client: &mut LlmClient, boneblob: &BoneBlobPipeline
         }
         "/url" => {
             if let Some(url) = parts.get(1) {
-                client.set_base_url(url);
-                LlmResponse::Text(format!("Base URL set to: {}", url))
+                match client.set_base_url(url) {
+                    Ok(()) => LlmResponse::Text(format!("Base URL set to: {}", url)),
+                    Err(e) => LlmResponse::Error(e),
+                }
             } else {
                 LlmResponse::Text(format!(
                     "Current base URL: {}\nUsage: /url <url>",
@@ -809,7 +892,12 @@ fn handle_command(cmd: &str, client: &mut LlmClient, boneblob: &BoneBlobPipeline
                  /boneblob [on|off]- Toggle BONEBLOB constraint optimization\n\
                  /clear            - Clear chat history\n\
                  /status           - Show GentlyOS status\n\
-                 /dance            - Toggle dance state\n\
+                 /dance            - Toggle dance authentication state\n\
+                 /dance gen        - Generate a dance keypair (XOR key-split)\n\
+                 /dance share <i>  - Retrieve one XOR share (do this separately per share)\n\
+                 /dance sign <msg> - Sign a message with the dance keypair\n\
+                 /dance verify <pub> <sig> <msg> - Verify a signature\n\
+                 /dance recover <sig> <msg>      - Recover pubkey/address from a signature\n\
                  /help             - Show this help\n\n\
                  BONEBLOB: Constraint-based optimization pipeline\n\
                  - BONES: Preprompt constraints (immutable rules)\n\
@@ -845,15 +933,80 @@ fn handle_command(cmd: &str, client: &mut LlmClient, boneblob: &BoneBlobPipeline
                  Provider: {}\n\
                  Model: {}\n\
                  Credentials: {}\n\
+                 {}\n\
                  \n{}",
                 client.provider().display_name(),
                 client.model_name(),
                 if client.has_credentials() { "OK" } else { "Missing" },
+                dance.status(),
                 boneblob.status()
             ))
         }
         "/dance" => {
-            LlmResponse::Text("Dance state toggled (handled by UI)".to_string())
+            match parts.get(1).map(|s| s.to_lowercase()).as_deref() {
+                Some("gen") => match dance.generate() {
+                    Ok(report) => LlmResponse::Text(report),
+                    Err(e) => LlmResponse::Error(e),
+                },
+                Some("share") => {
+                    if parts.len() < 3 {
+                        LlmResponse::Text("Usage: /dance share <i>".to_string())
+                    } else {
+                        match parts[2].parse::<usize>() {
+                            Ok(i) => match dance.share(i) {
+                                Ok(share) => LlmResponse::Text(format!("share[{}]: {}", i, share)),
+                                Err(e) => LlmResponse::Error(e),
+                            },
+                            Err(_) => LlmResponse::Text("Usage: /dance share <i>".to_string()),
+                        }
+                    }
+                }
+                Some("sign") => {
+                    if parts.len() < 3 {
+                        LlmResponse::Text("Usage: /dance sign <msg>".to_string())
+                    } else {
+                        let message = parts[2..].join(" ");
+                        match dance.sign(&message) {
+                            Ok(sig) => LlmResponse::Text(format!("Signature: {}", sig)),
+                            Err(e) => LlmResponse::Error(e),
+                        }
+                    }
+                }
+                Some("verify") => {
+                    if parts.len() < 5 {
+                        LlmResponse::Text("Usage: /dance verify <pub> <sig> <msg>".to_string())
+                    } else {
+                        let message = parts[4..].join(" ");
+                        match dance::verify(parts[2], parts[3], message.as_bytes()) {
+                            Ok(true) => LlmResponse::Text("Signature valid.".to_string()),
+                            Ok(false) => LlmResponse::Text("Signature INVALID.".to_string()),
+                            Err(e) => LlmResponse::Error(e),
+                        }
+                    }
+                }
+                Some("recover") => {
+                    if parts.len() < 4 {
+                        LlmResponse::Text("Usage: /dance recover <sig> <msg>".to_string())
+                    } else {
+                        let message = parts[3..].join(" ");
+                        match dance::recover(parts[2], message.as_bytes()) {
+                            Ok((pubkey, address)) => LlmResponse::Text(format!(
+                                "Recovered public key: {}\nAddress: {}",
+                                pubkey, address
+                            )),
+                            Err(e) => LlmResponse::Error(e),
+                        }
+                    }
+                }
+                _ => {
+                    let authenticated = dance.toggle();
+                    LlmResponse::Text(format!(
+                        "Dance {}.\n{}",
+                        if authenticated { "engaged" } else { "disengaged" },
+                        dance.status()
+                    ))
+                }
+            }
         }
         _ => {
             LlmResponse::Text(format!(
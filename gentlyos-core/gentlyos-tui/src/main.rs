@@ -6,6 +6,7 @@
 mod app;
 mod boneblob;
 mod claude;  // Legacy, kept for reference
+mod dance;
 mod events;
 mod llm;
 mod security;